@@ -35,6 +35,24 @@ where
 }
 
 fn main() -> Result<(), GenericError> {
+    let enabled = |feature: &str| std::env::var(feature).is_ok();
+    if enabled("CARGO_FEATURE_KLU") || enabled("CARGO_FEATURE_UMFPACK") {
+        println!("cargo:rerun-if-env-changed=SUITESPARSE_LIB_DIR");
+        if let Ok(dir) = std::env::var("SUITESPARSE_LIB_DIR") {
+            println!("cargo:rustc-link-search=native={}", dir);
+        }
+    }
+    if enabled("CARGO_FEATURE_CUDA") {
+        println!("cargo:rerun-if-env-changed=CUDA_PATH");
+        if let Ok(dir) = std::env::var("CUDA_PATH") {
+            let lib = Path::new(&dir).join("lib64");
+            println!("cargo:rustc-link-search=native={}", lib.display());
+        }
+    }
+    // The Eigen solver is only part of builds with the `eigen` feature.
+    if std::env::var("CARGO_FEATURE_EIGEN").is_err() {
+        return Ok(());
+    }
     if !std::env::var("DOCS_RS").is_ok() {
         // ... your code here ...
 
@@ -52,8 +70,32 @@ fn main() -> Result<(), GenericError> {
             remove_file(eingen_tar_path)?;
         }
 
-        cc::Build::new()
-            .cpp(true)
+        let mut build = cc::Build::new();
+        build.cpp(true);
+        if std::env::var("CARGO_FEATURE_F64").is_ok() {
+            build.define("SPARSE_COMPLEX_F64", None);
+        }
+        if std::env::var("CARGO_FEATURE_F32").is_ok() {
+            build.define("SPARSE_COMPLEX_F32", None);
+        }
+        if enabled("CARGO_FEATURE_INDEX32") {
+            build.define("SPARSE_COMPLEX_INDEX32", None);
+        }
+        if enabled("CARGO_FEATURE_OPENMP") {
+            let compiler = build.get_compiler();
+            if compiler.is_like_msvc() {
+                build.flag("/openmp");
+            } else {
+                // The OpenMP runtime of the compiler, libgomp for GCC and libomp for Clang.
+                build.flag("-fopenmp");
+                if compiler.is_like_clang() {
+                    println!("cargo:rustc-link-lib=omp");
+                } else {
+                    println!("cargo:rustc-link-lib=gomp");
+                }
+            }
+        }
+        build
             .include(eigen_dir)
             .file("src/solver.cpp")
             .flag_if_supported("-std=c++1y")
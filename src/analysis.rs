@@ -0,0 +1,198 @@
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// An axis-aligned box in the complex plane that contains the whole spectrum of a matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectrumBounds<T: Float> {
+    pub re_min: T,
+    pub re_max: T,
+    pub im_min: T,
+    pub im_max: T,
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Returns the Gershgorin disc of each row as a ```(center, radius)``` pair.
+    ///
+    /// The center is the diagonal entry and the radius is the sum of the absolute
+    /// values of the off-diagonal entries of the row. Every eigenvalue lies in the
+    /// union of the discs. Duplicated entries are summed first.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(4., 1.));
+    /// m.add_element(0, 1, Complex::new(3., 4.));
+    /// m.add_element(1, 1, Complex::new(-2., 0.));
+    ///
+    /// let discs = m.gershgorin_discs();
+    /// assert_eq!(discs[0], (Complex::new(4., 1.), 5.));
+    /// assert_eq!(discs[1], (Complex::new(-2., 0.), 0.));
+    ///```
+    pub fn gershgorin_discs(&self) -> Vec<(Complex<T>, T)> {
+        let csr = self.to_csr();
+        (0..csr.n)
+            .map(|i| {
+                csr.row(i).fold(
+                    (Complex::new(T::zero(), T::zero()), T::zero()),
+                    |(center, radius), (j, value)| {
+                        if i == j {
+                            (*value, radius)
+                        } else {
+                            (center, radius + value.norm())
+                        }
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns a box bounding the union of the Gershgorin discs, or None for an empty matrix.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(4., 1.));
+    /// m.add_element(0, 1, Complex::new(3., 4.));
+    /// m.add_element(1, 1, Complex::new(-2., 0.));
+    ///
+    /// let bounds = m.spectrum_bounds().unwrap();
+    /// assert_eq!(bounds.re_min, -2.);
+    /// assert_eq!(bounds.re_max, 9.);
+    /// assert_eq!(bounds.im_min, -4.);
+    /// assert_eq!(bounds.im_max, 6.);
+    ///```
+    pub fn spectrum_bounds(&self) -> Option<SpectrumBounds<T>> {
+        self.gershgorin_discs()
+            .into_iter()
+            .map(|(c, r)| SpectrumBounds {
+                re_min: c.re - r,
+                re_max: c.re + r,
+                im_min: c.im - r,
+                im_max: c.im + r,
+            })
+            .reduce(|a, b| SpectrumBounds {
+                re_min: a.re_min.min(b.re_min),
+                re_max: a.re_max.max(b.re_max),
+                im_min: a.im_min.min(b.im_min),
+                im_max: a.im_max.max(b.im_max),
+            })
+    }
+
+    /// Estimate the spectral radius with power iteration.
+    ///
+    /// Each step applies the matrix to the current vector and takes the modulus of the
+    /// complex Rayleigh quotient. Iteration stops when two consecutive estimates differ
+    /// by less than ```tol``` relative to the estimate.
+    ///
+    /// Returns `Err` if the estimate did not converge within ```max_iter``` iterations, which
+    /// happens when several eigenvalues share the largest modulus.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(0., 3.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 1.));
+    ///
+    /// let rho = m.spectral_radius_estimate(1e-10, 1000).unwrap();
+    /// assert!((rho - 3.).abs() < 1e-6);
+    ///```
+    pub fn spectral_radius_estimate(&self, tol: T, max_iter: usize) -> Result<T, SolveError> {
+        let csr = self.to_csr();
+        if csr.n == 0 {
+            return Ok(T::zero());
+        }
+
+        // A start vector with varying phases is unlikely to be orthogonal to the
+        // dominant eigenvector.
+        let half = T::from(0.5).unwrap();
+        let mut x = (0..csr.n)
+            .map(|i| Complex::new(T::one(), half * T::from(i % 7).unwrap()))
+            .collect::<Vec<_>>();
+        normalize(&mut x);
+
+        let mut estimate = T::zero();
+        for _ in 0..max_iter {
+            let mut y = csr.mul_vec(&x);
+            let rayleigh = x
+                .iter()
+                .zip(y.iter())
+                .fold(Complex::new(T::zero(), T::zero()), |acc, (xi, yi)| {
+                    acc + xi.conj() * yi
+                });
+            let next = rayleigh.norm();
+            if normalize(&mut y) == T::zero() {
+                return Ok(T::zero());
+            }
+            if (next - estimate).abs() <= tol * next {
+                return Ok(next);
+            }
+            estimate = next;
+            x = y;
+        }
+        Err(SolveError::NoConvergence)
+    }
+
+    /// Returns the diagonal dominance margin of each row, ```|a_ii| - sum_{j != i} |a_ij|```.
+    ///
+    /// A negative margin flags a row that is not diagonally dominant, which often
+    /// points to a sign error in the assembly of that row.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(3., 4.));
+    /// m.add_element(0, 1, Complex::new(-2., 0.));
+    /// m.add_element(1, 0, Complex::new(-2., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// assert_eq!(m.row_dominance_margins(), vec![3., -1.]);
+    ///```
+    pub fn row_dominance_margins(&self) -> Vec<T> {
+        self.gershgorin_discs()
+            .into_iter()
+            .map(|(center, radius)| center.norm() - radius)
+            .collect()
+    }
+
+    /// Returns `true` if every row is (weakly or strictly) diagonally dominant.
+    ///
+    /// A strictly diagonally dominant matrix is nonsingular and is a complex
+    /// H-matrix, so LU without pivoting and Jacobi-type iterations are stable on it.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(0., -2.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// assert!(m.is_diagonally_dominant(false));
+    /// assert!(!m.is_diagonally_dominant(true));
+    ///```
+    pub fn is_diagonally_dominant(&self, strict: bool) -> bool {
+        self.row_dominance_margins().into_iter().all(|margin| {
+            if strict {
+                margin > T::zero()
+            } else {
+                margin >= T::zero()
+            }
+        })
+    }
+}
+
+/// Scales ```x``` to unit 2-norm and returns its previous norm.
+fn normalize<T: Float>(x: &mut [Complex<T>]) -> T {
+    let norm = x.iter().fold(T::zero(), |acc, v| acc + v.norm_sqr()).sqrt();
+    if norm > T::zero() {
+        x.iter_mut().for_each(|v| *v = *v / norm);
+    }
+    norm
+}
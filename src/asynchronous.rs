@@ -0,0 +1,91 @@
+use crate::cancel;
+use crate::{ComplexMatrix, SolvableFloat, SolveError};
+use num::complex::Complex;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Solution<T> = Result<Vec<Complex<T>>, SolveError>;
+
+/// The state shared by a ```SolveFuture``` and the thread solving the system.
+struct Shared<T> {
+    result: Option<thread::Result<Solution<T>>>,
+    waker: Option<Waker>,
+}
+
+/// The solution of a system solved off the calling thread, returned by
+/// ```ComplexMatrix::solve_async```.
+///
+/// The future does not depend on any async runtime: the solve starts right away and
+/// the task awaiting the future is woken when it ends. A panic of the solver is
+/// resumed in the awaiting task.
+pub struct SolveFuture<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Future for SolveFuture<T> {
+    type Output = Solution<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T: SolvableFloat + Send + Sync + 'static> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` without blocking the calling thread, e.g. the reactor of
+    /// an async service, and return the solution through a future.
+    ///
+    /// The matrix is copied and solved by ```solve``` on the rayon thread pool with the
+    /// `rayon` feature, or on a thread of its own otherwise. Dropping the future does not
+    /// stop the solve; call it under ```with_cancellation``` to be able to abort it.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SolveError};
+    /// use num::Complex;
+    ///
+    /// async fn node_voltages(
+    ///     admittance: &ComplexMatrix<f64>,
+    ///     currents: Vec<Complex<f64>>,
+    /// ) -> Result<Vec<Complex<f64>>, SolveError> {
+    ///     admittance.solve_async(currents).await
+    /// }
+    ///```
+    pub fn solve_async(&self, b: Vec<Complex<T>>) -> SolveFuture<T> {
+        let shared = Arc::new(Mutex::new(Shared {
+            result: None,
+            waker: None,
+        }));
+        let worker = Arc::clone(&shared);
+        let matrix = self.clone();
+        let token = cancel::current();
+        let job = move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut x = b;
+                cancel::scoped(token, || matrix.solve(&mut x)).map(|_| x)
+            }));
+            let waker = {
+                let mut state = worker.lock().unwrap();
+                state.result = Some(result);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        };
+        #[cfg(feature = "rayon")]
+        rayon::spawn(job);
+        #[cfg(not(feature = "rayon"))]
+        thread::spawn(job);
+        SolveFuture { shared }
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::ComplexLu;
+use crate::{ComplexMatrix, SolveError, SparseLu};
+use alloc::boxed::Box;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A sparse direct solver engine, factorizing a ```ComplexMatrix``` into a
+/// ```Factorization``` that solves for any number of right-hand sides.
+///
+/// Each engine is a unit struct compiled in by its feature, e.g. ```EigenBackend```
+/// with `eigen`; ```RustBackend``` is always available. Implement this trait to plug
+/// another solver, and pass the engine to ```ComplexMatrix::solve_with_backend``` to
+/// select it at runtime.
+pub trait SolverBackend<T: Float> {
+    /// A short name of the engine, e.g. for logs.
+    fn name(&self) -> &'static str;
+
+    /// Factorize the matrix.
+    ///
+    /// Returns `Err` if the matrix is singular.
+    fn factorize(&self, matrix: &ComplexMatrix<T>) -> Result<Box<dyn Factorization<T>>, SolveError>;
+}
+
+/// A factorization computed by a ```SolverBackend```.
+pub trait Factorization<T: Float> {
+    /// The order of the factorized matrix.
+    fn size(&self) -> usize;
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError>;
+
+    /// Solve the system `Aᴴx=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    fn solve_adjoint(&self, b: &mut [Complex<T>]) -> Result<(), SolveError>;
+}
+
+/// The pure-Rust engine, factorizing with ```ComplexMatrix::factorize_sparse```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RustBackend;
+
+impl<T: Float + 'static> SolverBackend<T> for RustBackend {
+    fn name(&self) -> &'static str {
+        "Rust SparseLu"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<T>) -> Result<Box<dyn Factorization<T>>, SolveError> {
+        Ok(Box::new(matrix.factorize_sparse()?))
+    }
+}
+
+impl<T: Float> Factorization<T> for SparseLu<T> {
+    fn size(&self) -> usize {
+        SparseLu::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        SparseLu::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        SparseLu::solve_adjoint(self, b)
+    }
+}
+
+/// The Eigen engine, factorizing with ```ComplexMatrix::factorize```.
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EigenBackend;
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl SolverBackend<f64> for EigenBackend {
+    fn name(&self) -> &'static str {
+        "Eigen SparseLU"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<f64>) -> Result<Box<dyn Factorization<f64>>, SolveError> {
+        Ok(Box::new(matrix.factorize()?))
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl Factorization<f64> for ComplexLu<f64> {
+    fn size(&self) -> usize {
+        ComplexLu::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        ComplexLu::<f64>::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        ComplexLu::<f64>::solve_adjoint(self, b)
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl SolverBackend<f32> for EigenBackend {
+    fn name(&self) -> &'static str {
+        "Eigen SparseLU"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<f32>) -> Result<Box<dyn Factorization<f32>>, SolveError> {
+        Ok(Box::new(matrix.factorize()?))
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl Factorization<f32> for ComplexLu<f32> {
+    fn size(&self) -> usize {
+        ComplexLu::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+        ComplexLu::<f32>::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+        ComplexLu::<f32>::solve_adjoint(self, b)
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` with the given engine. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if the matrix is singular or ```b``` does not match its size.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, RustBackend, SolverBackend};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let backend: &dyn SolverBackend<f64> = &RustBackend;
+    /// let mut b = vec![Complex::new(2., 1.), Complex::new(1., 0.)];
+    /// m.solve_with_backend(backend, &mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn solve_with_backend(&self, backend: &dyn SolverBackend<T>, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        backend.factorize(self)?.solve(b)
+    }
+}
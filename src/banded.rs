@@ -0,0 +1,268 @@
+use crate::index::AsIndex;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+static BANDED_SOLVE_BANDWIDTH: AtomicUsize = AtomicUsize::new(2);
+
+/// Sets the largest bandwidth of the matrices solved by ```solve``` with a banded LU
+/// in pure Rust.
+///
+/// Matrices whose lower and upper bandwidths are both at most ```bandwidth``` skip the
+/// sparse solver, e.g. tridiagonal and pentadiagonal systems of transmission lines
+/// and 1D discretizations. The default is 2; 0 restricts the banded path to diagonal
+/// matrices.
+///```rust
+/// sparse_complex::set_banded_solve_bandwidth(4);
+/// assert_eq!(sparse_complex::banded_solve_bandwidth(), 4);
+/// # sparse_complex::set_banded_solve_bandwidth(2);
+///```
+pub fn set_banded_solve_bandwidth(bandwidth: usize) {
+    BANDED_SOLVE_BANDWIDTH.store(bandwidth, Ordering::Relaxed);
+}
+
+/// The largest bandwidth solved with a banded LU, see ```set_banded_solve_bandwidth```.
+pub fn banded_solve_bandwidth() -> usize {
+    BANDED_SOLVE_BANDWIDTH.load(Ordering::Relaxed)
+}
+
+/// A square matrix whose nonzeros lie within ```lower``` diagonals below and ```upper```
+/// diagonals above the main diagonal, solved by LU with partial pivoting in
+/// `O(n·lower·(lower + upper))` operations.
+///
+/// The band is stored by columns with room for the fill-in of the row interchanges,
+/// i.e. `n·(2·lower + upper + 1)` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BandedMatrix<T: Float> {
+    n: usize,
+    lower: usize,
+    upper: usize,
+    values: Vec<Complex<T>>,
+}
+
+impl<T: Float> BandedMatrix<T> {
+    /// Create a zero ```n × n``` matrix with the given bandwidths, e.g. 1 and 1 for a
+    /// tridiagonal matrix.
+    ///```rust
+    /// use sparse_complex::BandedMatrix;
+    /// use num::Complex;
+    ///
+    /// let n = 5;
+    /// let mut m = BandedMatrix::<f64>::new(n, 1, 1);
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(2., 0.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///         m.add_element(i + 1, i, Complex::new(-1., 0.));
+    ///     }
+    /// }
+    ///
+    /// let mut b = vec![Complex::new(0., 0.); n];
+    /// b[0] = Complex::new(6., 0.);
+    /// m.solve(&mut b).unwrap();
+    /// assert!((b[4] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn new(n: usize, lower: usize, upper: usize) -> Self {
+        let width = 2 * lower + upper + 1;
+        BandedMatrix {
+            n,
+            lower,
+            upper,
+            values: vec![Complex::new(T::zero(), T::zero()); n * width],
+        }
+    }
+
+    /// Copy a ```ComplexMatrix``` into a banded matrix of its own bandwidths.
+    pub fn from_matrix(matrix: &ComplexMatrix<T>) -> Self {
+        let (lower, upper) = matrix.bandwidths();
+        let mut banded = BandedMatrix::new(matrix.dimension(), lower, upper);
+        let elements = matrix
+            .rows
+            .iter()
+            .zip(matrix.cols.iter())
+            .zip(matrix.entries.iter());
+        for ((row, col), value) in elements {
+            banded.add_element(row.index(), col.index(), *value);
+        }
+        banded
+    }
+
+    /// The order of the matrix.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// The lower and upper bandwidths.
+    pub fn bandwidths(&self) -> (usize, usize) {
+        (self.lower, self.upper)
+    }
+
+    /// Add ```value``` to the element at ```(row, col)```.
+    ///
+    /// Panics if the element is out of the matrix or of its band.
+    pub fn add_element(&mut self, row: usize, col: usize, value: Complex<T>) {
+        assert!(
+            row < self.n && col < self.n,
+            "the element is out of the matrix"
+        );
+        assert!(
+            row <= col + self.lower && col <= row + self.upper,
+            "the element ({}, {}) is out of the band",
+            row,
+            col
+        );
+        let k = self.index(row, col);
+        self.values[k] = self.values[k] + value;
+    }
+
+    /// The element at ```(row, col)```, zero outside the band.
+    pub fn get(&self, row: usize, col: usize) -> Complex<T> {
+        if row < self.n && col < self.n && row <= col + self.lower && col <= row + self.upper {
+            self.values[self.index(row, col)]
+        } else {
+            Complex::new(T::zero(), T::zero())
+        }
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is singular.
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        if b.len() != self.n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let mut lu = self.clone();
+        let pivots = lu.factorize()?;
+        lu.solve_factorized(&pivots, b);
+        Ok(())
+    }
+
+    /// The position of ```(row, col)``` in the column-major band, for
+    /// `col - upper - lower <= row <= col + lower`.
+    fn index(&self, row: usize, col: usize) -> usize {
+        col * (2 * self.lower + self.upper + 1) + row + self.upper + self.lower - col
+    }
+
+    /// The last row of column ```k``` below the diagonal and the last column of row
+    /// ```k``` of `U`.
+    fn reach(&self, k: usize) -> (usize, usize) {
+        let last = self.n - 1;
+        (
+            (k + self.lower).min(last),
+            (k + self.lower + self.upper).min(last),
+        )
+    }
+
+    /// Factorize in place into `PA = LU` and return the row interchanges.
+    fn factorize(&mut self) -> Result<Vec<usize>, SolveError> {
+        let mut pivots = Vec::with_capacity(self.n);
+        for k in 0..self.n {
+            let (last_row, last_col) = self.reach(k);
+            let mut pivot = k;
+            let mut max = self.values[self.index(k, k)].norm();
+            for i in k + 1..=last_row {
+                let candidate = self.values[self.index(i, k)].norm();
+                if candidate > max {
+                    pivot = i;
+                    max = candidate;
+                }
+            }
+            if max == T::zero() || max.is_nan() {
+                return Err(SolveError::Singular);
+            }
+            pivots.push(pivot);
+            if pivot != k {
+                for j in k..=last_col {
+                    let (a, b) = (self.index(k, j), self.index(pivot, j));
+                    self.values.swap(a, b);
+                }
+            }
+
+            let diagonal = self.values[self.index(k, k)];
+            for i in k + 1..=last_row {
+                let p = self.index(i, k);
+                self.values[p] = self.values[p] / diagonal;
+            }
+            for j in k + 1..=last_col {
+                let akj = self.values[self.index(k, j)];
+                if akj.norm() == T::zero() {
+                    continue;
+                }
+                for i in k + 1..=last_row {
+                    let (p, l) = (self.index(i, j), self.values[self.index(i, k)]);
+                    self.values[p] = self.values[p] - l * akj;
+                }
+            }
+        }
+        Ok(pivots)
+    }
+
+    fn solve_factorized(&self, pivots: &[usize], b: &mut [Complex<T>]) {
+        for (k, pivot) in pivots.iter().enumerate() {
+            b.swap(k, *pivot);
+            let (last_row, _) = self.reach(k);
+            for i in k + 1..=last_row {
+                b[i] = b[i] - self.values[self.index(i, k)] * b[k];
+            }
+        }
+        for k in (0..self.n).rev() {
+            let (_, last_col) = self.reach(k);
+            let sum =
+                (k + 1..=last_col).fold(b[k], |acc, j| acc - self.values[self.index(k, j)] * b[j]);
+            b[k] = sum / self.values[self.index(k, k)];
+        }
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Returns the lower and upper bandwidths: the largest ```row - col``` and
+    /// ```col - row``` of the stored elements.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(3, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 2, Complex::new(1., 0.));
+    ///
+    /// assert_eq!(m.bandwidths(), (2, 1));
+    ///```
+    pub fn bandwidths(&self) -> (usize, usize) {
+        self.rows
+            .iter()
+            .zip(self.cols.iter())
+            .map(|(r, c)| (r.index(), c.index()))
+            .fold((0, 0), |(lower, upper), (r, c)| {
+                (
+                    lower.max(r.saturating_sub(c)),
+                    upper.max(c.saturating_sub(r)),
+                )
+            })
+    }
+
+    /// Solves `Ax=b` with a banded LU if both bandwidths are within the banded
+    /// threshold, or returns None to let the caller use the sparse solver.
+    #[cfg(any(feature = "f64", feature = "f32"))]
+    pub(crate) fn try_solve_banded(&self, b: &mut [Complex<T>]) -> Option<Result<(), SolveError>> {
+        let n = b.len();
+        let (lower, upper) = self.bandwidths();
+        let bandwidth = banded_solve_bandwidth();
+        if lower > bandwidth || upper > bandwidth || self.dimension() > n {
+            return None;
+        }
+        let mut banded = BandedMatrix::new(n, lower, upper);
+        let elements = self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            banded.add_element(row.index(), col.index(), *value);
+        }
+        Some(banded.solve(b))
+    }
+}
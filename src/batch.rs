@@ -0,0 +1,218 @@
+use crate::cancel::Watcher;
+use crate::progress::{Counter, Phase};
+#[cfg(all(feature = "rayon", any(feature = "f64", feature = "f32")))]
+use crate::SolvableFloat;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A small dense system `Ax=b` solved by ```solve_batch_small```.
+///
+/// Each system keeps its matrix in a contiguous row-major array, so the elimination
+/// runs over contiguous rows that the compiler can vectorize.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmallSystem<T: Float> {
+    n: usize,
+    matrix: Vec<Complex<T>>,
+    rhs: Vec<Complex<T>>,
+}
+
+impl<T: Float> SmallSystem<T> {
+    /// Create a system from a row-major ```n × n``` matrix and its right-hand side.
+    ///
+    /// Panics if the lengths do not match ```n```.
+    ///```rust
+    /// use sparse_complex::SmallSystem;
+    /// use num::Complex;
+    ///
+    /// let one = Complex::new(1., 0.);
+    /// let zero = Complex::new(0., 0.);
+    /// let system = SmallSystem::<f64>::new(2, vec![one, zero, zero, one], vec![one, one]);
+    ///```
+    pub fn new(n: usize, matrix: Vec<Complex<T>>, rhs: Vec<Complex<T>>) -> Self {
+        assert_eq!(matrix.len(), n * n, "the matrix must have n * n elements");
+        assert_eq!(rhs.len(), n, "the right-hand side must have n elements");
+        SmallSystem { n, matrix, rhs }
+    }
+
+    /// Create a system from a sparse matrix, summing duplicated entries.
+    ///
+    /// Panics if the matrix has an index beyond the length of ```rhs```.
+    pub fn from_matrix(matrix: &ComplexMatrix<T>, rhs: Vec<Complex<T>>) -> Self {
+        let n = rhs.len();
+        assert!(matrix.dimension() <= n, "the matrix is larger than the right-hand side");
+        SmallSystem {
+            n,
+            matrix: matrix.to_dense(n),
+            rhs,
+        }
+    }
+
+    /// The right-hand side, which holds the solution once the system is solved.
+    pub fn rhs(&self) -> &[Complex<T>] {
+        &self.rhs
+    }
+
+    /// Solves the system in place with partial pivoting, without allocating. The
+    /// matrix is overwritten by its factors.
+    fn solve(&mut self) -> Result<(), SolveError> {
+        let n = self.n;
+        let (a, b) = (&mut self.matrix, &mut self.rhs);
+        for k in 0..n {
+            let mut pivot = k;
+            let mut max = a[k * n + k].norm();
+            for i in k + 1..n {
+                let candidate = a[i * n + k].norm();
+                if candidate > max {
+                    pivot = i;
+                    max = candidate;
+                }
+            }
+            if max == T::zero() || max.is_nan() {
+                return Err(SolveError::Singular);
+            }
+            if pivot != k {
+                for j in k..n {
+                    a.swap(k * n + j, pivot * n + j);
+                }
+                b.swap(k, pivot);
+            }
+
+            let (upper, lower) = a.split_at_mut((k + 1) * n);
+            let pivot_row = &upper[k * n..];
+            for (offset, row) in lower.chunks_mut(n).enumerate() {
+                let factor = row[k] / pivot_row[k];
+                for (rj, pj) in row[k + 1..].iter_mut().zip(pivot_row[k + 1..].iter()) {
+                    *rj = *rj - factor * *pj;
+                }
+                b[k + 1 + offset] = b[k + 1 + offset] - factor * b[k];
+            }
+        }
+        for i in (0..n).rev() {
+            let row = &a[i * n..(i + 1) * n];
+            let sum = row[i + 1..]
+                .iter()
+                .zip(b[i + 1..].iter())
+                .fold(b[i], |acc, (u, xj)| acc - *u * *xj);
+            b[i] = sum / row[i];
+        }
+        Ok(())
+    }
+}
+
+/// Solves many small independent systems, storing each solution in its right-hand side.
+///
+/// The systems are distributed over ```num_threads()``` threads when the `rayon`
+/// feature is enabled.
+/// Returns the status of each system; a singular system does not stop the others.
+/// The matrices are overwritten by their LU factors.
+///```rust
+/// use sparse_complex::{solve_batch_small, SmallSystem};
+/// use num::Complex;
+///
+/// let mut systems = (1..100)
+///     .map(|k| {
+///         let d = Complex::new(k as f64, 1.);
+///         let zero = Complex::new(0., 0.);
+///         SmallSystem::new(2, vec![d, zero, zero, d], vec![d, d * 2.])
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let status = solve_batch_small(&mut systems);
+/// assert!(status.iter().all(|s| s.is_ok()));
+/// assert_eq!(systems[5].rhs(), &[Complex::new(1., 0.), Complex::new(2., 0.)]);
+///```
+#[cfg(feature = "rayon")]
+pub fn solve_batch_small<T: Float + Send + Sync>(systems: &mut [SmallSystem<T>]) -> Vec<Result<(), SolveError>> {
+    use rayon::prelude::*;
+    let counter = Counter::new(Phase::Batch, systems.len());
+    let watcher = Watcher::new();
+    crate::threads::run(|| {
+        systems
+            .par_iter_mut()
+            .map(|system| {
+                let result = watcher.check().and_then(|_| system.solve());
+                counter.tick();
+                result
+            })
+            .collect()
+    })
+}
+
+/// Solves many small independent systems, storing each solution in its right-hand side.
+///
+/// The systems are distributed over ```num_threads()``` threads when the `rayon`
+/// feature is enabled.
+/// Returns the status of each system; a singular system does not stop the others.
+/// The matrices are overwritten by their LU factors.
+///```rust
+/// use sparse_complex::{solve_batch_small, SmallSystem};
+/// use num::Complex;
+///
+/// let mut systems = (1..100)
+///     .map(|k| {
+///         let d = Complex::new(k as f64, 1.);
+///         let zero = Complex::new(0., 0.);
+///         SmallSystem::new(2, vec![d, zero, zero, d], vec![d, d * 2.])
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let status = solve_batch_small(&mut systems);
+/// assert!(status.iter().all(|s| s.is_ok()));
+/// assert_eq!(systems[5].rhs(), &[Complex::new(1., 0.), Complex::new(2., 0.)]);
+///```
+#[cfg(not(feature = "rayon"))]
+pub fn solve_batch_small<T: Float>(systems: &mut [SmallSystem<T>]) -> Vec<Result<(), SolveError>> {
+    let counter = Counter::new(Phase::Batch, systems.len());
+    let watcher = Watcher::new();
+    systems
+        .iter_mut()
+        .map(|system| {
+            let result = watcher.check().and_then(|_| system.solve());
+            counter.tick();
+            result
+        })
+        .collect()
+}
+
+/// Factorizes and solves many independent sparse systems `Ax=b` in parallel, e.g.
+/// the samples of a Monte-Carlo or corner analysis, and returns their solutions.
+///
+/// The systems are distributed over ```num_threads()``` threads, each solved by
+/// ```ComplexMatrix::solve```. Returns the status of each system; a singular system
+/// does not stop the others.
+///```rust
+/// use sparse_complex::{solve_batch_par, ComplexMatrix};
+/// use num::Complex;
+///
+/// let systems = (1..100)
+///     .map(|k| {
+///         let mut m = ComplexMatrix::<f64>::new();
+///         m.add_element(0, 0, Complex::new(k as f64, 0.));
+///         m.add_element(1, 1, Complex::new(0., 1.));
+///         (m, vec![Complex::new(k as f64, 0.), Complex::new(0., 2.)])
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let solutions = solve_batch_par(&systems);
+/// assert_eq!(solutions[5], Ok(vec![Complex::new(1., 0.), Complex::new(2., 0.)]));
+///```
+#[cfg(all(feature = "rayon", any(feature = "f64", feature = "f32")))]
+pub fn solve_batch_par<T: SolvableFloat + Send + Sync>(
+    systems: &[(ComplexMatrix<T>, Vec<Complex<T>>)],
+) -> Vec<Result<Vec<Complex<T>>, SolveError>> {
+    use rayon::prelude::*;
+    let counter = Counter::new(Phase::Batch, systems.len());
+    let token = crate::cancel::current();
+    crate::threads::run(|| {
+        systems
+            .par_iter()
+            .map(|(matrix, rhs)| {
+                let mut x = rhs.clone();
+                let result = crate::cancel::scoped(token.clone(), || matrix.solve(&mut x)).map(|_| x);
+                counter.tick();
+                result
+            })
+            .collect()
+    })
+}
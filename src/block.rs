@@ -0,0 +1,185 @@
+use crate::ComplexMatrix;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A block sparse (BSR) complex matrix with a fixed block size.
+///
+/// Each stored entry is a dense `block_size × block_size` block, kept in row-major
+/// order. This matches how coupled-phase and dq-frame models are assembled and only
+/// stores one index pair per block.
+#[derive(Clone, PartialEq)]
+pub struct BlockMatrix<T: Float> {
+    block_size: usize,
+    blocks: Vec<Complex<T>>,
+    block_rows: Vec<usize>,
+    block_cols: Vec<usize>,
+}
+
+impl<T: Float> BlockMatrix<T> {
+    /// Create a new, initially empty ```BlockMatrix``` with blocks of ```block_size × block_size```.
+    ///
+    /// # Panics
+    /// Panics if ```block_size``` is zero.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// let m = BlockMatrix::<f64>::new(3);
+    /// assert_eq!(m.block_size(), 3);
+    ///```
+    pub fn new(block_size: usize) -> Self {
+        Self::with_capacity(block_size, 0)
+    }
+
+    /// Create a new, initially empty ```BlockMatrix``` with room for ```capacity``` blocks.
+    ///
+    /// # Panics
+    /// Panics if ```block_size``` is zero.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// let m = BlockMatrix::<f64>::with_capacity(2, 10);
+    ///```
+    pub fn with_capacity(block_size: usize, capacity: usize) -> Self {
+        assert!(block_size > 0, "block size must be greater than zero");
+        BlockMatrix {
+            block_size,
+            blocks: Vec::with_capacity(capacity * block_size * block_size),
+            block_rows: Vec::with_capacity(capacity),
+            block_cols: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the size of the square blocks.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the number of stored blocks.
+    pub fn num_blocks(&self) -> usize {
+        self.block_rows.len()
+    }
+
+    /// Add a dense block at block location ```(block_row, block_col)```.
+    ///
+    /// The block is given in row-major order. Blocks added twice at the same location
+    /// are summed when converted to scalar form.
+    ///
+    /// # Panics
+    /// Panics if ```block.len()``` is not ```block_size * block_size```.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = BlockMatrix::<f64>::new(2);
+    /// let one = Complex::new(1., 0.);
+    /// let zero = Complex::new(0., 0.);
+    /// m.add_block(1, 1, &[one, zero, zero, one]);
+    ///
+    /// assert_eq!(m.get(3, 3), Some(&one));
+    ///```
+    pub fn add_block(&mut self, block_row: usize, block_col: usize, block: &[Complex<T>]) {
+        assert_eq!(
+            block.len(),
+            self.block_size * self.block_size,
+            "block must have block_size * block_size entries"
+        );
+        self.blocks.extend_from_slice(block);
+        self.block_rows.push(block_row);
+        self.block_cols.push(block_col);
+    }
+
+    /// Returns the block at ```(block_row, block_col)``` in row-major order if present, or None if not.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = BlockMatrix::<f64>::new(1);
+    /// m.add_block(0, 2, &[Complex::new(4., 1.)]);
+    ///
+    /// assert_eq!(m.get_block(0, 2), Some(&[Complex::new(4., 1.)][..]));
+    /// assert_eq!(m.get_block(2, 0), None);
+    ///```
+    pub fn get_block(&self, block_row: usize, block_col: usize) -> Option<&[Complex<T>]> {
+        let len = self.block_size * self.block_size;
+        self.block_rows
+            .iter()
+            .zip(self.block_cols.iter())
+            .position(|(r, c)| *r == block_row && *c == block_col)
+            .map(|k| &self.blocks[k * len..(k + 1) * len])
+    }
+
+    /// Returns the scalar element at ```(row, col)``` if its block is present, or None if not.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = BlockMatrix::<f64>::new(2);
+    /// let block = [
+    ///     Complex::new(1., 0.), Complex::new(2., 0.),
+    ///     Complex::new(3., 0.), Complex::new(4., 0.),
+    /// ];
+    /// m.add_block(0, 1, &block);
+    ///
+    /// assert_eq!(m.get(1, 2), Some(&Complex::new(3., 0.)));
+    /// assert_eq!(m.get(0, 0), None);
+    ///```
+    pub fn get(&self, row: usize, col: usize) -> Option<&Complex<T>> {
+        let bs = self.block_size;
+        self.get_block(row / bs, col / bs)
+            .map(|block| &block[(row % bs) * bs + col % bs])
+    }
+
+    /// Convert to a scalar ```ComplexMatrix```, ready to be solved.
+    ///
+    /// Every entry of every block is emitted, including explicit zeros, so the
+    /// sparsity pattern of the result does not depend on the block values.
+    ///```rust
+    /// use sparse_complex::BlockMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = BlockMatrix::<f64>::new(2);
+    /// let z = Complex::new(1., -1.);
+    /// let zero = Complex::new(0., 0.);
+    /// m.add_block(0, 0, &[z, zero, zero, z]);
+    ///
+    /// let scalar = m.to_complex_matrix();
+    /// assert_eq!(scalar.get(1, 1), Some(&z));
+    /// assert_eq!(scalar.get(0, 1), Some(&zero));
+    ///```
+    pub fn to_complex_matrix(&self) -> ComplexMatrix<T> {
+        let bs = self.block_size;
+        let mut m = ComplexMatrix::with_capacity(self.blocks.len());
+        let block_indices = self.block_rows.iter().zip(self.block_cols.iter());
+        for ((block_row, block_col), block) in block_indices.zip(self.blocks.chunks(bs * bs)) {
+            for (k, value) in block.iter().enumerate() {
+                m.add_element(block_row * bs + k / bs, block_col * bs + k % bs, *value);
+            }
+        }
+        m
+    }
+}
+
+impl<T: Float> From<BlockMatrix<T>> for ComplexMatrix<T> {
+    fn from(m: BlockMatrix<T>) -> Self {
+        m.to_complex_matrix()
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Debug for BlockMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bs = self.block_size;
+        let mut msg = format!("BlockMatrix ({}x{} blocks) {{ \n", bs, bs);
+        let block_indices = self.block_rows.iter().zip(self.block_cols.iter());
+        for ((row, col), block) in block_indices.zip(self.blocks.chunks(bs * bs)) {
+            let values = block
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            msg = format!("{}  [{},{}] -> [{}]\n", msg, row, col, values)
+        }
+        write!(f, "{}}}", msg)
+    }
+}
@@ -0,0 +1,276 @@
+use crate::dense::DenseLu;
+use crate::SolveError;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A block-tridiagonal matrix of ```blocks × blocks``` dense square blocks, optionally
+/// periodic, i.e. with the corner blocks coupling the first and last block rows.
+///
+/// Layered media, cascaded two-ports and 1D problems with several unknowns per node
+/// have this structure. ```solve``` runs a block LU in `O(blocks · block_size³)`
+/// operations without storing any fill outside the blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockTridiagonal<T: Float> {
+    block_size: usize,
+    periodic: bool,
+    lower: Vec<Vec<Complex<T>>>,
+    diagonal: Vec<Vec<Complex<T>>>,
+    upper: Vec<Vec<Complex<T>>>,
+}
+
+impl<T: Float> BlockTridiagonal<T> {
+    /// Create a zero block-tridiagonal matrix.
+    ///
+    /// # Panics
+    /// Panics if ```block_size``` is zero.
+    ///```rust
+    /// use sparse_complex::BlockTridiagonal;
+    /// use num::Complex;
+    ///
+    /// let (one, zero) = (Complex::new(1., 0.), Complex::new(0., 0.));
+    /// let mut m = BlockTridiagonal::<f64>::new(3, 2);
+    /// for i in 0..3 {
+    ///     m.set_diagonal(i, &[one * 4., one, one, one * 4.]);
+    ///     if i > 0 {
+    ///         m.set_lower(i, &[-one, zero, zero, -one]);
+    ///         m.set_upper(i - 1, &[-one, zero, zero, -one]);
+    ///     }
+    /// }
+    ///
+    /// let mut b = vec![one * 4., one * 4., one * 3., one * 3., one * 4., one * 4.];
+    /// m.solve(&mut b).unwrap();
+    /// assert!(b.iter().all(|x| (x - one).norm() < 1e-12));
+    ///```
+    pub fn new(blocks: usize, block_size: usize) -> Self {
+        assert!(block_size > 0, "the block size must be positive");
+        let zero = vec![Complex::new(T::zero(), T::zero()); block_size * block_size];
+        BlockTridiagonal {
+            block_size,
+            periodic: false,
+            lower: vec![zero.clone(); blocks],
+            diagonal: vec![zero.clone(); blocks],
+            upper: vec![zero; blocks],
+        }
+    }
+
+    /// Create a zero periodic block-tridiagonal matrix, whose ```set_lower(0, _)``` and
+    /// ```set_upper(blocks - 1, _)``` set the corner blocks.
+    ///
+    /// # Panics
+    /// Panics if ```block_size``` is zero.
+    pub fn periodic(blocks: usize, block_size: usize) -> Self {
+        BlockTridiagonal {
+            periodic: true,
+            ..Self::new(blocks, block_size)
+        }
+    }
+
+    /// Returns the size of the square blocks.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the number of block rows.
+    pub fn blocks(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    /// Returns whether the corner blocks couple the first and last block rows.
+    pub fn is_periodic(&self) -> bool {
+        self.periodic
+    }
+
+    /// Set the diagonal block of block row ```i```, given in row-major order.
+    ///
+    /// # Panics
+    /// Panics if ```i``` is out of bounds or the block does not have
+    /// ```block_size * block_size``` elements.
+    pub fn set_diagonal(&mut self, i: usize, block: &[Complex<T>]) {
+        let size = self.block_size;
+        Self::set(&mut self.diagonal[i], size, block);
+    }
+
+    /// Set the block of block row ```i``` coupling it to block ```i - 1```, or to the last
+    /// block for ```i = 0``` if the matrix is periodic.
+    ///
+    /// # Panics
+    /// Panics if ```i``` is out of bounds, if ```i = 0``` and the matrix is not periodic,
+    /// or if the block does not have ```block_size * block_size``` elements.
+    pub fn set_lower(&mut self, i: usize, block: &[Complex<T>]) {
+        assert!(
+            i > 0 || self.periodic,
+            "the first block row has no lower block"
+        );
+        let size = self.block_size;
+        Self::set(&mut self.lower[i], size, block);
+    }
+
+    /// Set the block of block row ```i``` coupling it to block ```i + 1```, or to the first
+    /// block for the last block row if the matrix is periodic.
+    ///
+    /// # Panics
+    /// Panics if ```i``` is out of bounds, if ```i``` is the last block row and the matrix
+    /// is not periodic, or if the block does not have ```block_size * block_size```
+    /// elements.
+    pub fn set_upper(&mut self, i: usize, block: &[Complex<T>]) {
+        assert!(
+            i + 1 < self.blocks() || self.periodic,
+            "the last block row has no upper block"
+        );
+        let size = self.block_size;
+        Self::set(&mut self.upper[i], size, block);
+    }
+
+    fn set(target: &mut [Complex<T>], size: usize, block: &[Complex<T>]) {
+        assert_eq!(
+            block.len(),
+            size * size,
+            "the block must have block_size * block_size elements"
+        );
+        target.copy_from_slice(block);
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// The block rows are eliminated in order with the last block row kept as a border,
+    /// which closes the cycle of a periodic matrix. No pivoting is done across blocks,
+    /// so a diagonal block of the eliminated matrix must not become singular, which
+    /// holds for block diagonally dominant matrices.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or a pivot block is
+    /// singular.
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let (n, s) = (self.blocks(), self.block_size);
+        if b.len() != n * s {
+            return Err(SolveError::DimensionMismatch);
+        }
+        if n <= 2 {
+            return self.solve_dense(b);
+        }
+        let zero = vec![Complex::new(T::zero(), T::zero()); s * s];
+        let last = n - 1;
+        // The corner blocks are zero unless the matrix is periodic.
+        let (corner_lower, corner_upper) = if self.periodic {
+            (&self.lower[0], &self.upper[last])
+        } else {
+            (&zero, &zero)
+        };
+
+        // Eliminated row i keeps its pivot inverse and its border block towards x_{last},
+        // into which the block of row last - 1 towards x_{last} is folded. The spike is
+        // the block of the last row towards the next block to eliminate.
+        let mut inverses = Vec::with_capacity(last);
+        let mut borders = Vec::with_capacity(last);
+        let mut pivot = self.diagonal[0].clone();
+        let mut border = corner_lower.clone();
+        let mut spike = corner_upper.clone();
+        let mut last_pivot = self.diagonal[last].clone();
+        for i in 0..last {
+            let inverse = DenseLu::new(s, mem::take(&mut pivot))?.inverse();
+            let rhs = b[i * s..(i + 1) * s].to_vec();
+            let upper = if i + 1 < last { &self.upper[i] } else { &zero };
+
+            // Eliminate x_i from the last row.
+            let k = product(s, &spike, &inverse);
+            subtract(&mut last_pivot, &product(s, &k, &border));
+            subtract(&mut b[last * s..], &apply(s, &k, &rhs));
+            if i + 1 < last {
+                spike = if i + 2 == last {
+                    self.lower[last].clone()
+                } else {
+                    zero.clone()
+                };
+                subtract(&mut spike, &product(s, &k, upper));
+            }
+
+            // Eliminate x_i from row i + 1.
+            if i + 1 < last {
+                let m = product(s, &self.lower[i + 1], &inverse);
+                pivot = self.diagonal[i + 1].clone();
+                subtract(&mut pivot, &product(s, &m, upper));
+                let mut next_border = if i + 2 == last {
+                    self.upper[i + 1].clone()
+                } else {
+                    zero.clone()
+                };
+                subtract(&mut next_border, &product(s, &m, &border));
+                subtract(&mut b[(i + 1) * s..(i + 2) * s], &apply(s, &m, &rhs));
+                borders.push(border);
+                border = next_border;
+            } else {
+                borders.push(border.clone());
+            }
+            inverses.push(inverse);
+        }
+
+        let mut x_last = b[last * s..].to_vec();
+        DenseLu::new(s, last_pivot)?.solve(&mut x_last);
+        b[last * s..].copy_from_slice(&x_last);
+        for i in (0..last).rev() {
+            let mut rhs = b[i * s..(i + 1) * s].to_vec();
+            subtract(&mut rhs, &apply(s, &borders[i], &x_last));
+            if i + 1 < last {
+                let next = b[(i + 1) * s..(i + 2) * s].to_vec();
+                subtract(&mut rhs, &apply(s, &self.upper[i], &next));
+            }
+            let x = apply(s, &inverses[i], &rhs);
+            b[i * s..(i + 1) * s].copy_from_slice(&x);
+        }
+        Ok(())
+    }
+
+    /// Solves a system of at most two block rows with a dense LU.
+    fn solve_dense(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let (n, s) = (self.blocks(), self.block_size);
+        let size = n * s;
+        let mut dense = vec![Complex::new(T::zero(), T::zero()); size * size];
+        let mut add = |bi: usize, bj: usize, block: &[Complex<T>]| {
+            for (k, v) in block.iter().enumerate() {
+                let (r, c) = (bi * s + k / s, bj * s + k % s);
+                dense[r * size + c] = dense[r * size + c] + *v;
+            }
+        };
+        for i in 0..n {
+            add(i, i, &self.diagonal[i]);
+            if i > 0 || self.periodic {
+                add(i, (i + n - 1) % n, &self.lower[i]);
+            }
+            if i + 1 < n || self.periodic {
+                add(i, (i + 1) % n, &self.upper[i]);
+            }
+        }
+        DenseLu::new(size, dense)?.solve(b);
+        Ok(())
+    }
+}
+
+/// The product of the row-major ```s × s``` blocks ```a``` and ```b```.
+fn product<T: Float>(s: usize, a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    let zero = Complex::new(T::zero(), T::zero());
+    (0..s * s)
+        .map(|k| (0..s).fold(zero, |acc, l| acc + a[(k / s) * s + l] * b[l * s + k % s]))
+        .collect()
+}
+
+/// The product of the row-major ```s × s``` block ```a``` and the vector ```x```.
+fn apply<T: Float>(s: usize, a: &[Complex<T>], x: &[Complex<T>]) -> Vec<Complex<T>> {
+    a.chunks(s)
+        .map(|row| {
+            row.iter()
+                .zip(x.iter())
+                .fold(Complex::new(T::zero(), T::zero()), |acc, (v, xj)| {
+                    acc + *v * *xj
+                })
+        })
+        .collect()
+}
+
+/// Computes `a -= b`.
+fn subtract<T: Float>(a: &mut [Complex<T>], b: &[Complex<T>]) {
+    for (ai, bi) in a.iter_mut().zip(b.iter()) {
+        *ai = *ai - *bi;
+    }
+}
@@ -0,0 +1,109 @@
+use crate::ComplexMatrix;
+use num_traits::float::Float;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+/// Assembles a ```ComplexMatrix``` from several threads without serializing every insertion.
+///
+/// Each thread fills its own ```MatrixShard``` without synchronization. The shard is
+/// handed back to the builder when dropped, and ```build``` concatenates all of them.
+/// Duplicated positions are summed by the solver as usual; their summation order
+/// depends on the order in which the shards were returned.
+pub struct ConcurrentMatrixBuilder<T: Float> {
+    shards: Mutex<Vec<ComplexMatrix<T>>>,
+}
+
+impl<T: Float> ConcurrentMatrixBuilder<T> {
+    /// Create a new builder without shards.
+    ///```rust
+    /// use sparse_complex::ConcurrentMatrixBuilder;
+    /// let builder = ConcurrentMatrixBuilder::<f64>::new();
+    ///```
+    pub fn new() -> Self {
+        ConcurrentMatrixBuilder {
+            shards: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Open a new shard with room for ```capacity``` elements.
+    ///
+    /// The shard dereferences to a ```ComplexMatrix```, so elements are added with the
+    /// usual insertion methods.
+    pub fn shard(&self, capacity: usize) -> MatrixShard<'_, T> {
+        MatrixShard {
+            builder: self,
+            matrix: ComplexMatrix::with_capacity(capacity),
+        }
+    }
+
+    /// Merge the returned shards into a single matrix.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, ConcurrentMatrixBuilder};
+    /// use num::Complex;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let builder = Arc::new(ConcurrentMatrixBuilder::<f64>::new());
+    /// let handles = (0..4)
+    ///     .map(|t| {
+    ///         let builder = Arc::clone(&builder);
+    ///         thread::spawn(move || {
+    ///             let mut shard = builder.shard(1);
+    ///             shard.add_element(t, t, Complex::new(1., 0.));
+    ///         })
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// for handle in handles {
+    ///     handle.join().unwrap();
+    /// }
+    ///
+    /// let m = Arc::try_unwrap(builder).ok().unwrap().build();
+    /// assert_eq!(m.get(3, 3), Some(&Complex::new(1., 0.)));
+    ///```
+    pub fn build(self) -> ComplexMatrix<T> {
+        let shards = self.shards.into_inner().unwrap_or_else(|e| e.into_inner());
+        let mut m = ComplexMatrix::with_capacity(shards.iter().map(|s| s.entries.len()).sum());
+        for shard in shards {
+            m.entries.extend_from_slice(&shard.entries);
+            m.rows.extend_from_slice(&shard.rows);
+            m.cols.extend_from_slice(&shard.cols);
+        }
+        m
+    }
+}
+
+impl<T: Float> Default for ConcurrentMatrixBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A thread-local part of a matrix assembled by a ```ConcurrentMatrixBuilder```.
+pub struct MatrixShard<'a, T: Float> {
+    builder: &'a ConcurrentMatrixBuilder<T>,
+    matrix: ComplexMatrix<T>,
+}
+
+impl<'a, T: Float> Deref for MatrixShard<'a, T> {
+    type Target = ComplexMatrix<T>;
+
+    fn deref(&self) -> &ComplexMatrix<T> {
+        &self.matrix
+    }
+}
+
+impl<'a, T: Float> DerefMut for MatrixShard<'a, T> {
+    fn deref_mut(&mut self) -> &mut ComplexMatrix<T> {
+        &mut self.matrix
+    }
+}
+
+impl<'a, T: Float> Drop for MatrixShard<'a, T> {
+    fn drop(&mut self) {
+        let matrix = std::mem::replace(&mut self.matrix, ComplexMatrix::new());
+        if !matrix.entries.is_empty() {
+            let mut shards = self.builder.shards.lock().unwrap_or_else(|e| e.into_inner());
+            shards.push(matrix);
+        }
+    }
+}
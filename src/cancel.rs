@@ -0,0 +1,120 @@
+use crate::SolveError;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// A flag to abort the solves run under ```with_cancellation``` from another thread.
+///
+/// Clones share the same flag. Once cancelled, a token stays cancelled.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+#[cfg(feature = "std")]
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of the operations watching this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether ```cancel``` was called on this token or one of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static TOKEN: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Runs ```f``` aborting its solves with `Err(SolveError::Cancelled)` once ```token```
+/// is cancelled.
+///
+/// The pure-Rust factorization checks the token at each column. With the default
+/// `eigen` feature, a factorization runs as a single Eigen call that cannot be
+/// interrupted: the token is only checked before it starts, so a long factorization
+/// runs to completion. The batch solves and ```ComplexMatrix::solve_async``` watch
+/// the token from their worker threads.
+///```rust
+/// use sparse_complex::{with_cancellation, CancellationToken, ComplexMatrix, SolveError};
+/// use num::Complex;
+///
+/// let mut m = ComplexMatrix::<f64>::new();
+/// m.add_element(0, 0, Complex::new(1., 0.));
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+/// let mut b = vec![Complex::new(1., 0.)];
+/// let result = with_cancellation(&token, || m.solve(&mut b));
+/// assert_eq!(result, Err(SolveError::Cancelled));
+///```
+#[cfg(feature = "std")]
+pub fn with_cancellation<R, F: FnOnce() -> R>(token: &CancellationToken, f: F) -> R {
+    scoped(Some(token.clone()), f)
+}
+
+/// The token of the enclosing ```with_cancellation```, if any.
+#[cfg(feature = "std")]
+pub(crate) fn current() -> Option<CancellationToken> {
+    TOKEN.with(|t| t.borrow().clone())
+}
+
+/// Runs ```f``` with ```token``` as the current token of this thread.
+#[cfg(feature = "std")]
+pub(crate) fn scoped<R, F: FnOnce() -> R>(token: Option<CancellationToken>, f: F) -> R {
+    let _guard = TokenGuard(TOKEN.with(|t| t.replace(token)));
+    f()
+}
+
+/// Returns `Err(SolveError::Cancelled)` if the current token is cancelled.
+#[cfg(any(feature = "f64", feature = "f32"))]
+pub(crate) fn check() -> Result<(), SolveError> {
+    Watcher::new().check()
+}
+
+/// The current token captured once, for the checks of a loop.
+pub(crate) struct Watcher {
+    #[cfg(feature = "std")]
+    token: Option<CancellationToken>,
+}
+
+impl Watcher {
+    pub(crate) fn new() -> Self {
+        Watcher {
+            #[cfg(feature = "std")]
+            token: current(),
+        }
+    }
+
+    /// Returns `Err(SolveError::Cancelled)` if the captured token is cancelled.
+    pub(crate) fn check(&self) -> Result<(), SolveError> {
+        #[cfg(feature = "std")]
+        if let Some(token) = &self.token {
+            if token.is_cancelled() {
+                return Err(SolveError::Cancelled);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Restores the previous token when dropped, even on panic.
+#[cfg(feature = "std")]
+struct TokenGuard(Option<CancellationToken>);
+
+#[cfg(feature = "std")]
+impl Drop for TokenGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        TOKEN.with(|t| *t.borrow_mut() = previous);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::compressed::compress;
+use crate::index::to_storage;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+use num_traits::NumCast;
+
+const MAGIC: &[u8; 4] = b"SCCM";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 4 + 4 + 4 + 8;
+const ENTRY_LEN: usize = 4 * 8;
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Sum the duplicated entries and sort the triplets in column-major order, i.e.
+    /// by ```(col, row)```, in `O(nnz + n)`.
+    ///
+    /// New elements can still be added afterwards and are appended after the
+    /// compacted triplets, so a long assembly can be compacted from time to time to
+    /// bound its memory and the size of its checkpoints.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// m.compact();
+    /// assert_eq!(m.get(1, 1), Some(&Complex::new(1., 1.)));
+    /// assert_eq!(m.get(0, 0), Some(&Complex::new(1., 0.)));
+    ///```
+    pub fn compact(&mut self) {
+        let csc = compress(self.dimension(), &self.cols, &self.rows, &self.entries);
+        let mut cols = Vec::with_capacity(csc.values.len());
+        for col in 0..csc.n {
+            cols.resize(csc.indptr[col + 1], to_storage(col));
+        }
+        self.rows = csc.indices.into_iter().map(to_storage).collect();
+        self.cols = cols;
+        self.entries = csc.values;
+    }
+
+    /// Serialize the stored triplets into a portable little-endian byte buffer.
+    ///
+    /// The values are stored as `f64`, so a checkpoint can only be restored into a
+    /// matrix of the same scalar type. Duplicated entries are kept as they are; call
+    /// ```compact``` first for a smaller checkpoint.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., -2.));
+    ///
+    /// let bytes = m.to_checkpoint();
+    /// let mut resumed = ComplexMatrix::<f64>::from_checkpoint(&bytes).unwrap();
+    /// assert_eq!(resumed, m);
+    /// resumed.add_element(1, 0, Complex::new(3., 0.));
+    ///```
+    pub fn to_checkpoint(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + ENTRY_LEN * self.entries.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(core::mem::size_of::<T>() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+        for ((row, col), value) in self.rows.iter().zip(self.cols.iter()).zip(self.entries.iter()) {
+            bytes.extend_from_slice(&(*row as u64).to_le_bytes());
+            bytes.extend_from_slice(&(*col as u64).to_le_bytes());
+            bytes.extend_from_slice(&value.re.to_f64().unwrap_or(f64::NAN).to_le_bytes());
+            bytes.extend_from_slice(&value.im.to_f64().unwrap_or(f64::NAN).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialize a matrix written by ```to_checkpoint```, possibly in another process.
+    ///
+    /// Returns `Err` if the buffer is truncated, was written by an incompatible
+    /// version or for another scalar type.
+    pub fn from_checkpoint(bytes: &[u8]) -> Result<Self, SolveError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(SolveError::InvalidInput("not a matrix checkpoint"));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(SolveError::InvalidInput("unsupported matrix checkpoint format version"));
+        }
+        let width = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if width as usize != core::mem::size_of::<T>() {
+            return Err(SolveError::InvalidInput(
+                "the checkpoint was written for another scalar type",
+            ));
+        }
+        let nnz = u64::from_le_bytes(bytes[12..20].try_into().unwrap()) as usize;
+        if bytes.len() != HEADER_LEN + ENTRY_LEN * nnz {
+            return Err(SolveError::InvalidInput("truncated matrix checkpoint"));
+        }
+
+        let mut m = ComplexMatrix::with_capacity(nnz);
+        for entry in bytes[HEADER_LEN..].chunks_exact(ENTRY_LEN) {
+            let word = |k: usize| u64::from_le_bytes(entry[8 * k..8 * (k + 1)].try_into().unwrap());
+            let value = |k: usize| <T as NumCast>::from(f64::from_bits(word(k))).unwrap_or_else(T::nan);
+            m.add_element(word(0) as usize, word(1) as usize, Complex::new(value(2), value(3)));
+        }
+        Ok(m)
+    }
+
+    /// Write a checkpoint of the matrix to ```path```, see ```to_checkpoint```.
+    ///
+    /// The checkpoint is written to a temporary file that then replaces ```path```, so
+    /// an interrupted write leaves the previous checkpoint intact.
+    #[cfg(feature = "std")]
+    pub fn save_checkpoint<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, self.to_checkpoint())?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Read a checkpoint written by ```save_checkpoint``` to resume the assembly.
+    ///
+    /// Returns an `InvalidData` error if the file is not a valid checkpoint.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let path = std::env::temp_dir().join("sparse_complex_doc_checkpoint.bin");
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.save_checkpoint(&path).unwrap();
+    ///
+    /// let resumed = ComplexMatrix::<f64>::load_checkpoint(&path).unwrap();
+    /// assert_eq!(resumed, m);
+    /// # std::fs::remove_file(&path).unwrap();
+    ///```
+    #[cfg(feature = "std")]
+    pub fn load_checkpoint<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_checkpoint(&bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
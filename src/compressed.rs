@@ -0,0 +1,252 @@
+use crate::index::AsIndex;
+use crate::progress::{self, Phase};
+use crate::simd;
+use crate::ComplexMatrix;
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// Compressed sparse row form of a square ```ComplexMatrix```, with sorted column
+/// indices and duplicated entries summed.
+pub(crate) struct Csr<T: Float> {
+    pub(crate) n: usize,
+    pub(crate) indptr: Vec<usize>,
+    pub(crate) indices: Vec<usize>,
+    pub(crate) values: Vec<Complex<T>>,
+}
+
+impl<T: Float> Csr<T> {
+    /// Iterate over the ```(col, value)``` pairs of ```row```.
+    #[inline]
+    pub(crate) fn row(&self, row: usize) -> impl Iterator<Item = (usize, &Complex<T>)> {
+        let range = self.indptr[row]..self.indptr[row + 1];
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter())
+    }
+
+    /// Computes the product `Ax`.
+    pub(crate) fn mul_vec(&self, x: &[Complex<T>]) -> Vec<Complex<T>> {
+        simd::dispatch(|| {
+            (0..self.n)
+                .map(|i| {
+                    self.row(i)
+                        .fold(Complex::new(T::zero(), T::zero()), |acc, (j, v)| acc + *v * x[j])
+                })
+                .collect()
+        })
+    }
+
+    /// Computes the residual `b - Ax`.
+    pub(crate) fn residual(&self, b: &[Complex<T>], x: &[Complex<T>]) -> Vec<Complex<T>> {
+        simd::dispatch(|| {
+            (0..self.n)
+                .map(|i| self.row(i).fold(b[i], |acc, (j, v)| acc - *v * x[j]))
+                .collect()
+        })
+    }
+}
+
+/// A compressed copy of a ```ComplexMatrix``` with duplicated entries summed, giving
+/// direct access to the nonzeros of a single row or column.
+pub struct CompressedMatrix<T: Float> {
+    by_row: Csr<T>,
+    by_col: Csr<T>,
+}
+
+impl<T: Float> CompressedMatrix<T> {
+    /// The order of the square matrix.
+    pub fn size(&self) -> usize {
+        self.by_row.n
+    }
+
+    /// The number of stored elements, after summing duplicated entries.
+    pub fn nnz(&self) -> usize {
+        self.by_row.values.len()
+    }
+
+    /// Iterate over the ```(col, value)``` pairs of ```row```, by increasing column.
+    ///
+    /// Panics if ```row``` is out of bounds.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(1, 2, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 2, Complex::new(0., 1.));
+    ///
+    /// let c = m.to_compressed();
+    /// let row = c.row(1).collect::<Vec<_>>();
+    /// assert_eq!(row, vec![(0, &Complex::new(2., 0.)), (2, &Complex::new(1., 1.))]);
+    ///```
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, &Complex<T>)> {
+        self.by_row.row(row)
+    }
+
+    /// Iterate over the ```(row, value)``` pairs of ```col```, by increasing row.
+    ///
+    /// Panics if ```col``` is out of bounds.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(2, 0, Complex::new(1., 0.));
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    ///
+    /// let c = m.to_compressed();
+    /// assert_eq!(c.col(0).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 2]);
+    ///```
+    pub fn col(&self, col: usize) -> impl Iterator<Item = (usize, &Complex<T>)> {
+        self.by_col.row(col)
+    }
+
+    /// Returns the element at ```(row, col)``` if present, or None if not.
+    pub fn get(&self, row: usize, col: usize) -> Option<&Complex<T>> {
+        if row >= self.size() {
+            return None;
+        }
+        let range = self.by_row.indptr[row]..self.by_row.indptr[row + 1];
+        self.by_row.indices[range.clone()]
+            .binary_search(&col)
+            .ok()
+            .map(|position| &self.by_row.values[range.start + position])
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Compress the triplets into CSR form, summing duplicated entries.
+    pub(crate) fn to_csr(&self) -> Csr<T> {
+        compress(self.dimension(), &self.rows, &self.cols, &self.entries)
+    }
+
+    /// Compress the matrix, summing duplicated entries, for row and column access.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    ///
+    /// let c = m.to_compressed();
+    /// assert_eq!(c.nnz(), 2);
+    /// assert_eq!(c.get(0, 0), Some(&Complex::new(2., 0.)));
+    ///```
+    pub fn to_compressed(&self) -> CompressedMatrix<T> {
+        let n = self.dimension();
+        CompressedMatrix {
+            by_row: compress(n, &self.rows, &self.cols, &self.entries),
+            by_col: compress(n, &self.cols, &self.rows, &self.entries),
+        }
+    }
+}
+
+/// Compress ```(outer, inner, value)``` triplets of an ```n × n``` matrix, summing duplicates.
+///
+/// Two stable counting sorts (by inner, then by outer index) order the triplets in
+/// `O(nnz + n)` without comparisons, so the conversion scales linearly with the
+/// number of entries. Swapping ```outer``` and ```inner``` gives the CSC form.
+///
+/// Triplets already sorted by ```(outer, inner)``` without duplicates, e.g. the CSC
+/// form of a matrix from ```ComplexMatrix::from_sorted_entries```, skip the sorts and
+/// are copied in a single pass.
+pub(crate) fn compress<T: Float, I: AsIndex>(
+    n: usize,
+    outer: &[I],
+    inner: &[I],
+    values: &[Complex<T>],
+) -> Csr<T> {
+    let nnz = values.len();
+    progress::report(Phase::Assembly, Some(0.));
+
+    let in_order = outer
+        .windows(2)
+        .zip(inner.windows(2))
+        .all(|(o, i)| (o[0].index(), i[0].index()) < (o[1].index(), i[1].index()));
+    if in_order {
+        let mut indptr = counting_offsets(n, outer);
+        indptr.push(nnz);
+        progress::report(Phase::Assembly, Some(1.));
+        let csr = Csr {
+            n,
+            indptr,
+            indices: inner.iter().map(|i| i.index()).collect(),
+            values: values.to_vec(),
+        };
+        csr.debug_validate();
+        return csr;
+    }
+
+    let mut by_inner = vec![0; nnz];
+    let mut offsets = counting_offsets(n, inner);
+    for (k, i) in inner.iter().enumerate() {
+        by_inner[offsets[i.index()]] = k;
+        offsets[i.index()] += 1;
+    }
+    progress::report(Phase::Assembly, Some(0.5));
+
+    let mut indptr = counting_offsets(n, outer);
+    let mut indices = vec![0; nnz];
+    let mut sorted = vec![Complex::new(T::zero(), T::zero()); nnz];
+    for k in by_inner {
+        let slot = &mut indptr[outer[k].index()];
+        indices[*slot] = inner[k].index();
+        sorted[*slot] = values[k];
+        *slot += 1;
+    }
+    // After the scatter each offset points to the end of its segment.
+    indptr.insert(0, 0);
+    indptr.truncate(n + 1);
+
+    // Sum adjacent duplicates while compacting the segments in place.
+    let mut len = 0;
+    let mut segment_start = 0;
+    for o in 0..n {
+        let segment_end = indptr[o + 1];
+        let start = len;
+        for p in segment_start..segment_end {
+            if len > start && indices[len - 1] == indices[p] {
+                sorted[len - 1] = sorted[len - 1] + sorted[p];
+            } else {
+                indices[len] = indices[p];
+                sorted[len] = sorted[p];
+                len += 1;
+            }
+        }
+        segment_start = segment_end;
+        indptr[o + 1] = len;
+    }
+    indices.truncate(len);
+    sorted.truncate(len);
+    progress::report(Phase::Assembly, Some(1.));
+
+    let csr = Csr {
+        n,
+        indptr,
+        indices,
+        values: sorted,
+    };
+    csr.debug_validate();
+    csr
+}
+
+/// The start of each key's segment in a counting sort of ```keys``` over ```0..n```.
+fn counting_offsets<I: AsIndex>(n: usize, keys: &[I]) -> Vec<usize> {
+    let mut offsets = vec![0; n];
+    for k in keys {
+        offsets[k.index()] += 1;
+    }
+    let mut sum = 0;
+    for offset in offsets.iter_mut() {
+        let count = *offset;
+        *offset = sum;
+        sum += count;
+    }
+    offsets
+}
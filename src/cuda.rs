@@ -0,0 +1,278 @@
+use crate::backend::{Factorization, SolverBackend};
+use crate::compressed::{compress, Csr};
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use std::mem;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// `cudaMemcpyKind` values.
+const HOST_TO_DEVICE: c_int = 1;
+const DEVICE_TO_HOST: c_int = 2;
+
+/// The tolerance below which cuSolverSp takes a diagonal entry of `R` as zero.
+const SINGULARITY_TOLERANCE: f64 = 1e-12;
+
+#[link(name = "cudart")]
+extern "C" {
+    fn cudaMalloc(ptr: *mut *mut c_void, size: usize) -> c_int;
+    fn cudaFree(ptr: *mut c_void) -> c_int;
+    fn cudaMemcpy(dst: *mut c_void, src: *const c_void, count: usize, kind: c_int) -> c_int;
+}
+
+#[link(name = "cusparse")]
+extern "C" {
+    fn cusparseCreateMatDescr(descr: *mut *mut c_void) -> c_int;
+    fn cusparseDestroyMatDescr(descr: *mut c_void) -> c_int;
+}
+
+#[link(name = "cusolver")]
+extern "C" {
+    fn cusolverSpCreate(handle: *mut *mut c_void) -> c_int;
+    fn cusolverSpDestroy(handle: *mut c_void) -> c_int;
+    fn cusolverSpZcsrlsvqr(
+        handle: *mut c_void,
+        m: c_int,
+        nnz: c_int,
+        descr: *const c_void,
+        csr_val: *const c_void,
+        csr_row_ptr: *const c_void,
+        csr_col_ind: *const c_void,
+        b: *const c_void,
+        tol: f64,
+        reorder: c_int,
+        x: *mut c_void,
+        singularity: *mut c_int,
+    ) -> c_int;
+}
+
+/// Maps the status of a CUDA runtime or library call to a ```Result```.
+fn check(status: c_int, reason: &'static str) -> Result<(), SolveError> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(SolveError::Unsupported(reason))
+    }
+}
+
+/// An allocation in device memory, freed on drop.
+struct DeviceBuffer {
+    ptr: *mut c_void,
+    bytes: usize,
+}
+
+impl DeviceBuffer {
+    fn new(bytes: usize) -> Result<Self, SolveError> {
+        let mut ptr = ptr::null_mut();
+        if bytes > 0 && unsafe { cudaMalloc(&mut ptr, bytes) } != 0 {
+            return Err(SolveError::OutOfMemory);
+        }
+        Ok(DeviceBuffer { ptr, bytes })
+    }
+
+    fn upload<T: Copy>(data: &[T]) -> Result<Self, SolveError> {
+        let buffer = DeviceBuffer::new(mem::size_of_val(data))?;
+        if buffer.bytes > 0 {
+            let status =
+                unsafe { cudaMemcpy(buffer.ptr, data.as_ptr() as *const c_void, buffer.bytes, HOST_TO_DEVICE) };
+            check(status, "the copy to the CUDA device failed")?;
+        }
+        Ok(buffer)
+    }
+
+    fn download<T: Copy>(&self, data: &mut [T]) -> Result<(), SolveError> {
+        if self.bytes > 0 {
+            let status = unsafe { cudaMemcpy(data.as_mut_ptr() as *mut c_void, self.ptr, self.bytes, DEVICE_TO_HOST) };
+            check(status, "the copy from the CUDA device failed")?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DeviceBuffer {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { cudaFree(self.ptr) };
+        }
+    }
+}
+
+/// A compressed sparse row matrix in device memory, with 32-bit indices.
+struct DeviceCsr {
+    values: DeviceBuffer,
+    indptr: DeviceBuffer,
+    indices: DeviceBuffer,
+    nnz: c_int,
+}
+
+impl DeviceCsr {
+    fn upload(csr: &Csr<f64>) -> Result<Self, SolveError> {
+        let too_large = SolveError::Unsupported("the matrix is too large for 32-bit indices");
+        let nnz = c_int::try_from(csr.values.len()).map_err(|_| too_large)?;
+        let indptr = csr.indptr.iter().map(|p| *p as c_int).collect::<Vec<_>>();
+        let indices = csr.indices.iter().map(|i| *i as c_int).collect::<Vec<_>>();
+        Ok(DeviceCsr {
+            values: DeviceBuffer::upload(&csr.values)?,
+            indptr: DeviceBuffer::upload(&indptr)?,
+            indices: DeviceBuffer::upload(&indices)?,
+            nnz,
+        })
+    }
+}
+
+/// The NVIDIA cuSolverSp engine, solving on the GPU with ```ComplexMatrix::upload_cuda```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CudaBackend;
+
+impl SolverBackend<f64> for CudaBackend {
+    fn name(&self) -> &'static str {
+        "CUDA cuSolverSp"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<f64>) -> Result<Box<dyn Factorization<f64>>, SolveError> {
+        Ok(Box::new(matrix.upload_cuda()?))
+    }
+}
+
+/// A matrix held in GPU memory and solved by sparse QR with cuSolverSp, created by
+/// ```ComplexMatrix::upload_cuda```.
+///
+/// cuSolverSp does not keep its sparse factorization between calls, so each solve
+/// factorizes the matrix on the device again; only the transfer of the matrix is
+/// saved. The adjoint is uploaded along with the matrix. A singular matrix is
+/// reported by the solves.
+pub struct CudaQr {
+    handle: *mut c_void,
+    descr: *mut c_void,
+    matrix: DeviceCsr,
+    adjoint: DeviceCsr,
+    size: usize,
+}
+
+impl ComplexMatrix<f64> {
+    /// Copy the matrix and its adjoint to the memory of the current CUDA device, to
+    /// solve them there.
+    ///
+    /// Returns `Err` if no device is available, its memory is exhausted or the matrix
+    /// has more than `i32::MAX` elements.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let qr = m.upload_cuda().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 1.), Complex::new(1., 0.)];
+    /// qr.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn upload_cuda(&self) -> Result<CudaQr, SolveError> {
+        let n = self.dimension();
+        if c_int::try_from(n).is_err() {
+            return Err(SolveError::Unsupported("the matrix is too large for 32-bit indices"));
+        }
+        let conj = self.entries.iter().map(|v| v.conj()).collect::<Vec<_>>();
+        let matrix = DeviceCsr::upload(&compress(n, &self.rows, &self.cols, &self.entries))?;
+        let adjoint = DeviceCsr::upload(&compress(n, &self.cols, &self.rows, &conj))?;
+
+        let mut handle = ptr::null_mut();
+        check(
+            unsafe { cusolverSpCreate(&mut handle) },
+            "cuSolverSp could not be initialized",
+        )?;
+        let mut descr = ptr::null_mut();
+        if let Err(err) = check(
+            unsafe { cusparseCreateMatDescr(&mut descr) },
+            "cuSPARSE could not be initialized",
+        ) {
+            unsafe { cusolverSpDestroy(handle) };
+            return Err(err);
+        }
+        Ok(CudaQr {
+            handle,
+            descr,
+            matrix,
+            adjoint,
+            size: n,
+        })
+    }
+}
+
+impl CudaQr {
+    /// The order of the matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Solve the system `Ax=b` on the device. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size, the matrix is singular or
+    /// the device fails.
+    pub fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_csr(&self.matrix, b)
+    }
+
+    /// Solve the system `Aᴴx=b` on the device. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size, the matrix is singular or
+    /// the device fails.
+    pub fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_csr(&self.adjoint, b)
+    }
+
+    fn solve_csr(&self, csr: &DeviceCsr, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        if b.len() != self.size {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let rhs = DeviceBuffer::upload(b)?;
+        let x = DeviceBuffer::new(rhs.bytes)?;
+        let mut singularity = -1;
+        let status = unsafe {
+            cusolverSpZcsrlsvqr(
+                self.handle,
+                self.size as c_int,
+                csr.nnz,
+                self.descr,
+                csr.values.ptr,
+                csr.indptr.ptr,
+                csr.indices.ptr,
+                rhs.ptr,
+                SINGULARITY_TOLERANCE,
+                0,
+                x.ptr,
+                &mut singularity,
+            )
+        };
+        check(status, "the cuSolverSp solve failed")?;
+        if singularity >= 0 {
+            return Err(SolveError::Singular);
+        }
+        x.download(b)
+    }
+}
+
+impl Factorization<f64> for CudaQr {
+    fn size(&self) -> usize {
+        CudaQr::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        CudaQr::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        CudaQr::solve_adjoint(self, b)
+    }
+}
+
+impl Drop for CudaQr {
+    fn drop(&mut self) {
+        unsafe {
+            cusparseDestroyMatDescr(self.descr);
+            cusolverSpDestroy(self.handle);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use crate::index::AsIndex;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+static DENSE_SOLVE_THRESHOLD: AtomicUsize = AtomicUsize::new(16);
+
+/// Sets the largest system size solved by ```solve``` with a dense LU in pure Rust.
+///
+/// Below this size the dense factorization is faster than building the sparse
+/// structures and calling into Eigen. The default is 16; 0 disables the dense path.
+///```rust
+/// sparse_complex::set_dense_solve_threshold(8);
+/// assert_eq!(sparse_complex::dense_solve_threshold(), 8);
+///```
+pub fn set_dense_solve_threshold(n: usize) {
+    DENSE_SOLVE_THRESHOLD.store(n, Ordering::Relaxed);
+}
+
+/// The largest system size solved with a dense LU, see ```set_dense_solve_threshold```.
+pub fn dense_solve_threshold() -> usize {
+    DENSE_SOLVE_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Dense LU factorization with partial pivoting, `PA = LU`, stored row-major.
+pub(crate) struct DenseLu<T: Float> {
+    n: usize,
+    lu: Vec<Complex<T>>,
+    perm: Vec<usize>,
+}
+
+impl<T: Float> DenseLu<T> {
+    /// Factorize the row-major ```n × n``` matrix ```a```.
+    pub(crate) fn new(n: usize, a: Vec<Complex<T>>) -> Result<Self, SolveError> {
+        Self::factorize(n, a, |_, _, _| {})
+    }
+
+    /// Factorize the row-major ```n × n``` matrix ```a```, calling ```observe``` after
+    /// each elimination step with the step, the chosen pivot row and the partially
+    /// eliminated matrix.
+    pub(crate) fn factorize<F>(n: usize, mut a: Vec<Complex<T>>, mut observe: F) -> Result<Self, SolveError>
+    where
+        F: FnMut(usize, usize, &[Complex<T>]),
+    {
+        let mut perm = (0..n).collect::<Vec<_>>();
+        for k in 0..n {
+            let mut pivot = k;
+            let mut max = a[k * n + k].norm();
+            for i in k + 1..n {
+                let candidate = a[i * n + k].norm();
+                if candidate > max {
+                    pivot = i;
+                    max = candidate;
+                }
+            }
+            if max == T::zero() || max.is_nan() {
+                return Err(SolveError::Singular);
+            }
+            if pivot != k {
+                for j in 0..n {
+                    a.swap(k * n + j, pivot * n + j);
+                }
+                perm.swap(k, pivot);
+            }
+
+            let (upper, lower) = a.split_at_mut((k + 1) * n);
+            let pivot_row = &upper[k * n..];
+            for row in lower.chunks_mut(n) {
+                let factor = row[k] / pivot_row[k];
+                row[k] = factor;
+                for (rj, pj) in row[k + 1..].iter_mut().zip(pivot_row[k + 1..].iter()) {
+                    *rj = *rj - factor * *pj;
+                }
+            }
+            observe(k, perm[k], &a);
+        }
+        Ok(DenseLu { n, lu: a, perm })
+    }
+
+    /// The row permutation: row ```i``` of `LU` is row ```permutation()[i]``` of `A`.
+    pub(crate) fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Solve `Ax=b` in place.
+    pub(crate) fn solve(&self, b: &mut [Complex<T>]) {
+        let n = self.n;
+        let mut x = self.perm.iter().map(|&p| b[p]).collect::<Vec<_>>();
+        for i in 0..n {
+            let row = &self.lu[i * n..i * n + i];
+            let sum = row
+                .iter()
+                .zip(x[..i].iter())
+                .fold(x[i], |acc, (l, xj)| acc - *l * *xj);
+            x[i] = sum;
+        }
+        for i in (0..n).rev() {
+            let row = &self.lu[i * n..(i + 1) * n];
+            let sum = row[i + 1..]
+                .iter()
+                .zip(x[i + 1..].iter())
+                .fold(x[i], |acc, (u, xj)| acc - *u * *xj);
+            x[i] = sum / row[i];
+        }
+        b.copy_from_slice(&x);
+    }
+
+    /// The inverse of the factorized matrix, row-major.
+    pub(crate) fn inverse(&self) -> Vec<Complex<T>> {
+        let n = self.n;
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut inverse = vec![zero; n * n];
+        let mut column = vec![zero; n];
+        for j in 0..n {
+            column.iter_mut().for_each(|v| *v = zero);
+            column[j] = Complex::new(T::one(), T::zero());
+            self.solve(&mut column);
+            for (i, v) in column.iter().enumerate() {
+                inverse[i * n + j] = *v;
+            }
+        }
+        inverse
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// The matrix as a dense row-major ```n × n``` array, summing duplicated entries.
+    pub(crate) fn to_dense(&self, n: usize) -> Vec<Complex<T>> {
+        let mut dense = vec![Complex::new(T::zero(), T::zero()); n * n];
+        let elements = self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            let (row, col) = (row.index(), col.index());
+            dense[row * n + col] = dense[row * n + col] + *value;
+        }
+        dense
+    }
+
+    /// Solves `Ax=b` with a dense LU if the system is below the dense threshold,
+    /// or returns None to let the caller use the sparse solver.
+    #[cfg(any(feature = "f64", feature = "f32"))]
+    pub(crate) fn try_solve_dense(&self, b: &mut [Complex<T>]) -> Option<Result<(), SolveError>> {
+        let n = b.len();
+        if n > dense_solve_threshold() || self.dimension() > n {
+            return None;
+        }
+        Some(DenseLu::new(n, self.to_dense(n)).map(|lu| lu.solve(b)))
+    }
+}
+
+/// Eigenvalues of the row-major ```n × n``` matrix ```a```, by a Householder reduction
+/// to Hessenberg form followed by shifted QR iterations.
+pub(crate) fn eigenvalues<T: Float>(n: usize, mut h: Vec<Complex<T>>) -> Result<Vec<Complex<T>>, SolveError> {
+    let zero = Complex::new(T::zero(), T::zero());
+    let one = Complex::new(T::one(), T::zero());
+    let two = T::one() + T::one();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v = (k + 1..n).map(|i| h[i * n + k]).collect::<Vec<_>>();
+        let alpha = v.iter().fold(T::zero(), |acc, x| acc + x.norm_sqr()).sqrt();
+        if alpha == T::zero() {
+            continue;
+        }
+        let phase = if v[0].norm() > T::zero() { v[0] / v[0].norm() } else { one };
+        v[0] = v[0] + phase * alpha;
+        let v_norm = v.iter().fold(T::zero(), |acc, x| acc + x.norm_sqr()).sqrt();
+        v.iter_mut().for_each(|x| *x = *x / v_norm);
+
+        // H = PHP with the reflector P = I - 2vvᴴ.
+        for j in k..n {
+            let s = v
+                .iter()
+                .enumerate()
+                .fold(zero, |acc, (i, vi)| acc + vi.conj() * h[(k + 1 + i) * n + j]);
+            for (i, vi) in v.iter().enumerate() {
+                h[(k + 1 + i) * n + j] = h[(k + 1 + i) * n + j] - *vi * s * two;
+            }
+        }
+        for i in 0..n {
+            let row = &mut h[i * n + k + 1..(i + 1) * n];
+            let s = row.iter().zip(v.iter()).fold(zero, |acc, (hij, vj)| acc + *hij * *vj);
+            for (hij, vj) in row.iter_mut().zip(v.iter()) {
+                *hij = *hij - s * vj.conj() * two;
+            }
+        }
+    }
+
+    // The active window is rows and columns lo..hi; the rest is already deflated.
+    let mut hi = n;
+    let mut iterations = 0;
+    while hi > 1 {
+        let mut lo = hi - 1;
+        while lo > 0 {
+            let scale = h[(lo - 1) * n + lo - 1].norm() + h[lo * n + lo].norm();
+            if h[lo * n + lo - 1].norm() <= T::epsilon() * scale {
+                h[lo * n + lo - 1] = zero;
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi - 1 {
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        if iterations > 100 {
+            return Err(SolveError::NoConvergence);
+        }
+
+        // Wilkinson shift, with an exceptional shift from time to time to break cycles.
+        let (a, b) = (h[(hi - 2) * n + hi - 2], h[(hi - 2) * n + hi - 1]);
+        let (c, d) = (h[(hi - 1) * n + hi - 2], h[(hi - 1) * n + hi - 1]);
+        let half = (a - d) / two;
+        let disc = (half * half + b * c).sqrt();
+        let mean = (a + d) / two;
+        let mut mu = if (mean + disc - d).norm() < (mean - disc - d).norm() { mean + disc } else { mean - disc };
+        if iterations % 10 == 0 {
+            mu = mu + c.norm();
+        }
+
+        for i in lo..hi {
+            h[i * n + i] = h[i * n + i] - mu;
+        }
+        let mut rotations = Vec::with_capacity(hi - lo - 1);
+        for k in lo..hi - 1 {
+            let (x, y) = (h[k * n + k], h[(k + 1) * n + k]);
+            let r = (x.norm_sqr() + y.norm_sqr()).sqrt();
+            let (c, s) = if r == T::zero() { (one, zero) } else { (x / r, y / r) };
+            for j in k..hi {
+                let (p, q) = (h[k * n + j], h[(k + 1) * n + j]);
+                h[k * n + j] = c.conj() * p + s.conj() * q;
+                h[(k + 1) * n + j] = c * q - s * p;
+            }
+            rotations.push((c, s));
+        }
+        for (offset, (c, s)) in rotations.into_iter().enumerate() {
+            let k = lo + offset;
+            for i in lo..(k + 2).min(hi) {
+                let (p, q) = (h[i * n + k], h[i * n + k + 1]);
+                h[i * n + k] = p * c + q * s;
+                h[i * n + k + 1] = q * c.conj() - p * s.conj();
+            }
+        }
+        for i in lo..hi {
+            h[i * n + i] = h[i * n + i] + mu;
+        }
+    }
+    Ok((0..n).map(|i| h[i * n + i]).collect())
+}
+
+/// A unit eigenvector of the row-major ```n × n``` matrix ```a``` for the approximate
+/// eigenvalue ```lambda```, by inverse iteration.
+pub(crate) fn eigenvector<T: Float>(n: usize, a: &[Complex<T>], lambda: Complex<T>) -> Result<Vec<Complex<T>>, SolveError> {
+    let scale = a.iter().fold(T::one(), |max, v| max.max(v.norm()));
+    let mut delta = scale * T::epsilon() * T::from(n + 1).unwrap();
+    for _ in 0..4 {
+        let mut shifted = a.to_vec();
+        for i in 0..n {
+            shifted[i * n + i] = shifted[i * n + i] - lambda - delta;
+        }
+        if let Ok(lu) = DenseLu::new(n, shifted) {
+            let mut x = (0..n)
+                .map(|i| Complex::new(T::one(), T::from(i).unwrap() / T::from(n).unwrap()))
+                .collect::<Vec<_>>();
+            for _ in 0..3 {
+                lu.solve(&mut x);
+                let norm = x.iter().fold(T::zero(), |acc, v| acc + v.norm_sqr()).sqrt();
+                if !norm.is_finite() || norm == T::zero() {
+                    break;
+                }
+                x.iter_mut().for_each(|v| *v = *v / norm);
+            }
+            if x.iter().all(|v| v.norm().is_finite()) {
+                return Ok(x);
+            }
+        }
+        delta = delta * T::from(1000).unwrap();
+    }
+    Err(SolveError::NumericalIssue)
+}
+
+/// Eigenvalues in ascending order and unit eigenvectors of the row-major Hermitian
+/// ```n × n``` matrix ```a```, by cyclic Jacobi rotations. The eigenvectors are the
+/// columns of the returned row-major matrix.
+pub(crate) fn hermitian_eigen<T: Float>(n: usize, mut a: Vec<Complex<T>>) -> (Vec<T>, Vec<Complex<T>>) {
+    let zero = Complex::new(T::zero(), T::zero());
+    let one = Complex::new(T::one(), T::zero());
+    let mut v = vec![zero; n * n];
+    for i in 0..n {
+        v[i * n + i] = one;
+    }
+    let total = a.iter().fold(T::zero(), |acc, x| acc + x.norm_sqr());
+    for _ in 0..50 {
+        let off = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| i * n + j))
+            .fold(T::zero(), |acc, k| acc + a[k].norm_sqr());
+        if off <= T::epsilon() * T::epsilon() * total {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let r = a[p * n + q].norm();
+                if r == T::zero() {
+                    continue;
+                }
+                // G = diag(1, e^{-iφ}) [c s; -s c] zeroes a_pq = r e^{iφ}.
+                let phase = a[p * n + q] / r;
+                let tau = (a[q * n + q].re - a[p * n + p].re) / (r + r);
+                let t = tau.signum() / (tau.abs() + (T::one() + tau * tau).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = t * c;
+                let (gqp, gqq) = (-phase.conj() * s, phase.conj() * c);
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = akp * c + akq * gqp;
+                    a[k * n + q] = akp * s + akq * gqq;
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = vkp * c + vkq * gqp;
+                    v[k * n + q] = vkp * s + vkq * gqq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = apk * c + aqk * gqp.conj();
+                    a[q * n + k] = apk * s + aqk * gqq.conj();
+                }
+                a[p * n + q] = zero;
+                a[q * n + p] = zero;
+            }
+        }
+    }
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|i, j| a[i * n + i].re.partial_cmp(&a[j * n + j].re).unwrap_or(core::cmp::Ordering::Equal));
+    let values = order.iter().map(|i| a[i * n + i].re).collect();
+    let vectors = (0..n * n).map(|k| v[(k / n) * n + order[k % n]]).collect();
+    (values, vectors)
+}
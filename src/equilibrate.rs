@@ -0,0 +1,120 @@
+use crate::index::AsIndex;
+use crate::ComplexMatrix;
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// Row and column scalings `D_r` and `D_c` bringing the largest entry modulus of
+/// every row and column of `D_r A D_c` close to one, as in LAPACK's ```zgeequb```.
+///
+/// The scale factors are powers of two, so scaling adds no rounding error.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Equilibration<T: Float> {
+    rows: Vec<T>,
+    cols: Vec<T>,
+}
+
+impl<T: Float> Equilibration<T> {
+    /// Computes the row scaling first and the column scaling of the row-scaled
+    /// matrix. Zero rows and columns are left unscaled.
+    pub(crate) fn new(matrix: &ComplexMatrix<T>) -> Self {
+        let csr = matrix.to_csr();
+        let rows = (0..csr.n)
+            .map(|i| power_of_two(csr.row(i).fold(T::zero(), |max, (_, v)| max.max(v.norm()))))
+            .collect::<Vec<_>>();
+        let mut max = vec![T::zero(); csr.n];
+        for (i, r) in rows.iter().enumerate() {
+            for (j, v) in csr.row(i) {
+                max[j] = max[j].max(*r * v.norm());
+            }
+        }
+        let cols = max.into_iter().map(power_of_two).collect();
+        Equilibration { rows, cols }
+    }
+
+    /// Returns `D_r A D_c`.
+    pub(crate) fn apply(&self, matrix: &ComplexMatrix<T>) -> ComplexMatrix<T> {
+        let mut scaled = matrix.clone();
+        let elements = scaled
+            .entries
+            .iter_mut()
+            .zip(scaled.rows.iter().zip(scaled.cols.iter()));
+        for (value, (row, col)) in elements {
+            *value = *value * (self.rows[row.index()] * self.cols[col.index()]);
+        }
+        scaled
+    }
+
+    /// Computes `b = D_r b`.
+    pub(crate) fn scale_rows(&self, b: &mut [Complex<T>]) {
+        scale(b, &self.rows);
+    }
+
+    /// Computes `b = D_c b`.
+    pub(crate) fn scale_cols(&self, b: &mut [Complex<T>]) {
+        scale(b, &self.cols);
+    }
+
+    /// Returns `det A` from `det(D_r A D_c)`.
+    pub(crate) fn unscale_determinant(&self, det: Complex<T>) -> Complex<T> {
+        self.rows
+            .iter()
+            .chain(self.cols.iter())
+            .fold(det, |det, s| det / *s)
+    }
+
+    /// Returns `ln|det A|` from `ln|det(D_r A D_c)|`.
+    pub(crate) fn unscale_log_abs_determinant(&self, log_abs: T) -> T {
+        self.rows
+            .iter()
+            .chain(self.cols.iter())
+            .fold(log_abs, |log_abs, s| log_abs - s.ln())
+    }
+
+    /// Turns the factors `P (D_r A D_c) Q = LU` into factors of `PAQ`, where
+    /// ```pivots[k]``` and ```columns[k]``` are the row and column of `A` at step ```k```.
+    ///
+    /// With `R = P D_r⁻¹ Pᵀ` and `C = Qᵀ D_c⁻¹ Q`, `PAQ = (R L R⁻¹)(R U C)`, so `L`
+    /// keeps its unit diagonal. The scale factors are powers of two, so this is exact.
+    #[cfg(feature = "eigen")]
+    pub(crate) fn unscale_factors(
+        &self,
+        lower: &mut ComplexMatrix<T>,
+        upper: &mut ComplexMatrix<T>,
+        pivots: &[usize],
+        columns: &[usize],
+    ) {
+        let row = |k: usize| self.rows[pivots[k]];
+        let col = |k: usize| self.cols[columns[k]];
+        let elements = lower
+            .entries
+            .iter_mut()
+            .zip(lower.rows.iter().zip(lower.cols.iter()));
+        for (value, (r, k)) in elements {
+            *value = *value * (row(k.index()) / row(r.index()));
+        }
+        let elements = upper
+            .entries
+            .iter_mut()
+            .zip(upper.rows.iter().zip(upper.cols.iter()));
+        for (value, (s, k)) in elements {
+            *value = *value / (row(s.index()) * col(k.index()));
+        }
+    }
+}
+
+/// The power of two nearest to `1 / max`, or one if ```max``` is zero or not finite.
+fn power_of_two<T: Float>(max: T) -> T {
+    if max > T::zero() && max.is_finite() {
+        (-max.log2()).round().exp2()
+    } else {
+        T::one()
+    }
+}
+
+fn scale<T: Float>(b: &mut [Complex<T>], factors: &[T]) {
+    for (bi, s) in b.iter_mut().zip(factors.iter()) {
+        *bi = *bi * *s;
+    }
+}
@@ -0,0 +1,70 @@
+use core::fmt;
+
+/// The error returned by the solvers and factorizations of the crate.
+///
+/// New failure modes may be added, so matching on it needs a wildcard arm.
+///```rust
+/// use sparse_complex::{ComplexMatrix, SolveError};
+/// use num::Complex;
+///
+/// let mut m = ComplexMatrix::<f64>::new();
+/// m.add_element(0, 0, Complex::new(1., 0.));
+/// m.add_element(1, 0, Complex::new(1., 0.));
+///
+/// let mut b = vec![Complex::new(1., 0.); 2];
+/// assert_eq!(m.solve(&mut b), Err(SolveError::Singular));
+///```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SolveError {
+    /// The matrix is singular or rank deficient: a zero pivot was found.
+    Singular,
+    /// The computation broke down or lost too much accuracy to be trusted.
+    NumericalIssue,
+    /// An iterative method did not converge within its iteration limit.
+    NoConvergence,
+    /// The sizes of the operands do not match.
+    DimensionMismatch,
+    /// The input is not valid for the operation, e.g. a malformed ordering or
+    /// serialized data.
+    InvalidInput(&'static str),
+    /// The operation is not supported in this configuration.
+    Unsupported(&'static str),
+    /// The operation was aborted through a ```CancellationToken```. The Eigen
+    /// factorizations are not cancellable once started, see ```with_cancellation```.
+    Cancelled,
+    /// The memory needed by the factorization could not be allocated.
+    OutOfMemory,
+    /// The solver failed unexpectedly, e.g. the C++ solver raised an exception
+    /// other than an allocation failure.
+    Internal,
+    /// The element of the matrix stored at ```(row, col)``` is NaN or infinite.
+    NonFiniteElement { row: usize, col: usize },
+    /// The element ```index``` of the right-hand side is NaN or infinite.
+    NonFiniteRhs { index: usize },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Singular => write!(f, "the matrix is singular"),
+            SolveError::NumericalIssue => write!(f, "the computation is numerically unreliable"),
+            SolveError::NoConvergence => write!(f, "the iteration did not converge"),
+            SolveError::DimensionMismatch => write!(f, "the dimensions do not match"),
+            SolveError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            SolveError::Unsupported(reason) => write!(f, "unsupported: {}", reason),
+            SolveError::Cancelled => write!(f, "the operation was cancelled"),
+            SolveError::OutOfMemory => write!(f, "out of memory"),
+            SolveError::Internal => write!(f, "internal solver error"),
+            SolveError::NonFiniteElement { row, col } => {
+                write!(f, "the element ({}, {}) is not finite", row, col)
+            }
+            SolveError::NonFiniteRhs { index } => {
+                write!(f, "the element {} of the right-hand side is not finite", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SolveError {}
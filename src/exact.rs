@@ -0,0 +1,94 @@
+use crate::index::AsIndex;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num::rational::BigRational;
+use num::Zero;
+use num_traits::float::Float;
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` exactly over the Gaussian rationals.
+    ///
+    /// Every floating point entry is converted to the rational number it represents,
+    /// so the returned solution is the exact solution of the stored system, free of
+    /// rounding errors. It is meant to verify floating point results on small systems:
+    /// the elimination is dense and the rationals grow quickly.
+    ///
+    /// Returns `Err` if `b` does not match the matrix size, an entry is not finite or
+    /// the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::{BigRational, Complex, ToPrimitive};
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(3., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let x = m.solve_exact(&[Complex::new(1., 0.), Complex::new(1., 0.)]).unwrap();
+    /// let third = BigRational::new(1.into(), 3.into());
+    /// assert_eq!(x[0], Complex::new(third, BigRational::from_integer(0.into())));
+    /// assert_eq!(x[1].im.to_f64(), Some(-1.));
+    ///```
+    pub fn solve_exact(&self, b: &[Complex<T>]) -> Result<Vec<Complex<BigRational>>, SolveError> {
+        let n = self.dimension();
+        if b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+
+        let zero = Complex::<BigRational>::zero();
+        let mut a = vec![vec![zero; n]; n];
+        let elements = self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            let (row, col) = (row.index(), col.index());
+            a[row][col] = &a[row][col] + to_rational(value)?;
+        }
+        let mut x = b.iter().map(to_rational).collect::<Result<Vec<_>, _>>()?;
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .find(|&i| !a[i][k].is_zero())
+                .ok_or(SolveError::Singular)?;
+            a.swap(k, pivot);
+            x.swap(k, pivot);
+
+            for i in k + 1..n {
+                if a[i][k].is_zero() {
+                    continue;
+                }
+                let factor = &a[i][k] / &a[k][k];
+                let (upper, lower) = a.split_at_mut(i);
+                for (aij, akj) in lower[0][k..].iter_mut().zip(upper[k][k..].iter()) {
+                    *aij -= &factor * akj;
+                }
+                let xk = x[k].clone();
+                x[i] -= factor * xk;
+            }
+        }
+
+        for k in (0..n).rev() {
+            let mut sum = x[k].clone();
+            for (akj, xj) in a[k][k + 1..].iter().zip(x[k + 1..].iter()) {
+                sum -= akj * xj;
+            }
+            x[k] = sum / &a[k][k];
+        }
+
+        Ok(x)
+    }
+}
+
+fn to_rational<T: Float>(value: &Complex<T>) -> Result<Complex<BigRational>, SolveError> {
+    let convert = |v: T| {
+        v.to_f64()
+            .and_then(BigRational::from_float)
+            .ok_or(SolveError::InvalidInput(
+                "the system has a non-finite entry",
+            ))
+    };
+    Ok(Complex::new(convert(value.re)?, convert(value.im)?))
+}
@@ -0,0 +1,146 @@
+use crate::dense::DenseLu;
+use crate::{ComplexMatrix, Permutation};
+use alloc::vec::Vec;
+use core::fmt;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// The pivoting decision of one step of an explained LU factorization.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PivotStep<T: Float> {
+    /// The elimination step, i.e. the column being eliminated.
+    pub step: usize,
+    /// The original row chosen as pivot row.
+    pub pivot_row: usize,
+    /// The magnitude of the diagonal element before pivoting.
+    pub diagonal: T,
+    /// The pivot.
+    pub pivot: Complex<T>,
+    /// `max|A⁽ᵏ⁾| / max|A|` after the step. A large growth means lost accuracy.
+    pub growth: T,
+    /// The smallest over the largest pivot magnitude so far, a cheap indicator of
+    /// ill-conditioning.
+    pub pivot_ratio: T,
+}
+
+/// A step-by-step record of the dense LU factorization of a small matrix, see
+/// ```ComplexMatrix::explain_factorization```. Its `Display` pretty-prints it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation<T: Float> {
+    size: usize,
+    steps: Vec<PivotStep<T>>,
+    permutation: Permutation,
+    singular_at: Option<usize>,
+}
+
+impl<T: Float> Explanation<T> {
+    /// The pivoting decisions, one per completed step.
+    pub fn steps(&self) -> &[PivotStep<T>] {
+        &self.steps
+    }
+
+    /// The row permutation: row ```i``` of `A` is row ```permutation().get(i)``` of `LU`.
+    /// Empty if the factorization failed.
+    pub fn permutation(&self) -> &Permutation {
+        &self.permutation
+    }
+
+    /// The step at which no nonzero pivot was found, if the matrix is singular.
+    pub fn singular_at(&self) -> Option<usize> {
+        self.singular_at
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Factorize the matrix with a dense LU with partial pivoting, recording the
+    /// pivoting decisions, the final permutation and per-step condition indicators.
+    ///
+    /// This is meant for teaching and for debugging small pathological systems: the
+    /// factorization is dense, so its cost grows as `n³`. The Eigen solver may take
+    /// different pivoting decisions.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let explanation = m.explain_factorization();
+    /// assert_eq!(explanation.steps()[0].pivot_row, 1);
+    /// assert_eq!(explanation.permutation().as_slice(), &[1, 0]);
+    /// println!("{}", explanation);
+    ///```
+    pub fn explain_factorization(&self) -> Explanation<T> {
+        let n = self.dimension();
+        let a = self.to_dense(n);
+        let max_a = a.iter().fold(T::zero(), |max, v| max.max(v.norm()));
+        let mut diagonal = (0..n).map(|k| a[k * n + k].norm()).collect::<Vec<_>>();
+
+        let mut steps = Vec::with_capacity(n);
+        let (mut min_pivot, mut max_pivot) = (T::infinity(), T::zero());
+        let result = DenseLu::factorize(n, a, |k, pivot_row, lu| {
+            let pivot = lu[k * n + k];
+            min_pivot = min_pivot.min(pivot.norm());
+            max_pivot = max_pivot.max(pivot.norm());
+            // The rows of U computed so far and the active submatrix.
+            let max_active = (0..n)
+                .flat_map(|i| (i.min(k + 1)..n).map(move |j| lu[i * n + j].norm()))
+                .fold(T::zero(), T::max);
+            steps.push(PivotStep {
+                step: k,
+                pivot_row,
+                diagonal: diagonal[k],
+                pivot,
+                growth: max_active / max_a,
+                pivot_ratio: min_pivot / max_pivot,
+            });
+            for (i, d) in diagonal.iter_mut().enumerate().skip(k + 1) {
+                *d = lu[i * n + i].norm();
+            }
+        });
+
+        let (permutation, singular_at) = match result {
+            Ok(lu) => (Permutation::from_vec(lu.permutation().to_vec()).unwrap().inverse(), None),
+            Err(_) => (Permutation::identity(0), Some(steps.len())),
+        };
+        Explanation {
+            size: n,
+            steps,
+            permutation,
+            singular_at,
+        }
+    }
+}
+
+impl<T: Float + fmt::LowerExp> fmt::Display for Explanation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "LU factorization of a {} × {} matrix with partial pivoting",
+            self.size, self.size
+        )?;
+        writeln!(
+            f,
+            "{:>5} {:>9} {:>11} {:>11} {:>11} {:>11}",
+            "step", "pivot row", "|a_kk|", "|pivot|", "growth", "min/max"
+        )?;
+        for s in &self.steps {
+            writeln!(
+                f,
+                "{:>5} {:>9} {:>11.3e} {:>11.3e} {:>11.3e} {:>11.3e}",
+                s.step,
+                s.pivot_row,
+                s.diagonal,
+                s.pivot.norm(),
+                s.growth,
+                s.pivot_ratio
+            )?;
+        }
+        match self.singular_at {
+            Some(step) => write!(f, "singular: no nonzero pivot at step {}", step),
+            None => write!(f, "row permutation: {:?}", self.permutation.as_slice()),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::dense::eigenvalues;
+use crate::krylov::{least_squares, norm};
+use crate::SolveError;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A rational pole–residue model `f(s) = Σ rₙ / (s - pₙ) + d + s e`, see ```vector_fit```.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RationalModel<T: Float> {
+    poles: Vec<Complex<T>>,
+    residues: Vec<Complex<T>>,
+    constant: Complex<T>,
+    proportional: Complex<T>,
+}
+
+impl<T: Float> RationalModel<T> {
+    /// The poles `pₙ`, all in the closed left half-plane.
+    pub fn poles(&self) -> &[Complex<T>] {
+        &self.poles
+    }
+
+    /// The residue `rₙ` of each pole.
+    pub fn residues(&self) -> &[Complex<T>] {
+        &self.residues
+    }
+
+    /// The constant term `d`.
+    pub fn constant(&self) -> Complex<T> {
+        self.constant
+    }
+
+    /// The proportional term `e`.
+    pub fn proportional(&self) -> Complex<T> {
+        self.proportional
+    }
+
+    /// Evaluate the model at ```s```.
+    pub fn evaluate(&self, s: Complex<T>) -> Complex<T> {
+        self.poles
+            .iter()
+            .zip(self.residues.iter())
+            .fold(self.constant + s * self.proportional, |acc, (p, r)| {
+                acc + *r / (s - *p)
+            })
+    }
+
+    /// The root mean square deviation of the model from the samples ```response``` at ```s```.
+    pub fn rms_error(&self, s: &[Complex<T>], response: &[Complex<T>]) -> T {
+        let sum = s
+            .iter()
+            .zip(response.iter())
+            .fold(T::zero(), |acc, (sk, fk)| {
+                acc + (self.evaluate(*sk) - *fk).norm_sqr()
+            });
+        (sum / T::from(s.len().max(1)).unwrap()).sqrt()
+    }
+}
+
+/// Fit a rational model with ```poles``` poles to the samples ```response``` of a
+/// frequency response at the points ```s```, typically `s = jω` of a frequency sweep.
+///
+/// This is the vector fitting method of Gustavsen and Semlyen: starting from poles
+/// spread over the sampled band, each of the ```iterations``` iterations relocates
+/// the poles as the zeros of a scaling function fitted by linear least squares, and
+/// unstable poles are flipped into the left half-plane. The residues and the `d`
+/// and `e` terms are then fitted for the final poles. The poles are not constrained
+/// to conjugate pairs, so responses of complex-valued systems can be fitted too.
+///
+/// Returns `Err` if the lengths differ, there are fewer samples than unknowns or a
+/// least-squares problem is rank deficient.
+///```rust
+/// use sparse_complex::vector_fit;
+/// use num::Complex;
+///
+/// // A resonance sampled on the imaginary axis.
+/// let p = Complex::new(-1., 20.);
+/// let s = (1..200).map(|k| Complex::new(0., k as f64 * 0.2)).collect::<Vec<_>>();
+/// let response = s.iter().map(|s| Complex::new(3., 0.) / (s - p) + 0.5).collect::<Vec<_>>();
+///
+/// let model = vector_fit(&s, &response, 1, 5).unwrap();
+/// assert!((model.poles()[0] - p).norm() < 1e-8);
+/// assert!(model.rms_error(&s, &response) < 1e-10);
+///```
+pub fn vector_fit<T: Float>(
+    s: &[Complex<T>],
+    response: &[Complex<T>],
+    poles: usize,
+    iterations: usize,
+) -> Result<RationalModel<T>, SolveError> {
+    if s.len() != response.len() {
+        return Err(SolveError::DimensionMismatch);
+    }
+    if s.len() < 2 * poles + 2 {
+        return Err(SolveError::InvalidInput(
+            "too few samples for the number of poles",
+        ));
+    }
+    let one = Complex::new(T::one(), T::zero());
+
+    // Starting poles with a small damping, spread over the imaginary part of the band.
+    let (low, high) = s
+        .iter()
+        .fold((T::infinity(), T::neg_infinity()), |(low, high), sk| {
+            (low.min(sk.im), high.max(sk.im))
+        });
+    let mut a = (0..poles)
+        .map(|n| {
+            let fraction = T::from(n).unwrap() / T::from(poles.max(2) - 1).unwrap();
+            let beta = low + (high - low) * fraction;
+            let damping = beta
+                .abs()
+                .max((high - low).abs() / T::from(100 * poles).unwrap())
+                .max(T::epsilon());
+            Complex::new(-damping / T::from(100).unwrap(), beta)
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..iterations {
+        // Σ cₙ/(s-aₙ) + d + s e - f(s) Σ c̃ₙ/(s-aₙ) = f(s)
+        let rows = s
+            .iter()
+            .zip(response.iter())
+            .map(|(sk, fk)| {
+                let partial = a.iter().map(|an| one / (*sk - *an)).collect::<Vec<_>>();
+                let mut row = partial.clone();
+                row.push(one);
+                row.push(*sk);
+                row.extend(partial.iter().map(|p| -*fk * *p));
+                row
+            })
+            .collect::<Vec<_>>();
+        let x = scaled_least_squares(&rows, response)?;
+        let sigma = &x[poles + 2..];
+
+        // The zeros of σ(s) = Σ c̃ₙ/(s-aₙ) + 1 are the eigenvalues of diag(a) - 1 c̃ᵀ.
+        let mut h = Vec::with_capacity(poles * poles);
+        for (i, ai) in a.iter().enumerate() {
+            h.extend(
+                sigma
+                    .iter()
+                    .enumerate()
+                    .map(|(j, cj)| if i == j { *ai - *cj } else { -*cj }),
+            );
+        }
+        a = eigenvalues(poles, h)?
+            .into_iter()
+            .map(|p| {
+                if p.re > T::zero() {
+                    Complex::new(-p.re, p.im)
+                } else {
+                    p
+                }
+            })
+            .collect();
+    }
+
+    // Residue identification for the final poles.
+    let rows = s
+        .iter()
+        .map(|sk| {
+            let mut row = a.iter().map(|an| one / (*sk - *an)).collect::<Vec<_>>();
+            row.push(one);
+            row.push(*sk);
+            row
+        })
+        .collect::<Vec<_>>();
+    let x = scaled_least_squares(&rows, response)?;
+    let (proportional, constant) = (x[poles + 1], x[poles]);
+    Ok(RationalModel {
+        poles: a,
+        residues: x[..poles].to_vec(),
+        constant,
+        proportional,
+    })
+}
+
+/// Solves `min ‖b - Ax‖` for the rows of `A`, scaling its columns to unit norm first.
+fn scaled_least_squares<T: Float>(
+    rows: &[Vec<Complex<T>>],
+    b: &[Complex<T>],
+) -> Result<Vec<Complex<T>>, SolveError> {
+    let cols = rows[0].len();
+    let scales = (0..cols)
+        .map(|j| {
+            let column = rows.iter().map(|row| row[j]).collect::<Vec<_>>();
+            let scale = norm(&column);
+            if scale > T::zero() {
+                T::one() / scale
+            } else {
+                T::one()
+            }
+        })
+        .collect::<Vec<_>>();
+    let a = rows
+        .iter()
+        .flat_map(|row| row.iter().zip(scales.iter()).map(|(v, scale)| *v * *scale))
+        .collect::<Vec<_>>();
+    let x = least_squares(rows.len(), cols, &a, b).ok_or(SolveError::Singular)?;
+    Ok(x.into_iter()
+        .zip(scales)
+        .map(|(x, scale)| x * scale)
+        .collect())
+}
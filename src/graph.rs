@@ -0,0 +1,79 @@
+use crate::ComplexMatrix;
+use alloc::vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Assemble the adjacency matrix of an undirected graph of ```n``` nodes.
+    ///
+    /// Each edge ```(i, j, weight)``` adds ```weight``` at ```(i, j)``` and ```(j, i)```;
+    /// a self-loop ```(i, i, weight)``` adds it once on the diagonal. Parallel edges
+    /// are summed.
+    ///
+    /// Panics if an edge has a node out of ```0..n```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let edges = vec![(0, 1, Complex::new(2., 0.)), (1, 2, Complex::new(0., 1.))];
+    /// let a = ComplexMatrix::<f64>::adjacency_from_edges(3, edges);
+    /// assert_eq!(a.get(1, 0), Some(&Complex::new(2., 0.)));
+    /// assert_eq!(a.get(2, 1), Some(&Complex::new(0., 1.)));
+    /// assert_eq!(a.get(0, 2), None);
+    ///```
+    pub fn adjacency_from_edges<I>(n: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, Complex<T>)>,
+    {
+        let mut m = ComplexMatrix::new();
+        for (i, j, weight) in edges {
+            assert!(i < n && j < n, "the edge ({}, {}) has a node out of bounds", i, j);
+            m.add_element(i, j, weight);
+            if i != j {
+                m.add_element(j, i, weight);
+            }
+        }
+        m
+    }
+
+    /// Assemble the Laplacian `L = D - A` of an undirected graph of ```n``` nodes.
+    ///
+    /// Each edge ```(i, j, weight)``` adds ```weight``` to the diagonal at ```i``` and
+    /// ```j``` and subtracts it at ```(i, j)``` and ```(j, i)```, so every row sums to
+    /// zero. Self-loops cancel out and are ignored. With admittances as weights this
+    /// is the nodal admittance matrix of the network. Every node gets a diagonal
+    /// entry, so the matrix has order ```n``` even if the last nodes are isolated.
+    ///
+    /// Panics if an edge has a node out of ```0..n```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let y = Complex::new(1., -2.);
+    /// let l = ComplexMatrix::<f64>::laplacian_from_edges(3, vec![(0, 1, y), (1, 2, y)]);
+    /// assert_eq!(l.get(1, 1), Some(&(y * 2.)));
+    /// assert_eq!(l.get(0, 1), Some(&-y));
+    /// assert_eq!(l.get(2, 0), None);
+    ///```
+    pub fn laplacian_from_edges<I>(n: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, Complex<T>)>,
+    {
+        let mut degree = vec![Complex::new(T::zero(), T::zero()); n];
+        let mut m = ComplexMatrix::new();
+        for (i, j, weight) in edges {
+            assert!(i < n && j < n, "the edge ({}, {}) has a node out of bounds", i, j);
+            if i == j {
+                continue;
+            }
+            degree[i] = degree[i] + weight;
+            degree[j] = degree[j] + weight;
+            m.add_element(i, j, -weight);
+            m.add_element(j, i, -weight);
+        }
+        for (i, d) in degree.into_iter().enumerate() {
+            m.add_element(i, i, d);
+        }
+        m
+    }
+}
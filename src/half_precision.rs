@@ -0,0 +1,195 @@
+use crate::index::{to_storage, AsIndex, StorageIndex};
+#[cfg(feature = "eigen")]
+use crate::progress::{self, Phase};
+#[cfg(feature = "eigen")]
+use crate::solvable::sealed::Kernels;
+#[cfg(feature = "eigen")]
+use crate::solver;
+use crate::{ComplexMatrix, SolveError};
+use half::f16;
+use num::complex::Complex;
+
+/// A sparse complex matrix storing its values in half precision (`f16`), for
+/// enormous matrices whose assembly data is not more accurate than that anyway.
+///
+/// The values take half the memory of a ```ComplexMatrix<f32>``` and are widened to
+/// `f32` for the solve. Half precision has about three significant decimal digits
+/// and a range of `6·10⁻⁸` to `65504`: smaller magnitudes are flushed towards zero
+/// and larger ones do not fit, so scale the system first if needed. The solution of
+/// the stored system is only as accurate as the stored values; use
+/// ```solve_refined``` to recover `f32` accuracy from an accurate residual.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HalfMatrix {
+    entries: Vec<Complex<f16>>,
+    rows: Vec<StorageIndex>,
+    cols: Vec<StorageIndex>,
+}
+
+impl HalfMatrix {
+    /// Create an empty matrix.
+    ///```rust
+    /// use sparse_complex::HalfMatrix;
+    /// let m = HalfMatrix::new();
+    ///```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert a ```ComplexMatrix<f32>```, rounding its values to half precision.
+    ///
+    /// Returns `Err` if a value is not finite or overflows `f16`.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, HalfMatrix};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// m.add_element(0, 0, Complex::new(1e5, 0.));
+    /// assert!(HalfMatrix::from_matrix(&m).is_err());
+    ///```
+    pub fn from_matrix(matrix: &ComplexMatrix<f32>) -> Result<Self, SolveError> {
+        let entries = matrix
+            .entries
+            .iter()
+            .map(|v| {
+                to_half(*v).ok_or(SolveError::InvalidInput(
+                    "the value does not fit in half precision",
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HalfMatrix {
+            entries,
+            rows: matrix.rows.clone(),
+            cols: matrix.cols.clone(),
+        })
+    }
+
+    /// Add an element, rounded to half precision, at ```(row, col)```.
+    ///
+    /// Panics if the value is not finite or overflows `f16`.
+    ///```rust
+    /// use sparse_complex::HalfMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = HalfMatrix::new();
+    /// m.add_element(0, 0, Complex::new(0.1, 2.));
+    /// assert_eq!(m.to_f32().get(0, 0), Some(&Complex::new(0.099975586, 2.)));
+    ///```
+    pub fn add_element(&mut self, row: usize, col: usize, value: Complex<f32>) {
+        let value = to_half(value).expect("the value does not fit in half precision");
+        self.entries.push(value);
+        self.rows.push(to_storage(row));
+        self.cols.push(to_storage(col));
+    }
+
+    /// Widen the values to a ```ComplexMatrix<f32>```.
+    pub fn to_f32(&self) -> ComplexMatrix<f32> {
+        let mut m = ComplexMatrix::with_capacity(self.entries.len());
+        for ((row, col), value) in self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter())
+        {
+            m.add_element(
+                row.index(),
+                col.index(),
+                Complex::new(value.re.to_f32(), value.im.to_f32()),
+            );
+        }
+        m
+    }
+
+    /// The heap memory held by the index and value arrays, including their spare capacity.
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.entries.capacity() * core::mem::size_of::<Complex<f16>>()
+            + (self.rows.capacity() + self.cols.capacity()) * core::mem::size_of::<StorageIndex>()
+    }
+
+    /// Solve the stored system `Ax=b` in `f32`.
+    ///
+    /// The widened copy of the values only lives during the solve. Returns `Err` if
+    /// the factorization fails. The result is stored in ```b```.
+    ///```rust
+    /// use sparse_complex::HalfMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = HalfMatrix::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 4.));
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(1., 0.)];
+    /// m.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(0.5, 0.), Complex::new(0., -0.25)]);
+    ///```
+    pub fn solve(&self, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+        self.to_f32().solve(b)
+    }
+
+    /// Solve `Ax=b` with the half-precision matrix as a preconditioner of ```steps```
+    /// steps of iterative refinement.
+    ///
+    /// ```residual(x, r)``` must store `r = b - Ax` computed with the accurate
+    /// operator, e.g. from the assembly data. Each step solves for a correction with
+    /// the single factorization of the stored matrix, so the solution converges to the
+    /// accurate one as long as the stored values are close enough to it.
+    ///
+    /// Returns `Err` if the factorization fails. The result is stored in ```b```.
+    ///```rust
+    /// use sparse_complex::HalfMatrix;
+    /// use num::Complex;
+    ///
+    /// // 0.1 is not exact in half precision.
+    /// let a = Complex::new(0.1f32, 0.);
+    /// let mut m = HalfMatrix::new();
+    /// m.add_element(0, 0, a);
+    ///
+    /// let rhs = Complex::new(1., 0.);
+    /// let mut b = vec![rhs];
+    /// m.solve_refined(&mut b, 3, |x, r| r[0] = rhs - a * x[0]).unwrap();
+    /// assert!((b[0] - Complex::new(10., 0.)).norm() < 1e-5);
+    ///```
+    #[cfg(feature = "eigen")]
+    pub fn solve_refined<F>(
+        &self,
+        b: &mut [Complex<f32>],
+        steps: usize,
+        mut residual: F,
+    ) -> Result<(), SolveError>
+    where
+        F: FnMut(&[Complex<f32>], &mut [Complex<f32>]),
+    {
+        let matrix = self.to_f32();
+        matrix.check_rhs(b.len())?;
+        matrix.debug_validate(b.len());
+        progress::report(Phase::Factorization, Some(0.));
+        let mut context = f32::new_context()?;
+        let info = unsafe { f32::context_factorize(&mut context, &matrix, b.len()) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Factorization, Some(1.));
+        drop(matrix);
+
+        let info = unsafe { f32::context_solve_factorized(&mut context, b) };
+        solver::info_to_result(info)?;
+        let mut r = vec![Complex::new(0., 0.); b.len()];
+        for i in 0..steps {
+            residual(b, &mut r);
+            let info = unsafe { f32::context_solve_factorized(&mut context, &mut r) };
+            solver::info_to_result(info)?;
+            for (x, d) in b.iter_mut().zip(r.iter()) {
+                *x += *d;
+            }
+            progress::report(Phase::Solve, Some((i + 1) as f64 / steps as f64));
+        }
+        Ok(())
+    }
+}
+
+/// Rounds ```value``` to half precision, or None if it is not finite or overflows.
+fn to_half(value: Complex<f32>) -> Option<Complex<f16>> {
+    let half = Complex::new(f16::from_f32(value.re), f16::from_f32(value.im));
+    if half.re.is_finite() && half.im.is_finite() {
+        Some(half)
+    } else {
+        None
+    }
+}
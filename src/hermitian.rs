@@ -0,0 +1,109 @@
+use crate::progress::{self, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use std::os::raw::c_int;
+
+/// The Cholesky solver reports any failure of the factorization as invalid input.
+fn to_result(info: c_int) -> Result<(), SolveError> {
+    match info {
+        solver::INVALID_INPUT => Err(SolveError::InvalidInput(
+            "the matrix is not Hermitian positive definite",
+        )),
+        _ => solver::info_to_result(info),
+    }
+}
+
+#[cfg(feature = "f64")]
+impl ComplexMatrix<f64> {
+    /// Solve the system `Ax=b` for a Hermitian positive definite matrix with a sparse
+    /// `LDLᴴ` (Cholesky) factorization, about twice as fast and half the memory of the
+    /// LU of ```solve```, e.g. for admittance matrices of passive networks. The result
+    /// is stored in ```b```.
+    ///
+    /// Only the lower triangle and the diagonal are read, so it is enough to assemble
+    /// them. The solution uses the
+    /// [Eigen::SimplicialLDLT](https://eigen.tuxfamily.org/dox/classEigen_1_1SimplicialLDLT.html).
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is not
+    /// positive definite.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(4., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 2.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let mut b = vec![Complex::new(6., 0.), Complex::new(0., 4.)];
+    /// m.solve_hermitian(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-5);
+    /// assert!((b[1] - Complex::new(0., 1.)).norm() < 1e-5);
+    ///```
+    pub fn solve_hermitian(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        self.debug_validate(b.len());
+        progress::report(Phase::Factorization, Some(0.));
+        let info = unsafe {
+            solver::solve_hermitian_cpp(
+                self.entries.as_ptr(),
+                self.rows.as_ptr(),
+                self.cols.as_ptr(),
+                self.entries.len(),
+                b.as_mut_ptr(),
+                b.len(),
+            )
+        };
+        to_result(info)?;
+        progress::report(Phase::Solve, Some(1.));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "f32")]
+impl ComplexMatrix<f32> {
+    /// Solve the system `Ax=b` for a Hermitian positive definite matrix with a sparse
+    /// `LDLᴴ` (Cholesky) factorization, about twice as fast and half the memory of the
+    /// LU of ```solve```, e.g. for admittance matrices of passive networks. The result
+    /// is stored in ```b```.
+    ///
+    /// Only the lower triangle and the diagonal are read, so it is enough to assemble
+    /// them. The solution uses the
+    /// [Eigen::SimplicialLDLT](https://eigen.tuxfamily.org/dox/classEigen_1_1SimplicialLDLT.html).
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is not
+    /// positive definite.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// m.add_element(0, 0, Complex::new(4., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 2.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let mut b = vec![Complex::new(6., 0.), Complex::new(0., 4.)];
+    /// m.solve_hermitian(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-5);
+    /// assert!((b[1] - Complex::new(0., 1.)).norm() < 1e-5);
+    ///```
+    pub fn solve_hermitian(&self, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        self.debug_validate(b.len());
+        progress::report(Phase::Factorization, Some(0.));
+        let info = unsafe {
+            solver::solve_hermitian_cpp32(
+                self.entries.as_ptr(),
+                self.rows.as_ptr(),
+                self.cols.as_ptr(),
+                self.entries.len(),
+                b.as_mut_ptr(),
+                b.len(),
+            )
+        };
+        to_result(info)?;
+        progress::report(Phase::Solve, Some(1.));
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{ComplexMatrix, Triplet};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A row index, for assembly code that wants the compiler to catch swapped row and
+/// column arguments. See ```ComplexMatrix::add_at```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Row(pub usize);
+
+/// A column index, see ```Row```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Col(pub usize);
+
+impl From<Row> for usize {
+    fn from(row: Row) -> Self {
+        row.0
+    }
+}
+
+impl From<Col> for usize {
+    fn from(col: Col) -> Self {
+        col.0
+    }
+}
+
+/// The integer type of the row and column indices stored by the matrices: `u32`
+/// with the `index32` feature, halving their memory on 64-bit targets, and `usize`
+/// otherwise. It is shared with the C++ solver.
+#[cfg(feature = "index32")]
+pub(crate) type StorageIndex = u32;
+#[cfg(not(feature = "index32"))]
+pub(crate) type StorageIndex = usize;
+
+/// Converts an index to ```StorageIndex```.
+///
+/// Panics with the `index32` feature if ```i``` does not fit in 32 bits.
+#[inline]
+pub(crate) fn to_storage(i: usize) -> StorageIndex {
+    #[cfg(feature = "index32")]
+    let i = StorageIndex::try_from(i)
+        .expect("the index does not fit in the 32-bit storage of the `index32` feature");
+    i
+}
+
+/// A stored row or column index, converted back to `usize` on use.
+pub(crate) trait AsIndex: Copy {
+    fn index(self) -> usize;
+}
+
+impl AsIndex for usize {
+    #[inline]
+    fn index(self) -> usize {
+        self
+    }
+}
+
+#[cfg(feature = "index32")]
+impl AsIndex for u32 {
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl<T: Float> From<(Row, Col, Complex<T>)> for Triplet<T> {
+    fn from((row, col, value): (Row, Col, Complex<T>)) -> Self {
+        Triplet::new(row.0, col.0, value)
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Add an element at a typed location, like ```add_element```.
+    ///
+    /// The arguments cannot be swapped by mistake: ```add_at(Col(1), Row(0), v)``` does
+    /// not compile. Typed ```(Row, Col, value)``` tuples are also accepted by
+    /// ```from_entries```, ```add_elements``` and ```extend```.
+    ///```rust
+    /// use sparse_complex::{Col, ComplexMatrix, Row};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_at(Row(0), Col(1), Complex::new(1., 0.));
+    /// m.extend(vec![(Row(1), Col(0), Complex::new(0., 1.))]);
+    ///
+    /// assert_eq!(m.get_at(Row(0), Col(1)), Some(&Complex::new(1., 0.)));
+    /// assert_eq!(m.get_at(Row(1), Col(0)), Some(&Complex::new(0., 1.)));
+    ///```
+    pub fn add_at(&mut self, row: Row, col: Col, value: Complex<T>) {
+        self.add_element(row.0, col.0, value);
+    }
+
+    /// Returns the element at a typed location, like ```get```.
+    pub fn get_at(&self, row: Row, col: Col) -> Option<&Complex<T>> {
+        self.get(row.0, col.0)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::index::StorageIndex;
+#[cfg(feature = "eigen")]
+use crate::solver;
+use std::fmt;
+#[cfg(feature = "eigen")]
+use std::os::raw::c_int;
+
+/// A description of the build that produces the solutions, for bug reports and logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolverInfo {
+    /// Version of this crate.
+    pub version: &'static str,
+    /// The sparse direct solver used by ```ComplexMatrix::solve```.
+    pub backend: &'static str,
+    /// Version of Eigen the backend was compiled against, as `(world, major, minor)`,
+    /// or `None` without the `eigen` feature.
+    pub eigen_version: Option<(u32, u32, u32)>,
+    /// Width in bits of the stored row and column indices passed to the backend, 32
+    /// with the `index32` feature.
+    pub index_bits: u32,
+    /// The scalar types whose solver kernels are compiled in.
+    pub scalar_types: Vec<&'static str>,
+    /// The enabled cargo features, including `std`.
+    pub features: Vec<&'static str>,
+}
+
+impl fmt::Display for SolverInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sparse_complex {} ({}", self.version, self.backend)?;
+        if let Some((world, major, minor)) = self.eigen_version {
+            write!(f, " with Eigen {}.{}.{}", world, major, minor)?;
+        }
+        write!(
+            f,
+            ", {}-bit indices, scalars: {}, features: [{}])",
+            self.index_bits,
+            self.scalar_types.join(", "),
+            self.features.join(", ")
+        )
+    }
+}
+
+/// Returns the backend, Eigen version, index width, scalar types and features of this build.
+///```rust
+/// let info = sparse_complex::solver_info();
+/// if info.features.contains(&"eigen") {
+///     assert_eq!(info.backend, "Eigen SparseLU");
+///     assert_eq!(info.eigen_version.unwrap().0, 3);
+/// }
+/// println!("{}", info);
+///```
+pub fn solver_info() -> SolverInfo {
+    #[cfg(feature = "eigen")]
+    let (backend, eigen_version) = {
+        let (mut world, mut major, mut minor): (c_int, c_int, c_int) = (0, 0, 0);
+        unsafe { solver::eigen_version_cpp(&mut world, &mut major, &mut minor) };
+        ("Eigen SparseLU", Some((world as u32, major as u32, minor as u32)))
+    };
+    #[cfg(not(feature = "eigen"))]
+    let (backend, eigen_version) = ("Rust SparseLu", None);
+
+    let mut scalar_types = Vec::new();
+    let mut features = Vec::new();
+    if cfg!(feature = "std") {
+        features.push("std");
+    }
+    if cfg!(feature = "eigen") {
+        features.push("eigen");
+    }
+    if cfg!(feature = "f32") {
+        scalar_types.push("Complex<f32>");
+        features.push("f32");
+    }
+    if cfg!(feature = "f64") {
+        scalar_types.push("Complex<f64>");
+        features.push("f64");
+    }
+    if cfg!(feature = "async") {
+        features.push("async");
+    }
+    if cfg!(feature = "cuda") {
+        features.push("cuda");
+    }
+    if cfg!(feature = "debug-validate") {
+        features.push("debug-validate");
+    }
+    if cfg!(feature = "exact") {
+        features.push("exact");
+    }
+    if cfg!(feature = "f16") {
+        features.push("f16");
+    }
+    if cfg!(feature = "index32") {
+        features.push("index32");
+    }
+    if cfg!(feature = "klu") {
+        features.push("klu");
+    }
+    if cfg!(feature = "umfpack") {
+        features.push("umfpack");
+    }
+    if cfg!(feature = "openmp") {
+        features.push("openmp");
+    }
+    if cfg!(feature = "rayon") {
+        features.push("rayon");
+    }
+
+    SolverInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        backend,
+        eigen_version,
+        index_bits: StorageIndex::BITS,
+        scalar_types,
+        features,
+    }
+}
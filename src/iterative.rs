@@ -0,0 +1,653 @@
+use crate::compressed::{compress, Csr};
+use crate::krylov::{axpy, dot, norm};
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::IncompleteLut;
+use crate::{ComplexMatrix, Jacobi, Preconditioner, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// Options of the iterative solvers, see ```ComplexMatrix::solve_iterative```.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IterOptions<T: Float> {
+    tolerance: T,
+    max_iterations: usize,
+    restart: usize,
+    drop_tolerance: T,
+    fill_factor: usize,
+}
+
+impl<T: Float> Default for IterOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> IterOptions<T> {
+    /// The default options: a relative tolerance of `√ε`, at most 1000 iterations,
+    /// GMRES restarted every 30 iterations and an incomplete LU dropping below `√ε`
+    /// with a fill factor of 10.
+    pub fn new() -> Self {
+        IterOptions {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+            restart: 30,
+            drop_tolerance: T::epsilon().sqrt(),
+            fill_factor: 10,
+        }
+    }
+
+    /// Sets the tolerance on the residual norm relative to the norm of `b`.
+    pub fn set_tolerance(&mut self, tolerance: T) {
+        self.tolerance = tolerance;
+    }
+
+    /// The tolerance on the relative residual norm.
+    pub fn tolerance(&self) -> T {
+        self.tolerance
+    }
+
+    /// Sets the maximum number of iterations.
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    /// The maximum number of iterations.
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Sets the number of GMRES iterations between restarts, i.e. the dimension of
+    /// the Krylov subspace. A longer restart converges in fewer iterations but stores
+    /// more vectors and orthogonalizes against all of them.
+    ///
+    /// # Panics
+    /// Panics if ```restart``` is zero.
+    pub fn set_restart(&mut self, restart: usize) {
+        assert!(restart > 0, "the restart length must be positive");
+        self.restart = restart;
+    }
+
+    /// The number of GMRES iterations between restarts.
+    pub fn restart(&self) -> usize {
+        self.restart
+    }
+
+    /// Sets the drop tolerance of the incomplete LU preconditioner, see
+    /// ```IncompleteLut::new```.
+    pub fn set_drop_tolerance(&mut self, drop_tolerance: T) {
+        self.drop_tolerance = drop_tolerance;
+    }
+
+    /// The drop tolerance of the incomplete LU preconditioner.
+    pub fn drop_tolerance(&self) -> T {
+        self.drop_tolerance
+    }
+
+    /// Sets the fill factor of the incomplete LU preconditioner, see
+    /// ```IncompleteLut::new```.
+    pub fn set_fill_factor(&mut self, fill_factor: usize) {
+        self.fill_factor = fill_factor;
+    }
+
+    /// The fill factor of the incomplete LU preconditioner.
+    pub fn fill_factor(&self) -> usize {
+        self.fill_factor
+    }
+}
+
+/// The outcome of a converged iterative solve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvergenceReport<T: Float> {
+    /// The number of iterations run.
+    pub iterations: usize,
+    /// The final residual norm `‖b - Ax‖` relative to `‖b‖`.
+    pub residual: T,
+    /// The relative residual norm before the first iteration and after each of
+    /// them, e.g. to plot the convergence or spot a stall. These are the norms
+    /// updated by the recurrences of the method, which may drift slightly from
+    /// ```residual```.
+    pub history: Vec<T>,
+}
+
+impl<T: Float> ConvergenceReport<T> {
+    /// Stores ```x``` into ```b``` and reports its true residual.
+    fn finish(a: &Csr<T>, b: &mut [Complex<T>], x: &[Complex<T>], history: Vec<T>) -> Self {
+        let b_norm = norm(b);
+        let residual = relative(norm(&a.residual(b, x)), b_norm);
+        b.copy_from_slice(x);
+        ConvergenceReport {
+            iterations: history.len() - 1,
+            residual,
+            history,
+        }
+    }
+}
+
+/// ```norm``` relative to ```b_norm```, or zero for a zero right-hand side.
+fn relative<T: Float>(norm: T, b_norm: T) -> T {
+    if b_norm > T::zero() {
+        norm / b_norm
+    } else {
+        T::zero()
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` with the right-preconditioned BiCGSTAB method, starting
+    /// from `x = 0`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size, if the method breaks
+    /// down, or if it does not reach the tolerance within the maximum number of
+    /// iterations, in which case ```b``` holds the last iterate.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions, Jacobi};
+    /// use num::Complex;
+    ///
+    /// let n = 20;
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(4., 1.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///         m.add_element(i + 1, i, Complex::new(-1., 0.5));
+    ///     }
+    /// }
+    ///
+    /// let jacobi = Jacobi::new(&m).unwrap();
+    /// let mut b = vec![Complex::new(1., 0.); n];
+    /// let report = m.solve_bicgstab(&jacobi, &mut b, &IterOptions::new()).unwrap();
+    /// assert!(report.residual < 1e-8);
+    ///```
+    pub fn solve_bicgstab<P: Preconditioner<T>>(
+        &self,
+        preconditioner: &P,
+        b: &mut [Complex<T>],
+        options: &IterOptions<T>,
+    ) -> Result<ConvergenceReport<T>, SolveError> {
+        let n = b.len();
+        self.check_rhs(n)?;
+        let a = compress(n, &self.rows, &self.cols, &self.entries);
+        let zero = Complex::new(T::zero(), T::zero());
+        let precondition = |v: &[Complex<T>]| {
+            let mut z = vec![zero; n];
+            preconditioner.apply(v, &mut z);
+            z
+        };
+
+        let b_norm = norm(b);
+        let mut x = vec![zero; n];
+        let mut r = b.to_vec();
+        let target = options.tolerance * b_norm;
+        let mut history = vec![relative(b_norm, b_norm)];
+        if b_norm <= target {
+            return Ok(ConvergenceReport::finish(&a, b, &x, history));
+        }
+
+        let mut r0 = r.clone();
+        let mut r0_sqr = dot(&r0, &r0).re;
+        let (mut rho, mut alpha, mut omega) = (zero, zero, zero);
+        let (mut v, mut p) = (vec![zero; n], vec![zero; n]);
+        let mut restart = true;
+        loop {
+            if history.len() > options.max_iterations {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(SolveError::NoConvergence);
+            }
+
+            let mut rho_new = dot(&r0, &r);
+            if rho_new.norm() <= T::epsilon() * T::epsilon() * r0_sqr {
+                // r has become orthogonal to r0: restart from the current residual.
+                r0.copy_from_slice(&r);
+                r0_sqr = dot(&r0, &r0).re;
+                rho_new = Complex::new(r0_sqr, T::zero());
+                restart = true;
+            }
+            if restart {
+                p.copy_from_slice(&r);
+            } else {
+                let beta = (rho_new / rho) * (alpha / omega);
+                for ((pi, ri), vi) in p.iter_mut().zip(r.iter()).zip(v.iter()) {
+                    *pi = *ri + beta * (*pi - omega * *vi);
+                }
+            }
+            rho = rho_new;
+
+            let y = precondition(&p);
+            v = a.mul_vec(&y);
+            let r0v = dot(&r0, &v);
+            if r0v == zero {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(SolveError::NumericalIssue);
+            }
+            alpha = rho / r0v;
+            let s = r
+                .iter()
+                .zip(v.iter())
+                .map(|(ri, vi)| *ri - alpha * *vi)
+                .collect::<Vec<_>>();
+
+            let z = precondition(&s);
+            let t = a.mul_vec(&z);
+            let tt = dot(&t, &t).re;
+            omega = if tt > T::zero() {
+                dot(&t, &s) / tt
+            } else {
+                zero
+            };
+            restart = omega == zero;
+            for ((xi, yi), zi) in x.iter_mut().zip(y.iter()).zip(z.iter()) {
+                *xi = *xi + alpha * *yi + omega * *zi;
+            }
+            for ((ri, si), ti) in r.iter_mut().zip(s.iter()).zip(t.iter()) {
+                *ri = *si - omega * *ti;
+            }
+            let r_norm = norm(&r);
+            history.push(relative(r_norm, b_norm));
+            if r_norm <= target {
+                return Ok(ConvergenceReport::finish(&a, b, &x, history));
+            }
+        }
+    }
+
+    /// Solve the system `Ax=b` with the right-preconditioned restarted GMRES(m)
+    /// method, starting from `x = 0`, with `m` set by ```IterOptions::set_restart```.
+    /// The result is stored in ```b```.
+    ///
+    /// GMRES minimizes the residual over the Krylov subspace, so unlike BiCGSTAB its
+    /// residual never increases within a cycle, which makes it robust for
+    /// nonsymmetric systems where BiCGSTAB stagnates or breaks down.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or if the tolerance is
+    /// not reached within the maximum number of iterations, in which case ```b```
+    /// holds the last iterate.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IdentityPreconditioner, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let n = 50;
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(2., 1.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///     }
+    /// }
+    ///
+    /// let mut options = IterOptions::new();
+    /// options.set_restart(10);
+    /// let mut b = vec![Complex::new(1., 0.); n];
+    /// let report = m.solve_gmres(&IdentityPreconditioner, &mut b, &options).unwrap();
+    /// assert!(report.residual < 1e-8);
+    /// assert_eq!(report.history.len(), report.iterations + 1);
+    ///```
+    pub fn solve_gmres<P: Preconditioner<T>>(
+        &self,
+        preconditioner: &P,
+        b: &mut [Complex<T>],
+        options: &IterOptions<T>,
+    ) -> Result<ConvergenceReport<T>, SolveError> {
+        let n = b.len();
+        self.check_rhs(n)?;
+        let a = compress(n, &self.rows, &self.cols, &self.entries);
+        let zero = Complex::new(T::zero(), T::zero());
+        let precondition = |v: &[Complex<T>]| {
+            let mut z = vec![zero; n];
+            preconditioner.apply(v, &mut z);
+            z
+        };
+
+        let b_norm = norm(b);
+        let target = options.tolerance * b_norm;
+        let mut x = vec![zero; n];
+        let mut r = b.to_vec();
+        let mut history = vec![relative(b_norm, b_norm)];
+        loop {
+            let beta = norm(&r);
+            if beta <= target {
+                return Ok(ConvergenceReport::finish(&a, b, &x, history));
+            }
+            let iterations = history.len() - 1;
+            if iterations >= options.max_iterations {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(SolveError::NoConvergence);
+            }
+
+            // Arnoldi process, with the Hessenberg columns reduced to upper triangular
+            // form by Givens rotations as they are built. |g[k + 1]| is the residual
+            // norm after k + 1 iterations of the cycle.
+            let m = options.restart.min(options.max_iterations - iterations);
+            let mut v = vec![r.iter().map(|ri| *ri / beta).collect::<Vec<_>>()];
+            let mut h: Vec<Vec<Complex<T>>> = Vec::with_capacity(m);
+            let mut rotations: Vec<(T, Complex<T>)> = Vec::with_capacity(m);
+            let mut g = vec![Complex::new(beta, T::zero())];
+            while h.len() < m {
+                let k = h.len();
+                let mut w = a.mul_vec(&precondition(&v[k]));
+                let mut column = Vec::with_capacity(k + 2);
+                for vi in v.iter() {
+                    let s = dot(vi, &w);
+                    axpy(-s, vi, &mut w);
+                    column.push(s);
+                }
+                let w_norm = norm(&w);
+                column.push(Complex::new(w_norm, T::zero()));
+
+                for (i, (c, s)) in rotations.iter().enumerate() {
+                    let (hi, hj) = (column[i], column[i + 1]);
+                    column[i] = hi * *c + *s * hj;
+                    column[i + 1] = hj * *c - s.conj() * hi;
+                }
+                let (c, s) = givens(column[k], column[k + 1]);
+                column[k] = column[k] * c + s * column[k + 1];
+                column[k + 1] = zero;
+                g.push(-s.conj() * g[k]);
+                g[k] = g[k] * c;
+                rotations.push((c, s));
+                h.push(column);
+                history.push(relative(g[k + 1].norm(), b_norm));
+
+                if g[k + 1].norm() <= target || w_norm == T::zero() {
+                    break;
+                }
+                v.push(w.iter().map(|wi| *wi / w_norm).collect());
+            }
+
+            // Back substitution of the triangular system and update of x.
+            let k = h.len();
+            let mut y = vec![zero; k];
+            for i in (0..k).rev() {
+                let sum = (i + 1..k).fold(g[i], |acc, j| acc - h[j][i] * y[j]);
+                if h[i][i] == zero {
+                    ConvergenceReport::finish(&a, b, &x, history);
+                    return Err(SolveError::NumericalIssue);
+                }
+                y[i] = sum / h[i][i];
+            }
+            let mut correction = vec![zero; n];
+            for (vi, yi) in v.iter().zip(y.iter()) {
+                axpy(*yi, vi, &mut correction);
+            }
+            axpy(
+                Complex::new(T::one(), T::zero()),
+                &precondition(&correction),
+                &mut x,
+            );
+            r = a.residual(b, &x);
+        }
+    }
+
+    /// Solve the system `Ax=b` for a Hermitian positive definite matrix with the
+    /// conjugate gradient method preconditioned by its diagonal (Jacobi), starting
+    /// from `x = 0`, e.g. for large Helmholtz-like finite element systems with losses
+    /// that do not fit a direct factorization. The result is stored in ```b```.
+    ///
+    /// Unlike ```solve_hermitian```, both triangles of the matrix must be stored.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size, if a diagonal entry is
+    /// zero, if the matrix turns out not to be positive definite, or in the cases of
+    /// ```solve_pcg```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let n = 50;
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(2. + i as f64, 0.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.5));
+    ///         m.add_element(i + 1, i, Complex::new(-1., -0.5));
+    ///     }
+    /// }
+    ///
+    /// let mut b = vec![Complex::new(1., 0.); n];
+    /// let report = m.solve_cg(&mut b, &IterOptions::new()).unwrap();
+    /// assert!(report.residual < 1e-8);
+    ///```
+    pub fn solve_cg(
+        &self,
+        b: &mut [Complex<T>],
+        options: &IterOptions<T>,
+    ) -> Result<ConvergenceReport<T>, SolveError> {
+        self.check_rhs(b.len())?;
+        let jacobi = Jacobi::new(self)?;
+        self.solve_pcg(&jacobi, b, options)
+    }
+
+    /// Solve the system `Ax=b` for a Hermitian positive definite matrix with the
+    /// preconditioned conjugate gradient method, starting from `x = 0`. The
+    /// preconditioner must be Hermitian positive definite too. The result is stored
+    /// in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size, if the matrix or the
+    /// preconditioner turns out not to be positive definite, or if the tolerance is
+    /// not reached within the maximum number of iterations, in which case ```b```
+    /// holds the last iterate.
+    pub fn solve_pcg<P: Preconditioner<T>>(
+        &self,
+        preconditioner: &P,
+        b: &mut [Complex<T>],
+        options: &IterOptions<T>,
+    ) -> Result<ConvergenceReport<T>, SolveError> {
+        let n = b.len();
+        self.check_rhs(n)?;
+        let a = compress(n, &self.rows, &self.cols, &self.entries);
+        let zero = Complex::new(T::zero(), T::zero());
+        let precondition = |v: &[Complex<T>]| {
+            let mut z = vec![zero; n];
+            preconditioner.apply(v, &mut z);
+            z
+        };
+        let not_definite =
+            SolveError::InvalidInput("the matrix is not Hermitian positive definite");
+
+        let b_norm = norm(b);
+        let target = options.tolerance * b_norm;
+        let mut x = vec![zero; n];
+        let mut r = b.to_vec();
+        let mut history = vec![relative(b_norm, b_norm)];
+        let mut p = precondition(&r);
+        let mut rz = dot(&r, &p).re;
+        loop {
+            if norm(&r) <= target {
+                return Ok(ConvergenceReport::finish(&a, b, &x, history));
+            }
+            if history.len() > options.max_iterations {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(SolveError::NoConvergence);
+            }
+            if rz <= T::zero() {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(not_definite);
+            }
+
+            let q = a.mul_vec(&p);
+            let pq = dot(&p, &q).re;
+            if pq <= T::zero() {
+                ConvergenceReport::finish(&a, b, &x, history);
+                return Err(not_definite);
+            }
+            let alpha = Complex::new(rz / pq, T::zero());
+            axpy(alpha, &p, &mut x);
+            axpy(-alpha, &q, &mut r);
+            history.push(relative(norm(&r), b_norm));
+
+            let z = precondition(&r);
+            let rz_new = dot(&r, &z).re;
+            let beta = rz_new / rz;
+            rz = rz_new;
+            for (pi, zi) in p.iter_mut().zip(z.iter()) {
+                *pi = *zi + *pi * beta;
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl ComplexMatrix<f64> {
+    /// Solve the system `Ax=b` with BiCGSTAB preconditioned by an incomplete LU
+    /// factorization, for matrices too large to factorize directly. The result is
+    /// stored in ```b```.
+    ///
+    /// Returns `Err` if the preconditioner cannot be computed or in the cases of
+    /// ```solve_bicgstab```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let n = 100;
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(4., 1.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///         m.add_element(i + 1, i, Complex::new(-1., 0.5));
+    ///     }
+    /// }
+    ///
+    /// let mut b = vec![Complex::new(1., 0.); n];
+    /// let report = m.solve_iterative(&mut b, &IterOptions::new()).unwrap();
+    /// assert!(report.residual < 1e-8);
+    ///```
+    pub fn solve_iterative(
+        &self,
+        b: &mut [Complex<f64>],
+        options: &IterOptions<f64>,
+    ) -> Result<ConvergenceReport<f64>, SolveError> {
+        self.check_rhs(b.len())?;
+        let ilut = IncompleteLut::<f64>::new(self, options.drop_tolerance, options.fill_factor)?;
+        self.solve_bicgstab(&ilut, b, options)
+    }
+
+    /// Solve the system `Ax=b` with ```solve_iterative```, falling back to the direct
+    /// ```solve``` if the iteration fails, e.g. does not converge within
+    /// ```max_iterations``` or the incomplete LU breaks down. The result is stored in
+    /// ```b```.
+    ///
+    /// Returns the convergence report of the iterative solve, or `None` if the direct
+    /// solver was used. Returns `Err` if the direct solver fails.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(3., 1.));
+    ///
+    /// let mut options = IterOptions::new();
+    /// options.set_max_iterations(0);
+    /// let mut b = vec![Complex::new(3., 0.), Complex::new(4., 1.)];
+    /// assert_eq!(m.solve_auto(&mut b, &options), Ok(None));
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-8);
+    /// assert!((b[1] - Complex::new(1., 0.)).norm() < 1e-8);
+    ///```
+    pub fn solve_auto(
+        &self,
+        b: &mut [Complex<f64>],
+        options: &IterOptions<f64>,
+    ) -> Result<Option<ConvergenceReport<f64>>, SolveError> {
+        self.check_rhs(b.len())?;
+        let mut x = b.to_vec();
+        match self.solve_iterative(&mut x, options) {
+            Ok(report) => {
+                b.copy_from_slice(&x);
+                Ok(Some(report))
+            }
+            Err(_) => self.solve(b).map(|_| None),
+        }
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl ComplexMatrix<f32> {
+    /// Solve the system `Ax=b` with BiCGSTAB preconditioned by an incomplete LU
+    /// factorization, for matrices too large to factorize directly. The result is
+    /// stored in ```b```.
+    ///
+    /// Returns `Err` if the preconditioner cannot be computed or in the cases of
+    /// ```solve_bicgstab```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let n = 100;
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(4., 1.));
+    ///     if i + 1 < n {
+    ///         m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///         m.add_element(i + 1, i, Complex::new(-1., 0.5));
+    ///     }
+    /// }
+    ///
+    /// let mut b = vec![Complex::new(1., 0.); n];
+    /// let report = m.solve_iterative(&mut b, &IterOptions::new()).unwrap();
+    /// assert!(report.residual < 1e-3);
+    ///```
+    pub fn solve_iterative(
+        &self,
+        b: &mut [Complex<f32>],
+        options: &IterOptions<f32>,
+    ) -> Result<ConvergenceReport<f32>, SolveError> {
+        self.check_rhs(b.len())?;
+        let ilut = IncompleteLut::<f32>::new(self, options.drop_tolerance, options.fill_factor)?;
+        self.solve_bicgstab(&ilut, b, options)
+    }
+
+    /// Solve the system `Ax=b` with ```solve_iterative```, falling back to the direct
+    /// ```solve``` if the iteration fails, e.g. does not converge within
+    /// ```max_iterations``` or the incomplete LU breaks down. The result is stored in
+    /// ```b```.
+    ///
+    /// Returns the convergence report of the iterative solve, or `None` if the direct
+    /// solver was used. Returns `Err` if the direct solver fails.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IterOptions};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(3., 1.));
+    ///
+    /// let mut options = IterOptions::new();
+    /// options.set_max_iterations(0);
+    /// let mut b = vec![Complex::new(3., 0.), Complex::new(4., 1.)];
+    /// assert_eq!(m.solve_auto(&mut b, &options), Ok(None));
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-3);
+    /// assert!((b[1] - Complex::new(1., 0.)).norm() < 1e-3);
+    ///```
+    pub fn solve_auto(
+        &self,
+        b: &mut [Complex<f32>],
+        options: &IterOptions<f32>,
+    ) -> Result<Option<ConvergenceReport<f32>>, SolveError> {
+        self.check_rhs(b.len())?;
+        let mut x = b.to_vec();
+        match self.solve_iterative(&mut x, options) {
+            Ok(report) => {
+                b.copy_from_slice(&x);
+                Ok(Some(report))
+            }
+            Err(_) => self.solve(b).map(|_| None),
+        }
+    }
+}
+
+/// The rotation `[c s; -s̄ c]`, with `c` real, mapping `(a, b)` to `(r, 0)`.
+fn givens<T: Float>(a: Complex<T>, b: Complex<T>) -> (T, Complex<T>) {
+    let rho = (a.norm_sqr() + b.norm_sqr()).sqrt();
+    if rho == T::zero() {
+        (T::one(), Complex::new(T::zero(), T::zero()))
+    } else if a.norm() == T::zero() {
+        (T::zero(), Complex::new(T::one(), T::zero()))
+    } else {
+        let phase = a / a.norm();
+        (a.norm() / rho, phase * b.conj() / rho)
+    }
+}
@@ -0,0 +1,258 @@
+use crate::backend::{Factorization, SolverBackend};
+use crate::compressed::compress;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// Values of `Common.status` set by KLU.
+const KLU_OK: c_int = 0;
+const KLU_SINGULAR: c_int = 1;
+const KLU_OUT_OF_MEMORY: c_int = -2;
+const KLU_TOO_LARGE: c_int = -4;
+
+/// `klu_l_common`, the options and statistics of the `SuiteSparse_long` interface.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct KluCommon {
+    tol: f64,
+    memgrow: f64,
+    initmem_amd: f64,
+    initmem: f64,
+    maxwork: f64,
+    btf: i64,
+    ordering: i64,
+    scale: i64,
+    user_order: Option<unsafe extern "C" fn(i64, *mut i64, *mut i64, *mut i64, *mut KluCommon) -> i64>,
+    user_data: *mut c_void,
+    halt_if_singular: i64,
+    status: i64,
+    nrealloc: i64,
+    structural_rank: i64,
+    numerical_rank: i64,
+    singular_col: i64,
+    noffdiag: i64,
+    flops: f64,
+    rcond: f64,
+    condest: f64,
+    rgrowth: f64,
+    work: f64,
+    memusage: usize,
+    mempeak: usize,
+}
+
+#[link(name = "klu")]
+extern "C" {
+    fn klu_l_defaults(common: *mut KluCommon) -> i64;
+    fn klu_l_analyze(n: i64, ap: *const i64, ai: *const i64, common: *mut KluCommon) -> *mut c_void;
+    fn klu_l_free_symbolic(symbolic: *mut *mut c_void, common: *mut KluCommon) -> i64;
+    fn klu_zl_factor(
+        ap: *const i64,
+        ai: *const i64,
+        ax: *const f64,
+        symbolic: *mut c_void,
+        common: *mut KluCommon,
+    ) -> *mut c_void;
+    fn klu_zl_solve(
+        symbolic: *mut c_void,
+        numeric: *mut c_void,
+        ldim: i64,
+        nrhs: i64,
+        b: *mut f64,
+        common: *mut KluCommon,
+    ) -> i64;
+    fn klu_zl_tsolve(
+        symbolic: *mut c_void,
+        numeric: *mut c_void,
+        ldim: i64,
+        nrhs: i64,
+        b: *mut f64,
+        conj_solve: c_int,
+        common: *mut KluCommon,
+    ) -> i64;
+    fn klu_zl_free_numeric(numeric: *mut *mut c_void, common: *mut KluCommon) -> i64;
+}
+
+/// Maps the status of a failed KLU call to a ```SolveError```.
+fn status_to_error(status: i64) -> SolveError {
+    match status as c_int {
+        KLU_SINGULAR => SolveError::Singular,
+        KLU_OUT_OF_MEMORY => SolveError::OutOfMemory,
+        KLU_TOO_LARGE => SolveError::Unsupported("the matrix is too large for KLU"),
+        _ => SolveError::InvalidInput("KLU rejected the matrix"),
+    }
+}
+
+/// The SuiteSparse KLU engine, factorizing with ```ComplexMatrix::factorize_klu```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KluBackend;
+
+impl SolverBackend<f64> for KluBackend {
+    fn name(&self) -> &'static str {
+        "SuiteSparse KLU"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<f64>) -> Result<Box<dyn Factorization<f64>>, SolveError> {
+        Ok(Box::new(matrix.factorize_klu()?))
+    }
+}
+
+/// A sparse LU factorization computed by SuiteSparse KLU, created by
+/// ```ComplexMatrix::factorize_klu```.
+///
+/// KLU permutes the matrix to block triangular form and factorizes each diagonal
+/// block separately, which suits the very sparse, nearly decoupled matrices of
+/// circuit simulation (modified nodal analysis).
+pub struct KluLu {
+    symbolic: *mut c_void,
+    numeric: *mut c_void,
+    common: KluCommon,
+    size: usize,
+}
+
+impl ComplexMatrix<f64> {
+    /// Compute a sparse LU factorization of the matrix with SuiteSparse KLU.
+    ///
+    /// Returns `Err` if the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let lu = m.factorize_klu().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 1.), Complex::new(1., 0.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn factorize_klu(&self) -> Result<KluLu, SolveError> {
+        let n = self.dimension();
+        let by_col = compress(n, &self.cols, &self.rows, &self.entries);
+        let indptr = by_col.indptr.iter().map(|p| *p as i64).collect::<Vec<_>>();
+        let indices = by_col.indices.iter().map(|i| *i as i64).collect::<Vec<_>>();
+
+        let mut lu = KluLu {
+            symbolic: ptr::null_mut(),
+            numeric: ptr::null_mut(),
+            common: unsafe { std::mem::zeroed() },
+            size: n,
+        };
+        unsafe {
+            klu_l_defaults(&mut lu.common);
+            lu.symbolic = klu_l_analyze(n as i64, indptr.as_ptr(), indices.as_ptr(), &mut lu.common);
+            if lu.symbolic.is_null() {
+                return Err(status_to_error(lu.common.status));
+            }
+            lu.numeric = klu_zl_factor(
+                indptr.as_ptr(),
+                indices.as_ptr(),
+                by_col.values.as_ptr() as *const f64,
+                lu.symbolic,
+                &mut lu.common,
+            );
+        }
+        if lu.numeric.is_null() || lu.common.status != KLU_OK as i64 {
+            return Err(status_to_error(lu.common.status));
+        }
+        Ok(lu)
+    }
+}
+
+impl KluLu {
+    /// The order of the factorized matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        if b.len() != self.size {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let mut common = self.common;
+        let ldim = self.size as i64;
+        let ok = unsafe {
+            klu_zl_solve(
+                self.symbolic,
+                self.numeric,
+                ldim,
+                1,
+                b.as_mut_ptr() as *mut f64,
+                &mut common,
+            )
+        };
+        if ok == 0 {
+            return Err(status_to_error(common.status));
+        }
+        Ok(())
+    }
+
+    /// Solve the system `Aᵀx=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_transposed(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_transposed_conj(b, false)
+    }
+
+    /// Solve the system `Aᴴx=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_transposed_conj(b, true)
+    }
+
+    fn solve_transposed_conj(&self, b: &mut [Complex<f64>], conj: bool) -> Result<(), SolveError> {
+        if b.len() != self.size {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let mut common = self.common;
+        let ldim = self.size as i64;
+        let ok = unsafe {
+            klu_zl_tsolve(
+                self.symbolic,
+                self.numeric,
+                ldim,
+                1,
+                b.as_mut_ptr() as *mut f64,
+                conj as c_int,
+                &mut common,
+            )
+        };
+        if ok == 0 {
+            return Err(status_to_error(common.status));
+        }
+        Ok(())
+    }
+}
+
+impl Factorization<f64> for KluLu {
+    fn size(&self) -> usize {
+        KluLu::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        KluLu::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        KluLu::solve_adjoint(self, b)
+    }
+}
+
+impl Drop for KluLu {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.numeric.is_null() {
+                klu_zl_free_numeric(&mut self.numeric, &mut self.common);
+            }
+            if !self.symbolic.is_null() {
+                klu_l_free_symbolic(&mut self.symbolic, &mut self.common);
+            }
+        }
+    }
+}
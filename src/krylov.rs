@@ -0,0 +1,93 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// The inner product `xᴴy`.
+pub(crate) fn dot<T: Float>(x: &[Complex<T>], y: &[Complex<T>]) -> Complex<T> {
+    x.iter()
+        .zip(y.iter())
+        .fold(Complex::new(T::zero(), T::zero()), |acc, (xi, yi)| {
+            acc + xi.conj() * *yi
+        })
+}
+
+/// The Euclidean norm of ```x```.
+pub(crate) fn norm<T: Float>(x: &[Complex<T>]) -> T {
+    x.iter().fold(T::zero(), |acc, v| acc + v.norm_sqr()).sqrt()
+}
+
+/// Computes `y += ax`.
+pub(crate) fn axpy<T: Float>(a: Complex<T>, x: &[Complex<T>], y: &mut [Complex<T>]) {
+    for (yi, xi) in y.iter_mut().zip(x.iter()) {
+        *yi = *yi + a * *xi;
+    }
+}
+
+/// Orthonormalize ```columns``` in place with classical Gram-Schmidt applied twice and
+/// return the row-major upper triangular `R` of `QR`.
+///
+/// Returns None if a column loses more than ```1 - tolerance``` of its norm, i.e. the
+/// columns are numerically dependent.
+pub(crate) fn orthonormalize<T: Float>(
+    columns: &mut [Vec<Complex<T>>],
+    tolerance: T,
+) -> Option<Vec<Complex<T>>> {
+    let k = columns.len();
+    let mut r = vec![Complex::new(T::zero(), T::zero()); k * k];
+    for j in 0..k {
+        let (done, rest) = columns.split_at_mut(j);
+        let column = &mut rest[0];
+        let original = norm(column);
+        for _ in 0..2 {
+            let coefficients = done.iter().map(|q| dot(q, column)).collect::<Vec<_>>();
+            for (i, (q, s)) in done.iter().zip(coefficients).enumerate() {
+                axpy(-s, q, column);
+                r[i * k + j] = r[i * k + j] + s;
+            }
+        }
+        let length = norm(column);
+        if length <= tolerance * original || !length.is_finite() {
+            return None;
+        }
+        column.iter_mut().for_each(|v| *v = *v / length);
+        r[j * k + j] = Complex::new(length, T::zero());
+    }
+    Some(r)
+}
+
+/// Computes `XR⁻¹` for the columns of `X` and the row-major upper triangular `R`.
+pub(crate) fn times_inverse_upper<T: Float>(
+    mut columns: Vec<Vec<Complex<T>>>,
+    r: &[Complex<T>],
+) -> Vec<Vec<Complex<T>>> {
+    let k = columns.len();
+    for j in 0..k {
+        let (done, rest) = columns.split_at_mut(j);
+        for (i, q) in done.iter().enumerate() {
+            axpy(-r[i * k + j], q, &mut rest[0]);
+        }
+        rest[0].iter_mut().for_each(|v| *v = *v / r[j * k + j]);
+    }
+    columns
+}
+
+/// Solves the least squares problem `min ‖b - Gy‖` for the row-major ```rows × cols```
+/// matrix ```g``` of full column rank.
+pub(crate) fn least_squares<T: Float>(
+    rows: usize,
+    cols: usize,
+    g: &[Complex<T>],
+    b: &[Complex<T>],
+) -> Option<Vec<Complex<T>>> {
+    let mut q = (0..cols)
+        .map(|j| (0..rows).map(|i| g[i * cols + j]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let r = orthonormalize(&mut q, T::epsilon())?;
+    let mut y = q.iter().map(|qj| dot(qj, b)).collect::<Vec<_>>();
+    for i in (0..cols).rev() {
+        let sum = (i + 1..cols).fold(y[i], |acc, j| acc - r[i * cols + j] * y[j]);
+        y[i] = sum / r[i * cols + i];
+    }
+    Some(y)
+}
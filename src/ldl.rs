@@ -0,0 +1,312 @@
+use crate::compressed::compress;
+use crate::index::AsIndex;
+use crate::{Permutation, SolveError, SymmetricMatrix, Symmetry};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A sparse `LDLᴴ` factorization `PAPᵀ = LDLᴴ` of a Hermitian matrix, with `L` unit
+/// lower triangular and `D` real diagonal.
+///
+/// No pivoting is done, so the matrix must be factorizable in the given ordering,
+/// which is always the case for positive definite matrices. The factorization can
+/// be modified in place when the matrix changes by a rank-1 term, see ```update```.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HermitianLdl<T: Float> {
+    ordering: Permutation,
+    /// The parent of each column in the elimination tree, `usize::MAX` for a root.
+    parent: Vec<usize>,
+    /// The strictly lower part of `L`, by column, with sorted row indices.
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<Complex<T>>,
+    diagonal: Vec<T>,
+}
+
+impl<T: Float> SymmetricMatrix<T> {
+    /// Compute the `LDLᴴ` factorization of a Hermitian matrix in its natural ordering.
+    ///
+    /// Returns `Err` if the matrix is not Hermitian or a pivot is zero.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Lower);
+    /// m.add_element(0, 0, Complex::new(4., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 2.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let ldl = m.factorize_ldl().unwrap();
+    /// let mut b = vec![Complex::new(6., 0.), Complex::new(0., 4.)];
+    /// ldl.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    /// assert!((b[1] - Complex::new(0., 1.)).norm() < 1e-12);
+    ///```
+    pub fn factorize_ldl(&self) -> Result<HermitianLdl<T>, SolveError> {
+        let n = self.to_complex_matrix().dimension();
+        self.factorize_ldl_with_ordering(Permutation::identity(n))
+    }
+
+    /// Compute the `LDLᴴ` factorization of the symmetrically permuted matrix `PAPᵀ`,
+    /// where row ```i``` of `A` becomes row ```ordering.get(i)```, e.g. to reduce fill-in.
+    ///
+    /// Returns `Err` if the matrix is not Hermitian, the ordering does not match its
+    /// size or a pivot is zero.
+    pub fn factorize_ldl_with_ordering(
+        &self,
+        ordering: Permutation,
+    ) -> Result<HermitianLdl<T>, SolveError> {
+        if self.symmetry() != Symmetry::Hermitian {
+            return Err(SolveError::InvalidInput("the matrix is not Hermitian"));
+        }
+        let full = self.to_complex_matrix();
+        let n = ordering.len();
+        if full.dimension() > n {
+            return Err(SolveError::DimensionMismatch);
+        }
+
+        // Column k of the upper triangle of PAPᵀ, as row k of a compressed transpose.
+        let (mut rows, mut cols, mut entries) = (Vec::new(), Vec::new(), Vec::new());
+        for ((r, c), v) in full
+            .rows
+            .iter()
+            .zip(full.cols.iter())
+            .zip(full.entries.iter())
+        {
+            let (i, j) = (ordering.forward[r.index()], ordering.forward[c.index()]);
+            if i <= j {
+                rows.push(i);
+                cols.push(j);
+                entries.push(*v);
+            }
+        }
+        let upper = compress(n, &cols, &rows, &entries);
+
+        // Elimination tree and column counts of L.
+        let mut parent = vec![usize::MAX; n];
+        let mut counts = vec![0; n];
+        let mut flag = vec![usize::MAX; n];
+        for k in 0..n {
+            flag[k] = k;
+            for (mut i, _) in upper.row(k) {
+                while flag[i] != k {
+                    if parent[i] == usize::MAX {
+                        parent[i] = k;
+                    }
+                    counts[i] += 1;
+                    flag[i] = k;
+                    i = parent[i];
+                }
+            }
+        }
+        let mut indptr = vec![0; n + 1];
+        for k in 0..n {
+            indptr[k + 1] = indptr[k] + counts[k];
+        }
+
+        // Up-looking factorization: row k of L solves L₀₀D₀₀ l̄ = A(0..k, k).
+        let zero = Complex::new(T::zero(), T::zero());
+        let nnz = indptr[n];
+        let mut indices = vec![0; nnz];
+        let mut values = vec![zero; nnz];
+        let mut diagonal = vec![T::zero(); n];
+        let mut filled = vec![0; n];
+        let mut y = vec![zero; n];
+        let mut pattern = vec![0; n];
+        for k in 0..n {
+            let mut top = n;
+            flag[k] = k;
+            for (i, v) in upper.row(k) {
+                y[i] = y[i] + *v;
+                let mut len = 0;
+                let mut i = i;
+                while flag[i] != k {
+                    pattern[len] = i;
+                    len += 1;
+                    flag[i] = k;
+                    i = parent[i];
+                }
+                while len > 0 {
+                    top -= 1;
+                    len -= 1;
+                    pattern[top] = pattern[len];
+                }
+            }
+            let mut d = y[k].re;
+            y[k] = zero;
+            for &i in &pattern[top..n] {
+                let yi = y[i];
+                y[i] = zero;
+                let end = indptr[i] + filled[i];
+                for p in indptr[i]..end {
+                    y[indices[p]] = y[indices[p]] - values[p] * yi;
+                }
+                let lki = yi.conj() / diagonal[i];
+                d = d - (lki * yi).re;
+                indices[end] = k;
+                values[end] = lki;
+                filled[i] += 1;
+            }
+            if d == T::zero() || !d.is_finite() {
+                return Err(SolveError::Singular);
+            }
+            diagonal[k] = d;
+        }
+
+        Ok(HermitianLdl {
+            ordering,
+            parent,
+            indptr,
+            indices,
+            values,
+            diagonal,
+        })
+    }
+}
+
+impl<T: Float> HermitianLdl<T> {
+    /// The order of the factorized matrix.
+    pub fn size(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    /// The number of stored entries of `L`, excluding its unit diagonal.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The ordering `P` of the factorization.
+    pub fn ordering(&self) -> &Permutation {
+        &self.ordering
+    }
+
+    /// The diagonal of `D`, in the order of the factorization.
+    pub fn diagonal(&self) -> &[T] {
+        &self.diagonal
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let n = self.size();
+        if b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let mut x = self.ordering.apply(b);
+        for j in 0..n {
+            let xj = x[j];
+            for p in self.indptr[j]..self.indptr[j + 1] {
+                x[self.indices[p]] = x[self.indices[p]] - self.values[p] * xj;
+            }
+        }
+        for (xj, d) in x.iter_mut().zip(self.diagonal.iter()) {
+            *xj = *xj / *d;
+        }
+        for j in (0..n).rev() {
+            let sum = (self.indptr[j]..self.indptr[j + 1]).fold(x[j], |acc, p| {
+                acc - self.values[p].conj() * x[self.indices[p]]
+            });
+            x[j] = sum;
+        }
+        b.copy_from_slice(&self.ordering.apply_inverse(&x));
+        Ok(())
+    }
+
+    /// Update the factorization of `A` into the factorization of `A + vvᴴ`, in
+    /// `O(nnz(L))` at most instead of a full refactorization, e.g. after adding a shunt
+    /// admittance or a measurement.
+    ///
+    /// The pattern of `L` is kept, so the nonzeros of ```v``` must lie on a single path
+    /// of the elimination tree, which holds when they already are a clique of the
+    /// pattern of `A`, e.g. a single entry, or the terminals of an existing branch.
+    /// Returns `Err`, leaving the factorization unchanged, if they do not, if ```v```
+    /// does not match the matrix size or if the updated matrix is singular.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Lower);
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(-1., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    /// let mut ldl = m.factorize_ldl().unwrap();
+    ///
+    /// // A unit shunt at node 1.
+    /// ldl.update(&[Complex::new(0., 0.), Complex::new(1., 0.)]).unwrap();
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(2., 0.)];
+    /// ldl.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    /// assert!((b[1] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn update(&mut self, v: &[Complex<T>]) -> Result<(), SolveError> {
+        self.modify(T::one(), v)
+    }
+
+    /// Downdate the factorization of `A` into the factorization of `A - vvᴴ`, e.g.
+    /// after removing a shunt admittance or a measurement. See ```update```.
+    pub fn downdate(&mut self, v: &[Complex<T>]) -> Result<(), SolveError> {
+        self.modify(-T::one(), v)
+    }
+
+    /// Modifies the factorization into that of `A + σvvᴴ` along the elimination tree
+    /// path of the first nonzero of `Pv`.
+    fn modify(&mut self, sigma: T, v: &[Complex<T>]) -> Result<(), SolveError> {
+        let n = self.size();
+        if v.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut w = self.ordering.apply(v);
+        let mut path = Vec::new();
+        let mut j = w.iter().position(|wi| *wi != zero).unwrap_or(n);
+        while j < n {
+            path.push(j);
+            j = self.parent[j];
+        }
+        let mut on_path = vec![false; n];
+        path.iter().for_each(|j| on_path[*j] = true);
+        if w.iter()
+            .zip(on_path.iter())
+            .any(|(wi, on)| *wi != zero && !on)
+        {
+            return Err(SolveError::InvalidInput(
+                "the update would add fill-in to the factorization",
+            ));
+        }
+
+        let saved = path
+            .iter()
+            .map(|j| {
+                (
+                    self.diagonal[*j],
+                    self.values[self.indptr[*j]..self.indptr[*j + 1]].to_vec(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut alpha = T::one();
+        for &j in &path {
+            let (dj, wj) = (self.diagonal[j], w[j]);
+            let swj2 = sigma * wj.norm_sqr();
+            let gamma = dj * alpha + swj2;
+            self.diagonal[j] = dj + swj2 / alpha;
+            alpha = alpha + swj2 / dj;
+            for p in self.indptr[j]..self.indptr[j + 1] {
+                let i = self.indices[p];
+                w[i] = w[i] - wj * self.values[p];
+                if gamma != T::zero() {
+                    self.values[p] = self.values[p] + wj.conj() * (sigma / gamma) * w[i];
+                }
+            }
+            if self.diagonal[j] == T::zero() || !self.diagonal[j].is_finite() {
+                for (&j, (d, column)) in path.iter().zip(saved) {
+                    self.diagonal[j] = d;
+                    self.values[self.indptr[j]..self.indptr[j + 1]].copy_from_slice(&column);
+                }
+                return Err(SolveError::Singular);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use crate::compressed::compress;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::index::AsIndex;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::solver;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A complex least-squares problem `min ‖Ax - b‖` whose QR factorization is updated
+/// as new rows (observations) are appended, e.g. for online phasor or impedance
+/// estimation.
+///
+/// Only the triangular factor `R` of the ```n``` unknowns and `Qᴴb` are stored, so
+/// appending a row costs `O(n²)` Givens rotations whatever the number of rows already
+/// seen, and the solution can be read after every observation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdatingLeastSquares<T: Float> {
+    n: usize,
+    r: Vec<Complex<T>>,
+    qtb: Vec<Complex<T>>,
+    residual_sqr: T,
+    observations: usize,
+}
+
+impl<T: Float> UpdatingLeastSquares<T> {
+    /// Create an empty problem with ```n``` unknowns.
+    ///```rust
+    /// use sparse_complex::UpdatingLeastSquares;
+    /// let ls = UpdatingLeastSquares::<f64>::new(3);
+    /// assert_eq!(ls.observations(), 0);
+    ///```
+    pub fn new(n: usize) -> Self {
+        let zero = Complex::new(T::zero(), T::zero());
+        UpdatingLeastSquares {
+            n,
+            r: vec![zero; n * n],
+            qtb: vec![zero; n],
+            residual_sqr: T::zero(),
+            observations: 0,
+        }
+    }
+
+    /// Factorize the problem of ```n``` unknowns with the rows of ```matrix``` and the
+    /// right-hand side ```b```, one observation per element of ```b```.
+    ///
+    /// Panics if ```matrix``` has a row beyond ```b``` or a column beyond ```n```.
+    pub fn from_matrix(matrix: &ComplexMatrix<T>, n: usize, b: &[Complex<T>]) -> Self {
+        let mut ls = Self::new(n);
+        ls.append_rows(matrix, b);
+        ls
+    }
+
+    /// Append the observation `aᵀx = rhs`, with `a` given by its ```(col, value)```
+    /// pairs, and update the factorization. Duplicated columns are summed.
+    ///
+    /// Panics if a column is out of ```0..n```.
+    ///```rust
+    /// use sparse_complex::UpdatingLeastSquares;
+    /// use num::Complex;
+    ///
+    /// // Estimate a phasor from noisy measurements, refining it as they arrive.
+    /// let mut ls = UpdatingLeastSquares::<f64>::new(1);
+    /// for measurement in [Complex::new(1.1, 0.5), Complex::new(0.9, 0.5)] {
+    ///     ls.append_row(&[(0, Complex::new(1., 0.))], measurement);
+    /// }
+    /// assert_eq!(ls.solution().unwrap(), vec![Complex::new(1., 0.5)]);
+    /// assert!((ls.residual_norm() - 0.02f64.sqrt()).abs() < 1e-12);
+    ///```
+    pub fn append_row(&mut self, row: &[(usize, Complex<T>)], rhs: Complex<T>) {
+        let n = self.n;
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut w = vec![zero; n];
+        for (col, value) in row {
+            assert!(*col < n, "the column {} is out of bounds", col);
+            w[*col] = w[*col] + *value;
+        }
+        let mut beta = rhs;
+
+        // Rotate the new row into R, zeroing it from left to right.
+        let first = w.iter().position(|v| *v != zero).unwrap_or(n);
+        for k in first..n {
+            let (a, b) = (self.r[k * n + k], w[k]);
+            if b == zero {
+                continue;
+            }
+            let norm = (a.norm_sqr() + b.norm_sqr()).sqrt();
+            let (c, s) = (a / norm, b / norm);
+            for (rj, wj) in self.r[k * n + k..(k + 1) * n]
+                .iter_mut()
+                .zip(w[k..].iter_mut())
+            {
+                let (p, q) = (*rj, *wj);
+                *rj = c.conj() * p + s.conj() * q;
+                *wj = c * q - s * p;
+            }
+            let (p, q) = (self.qtb[k], beta);
+            self.qtb[k] = c.conj() * p + s.conj() * q;
+            beta = c * q - s * p;
+        }
+        self.residual_sqr = self.residual_sqr + beta.norm_sqr();
+        self.observations += 1;
+    }
+
+    /// Append the rows of ```matrix``` with the right-hand side ```b```, see ```append_row```.
+    ///
+    /// Panics if ```matrix``` has a row beyond ```b``` or a column beyond ```n```.
+    pub fn append_rows(&mut self, matrix: &ComplexMatrix<T>, b: &[Complex<T>]) {
+        let size = matrix.dimension().max(b.len());
+        let csr = compress(size, &matrix.rows, &matrix.cols, &matrix.entries);
+        assert!(
+            (b.len()..size).all(|i| csr.indptr[i] == csr.indptr[i + 1]),
+            "the matrix has more rows than the right-hand side"
+        );
+        for (i, rhs) in b.iter().enumerate() {
+            let row = csr.row(i).map(|(j, v)| (j, *v)).collect::<Vec<_>>();
+            self.append_row(&row, *rhs);
+        }
+    }
+
+    /// The least-squares solution of the observations so far.
+    ///
+    /// Returns `Err` if the observations do not determine all the unknowns yet.
+    pub fn solution(&self) -> Result<Vec<Complex<T>>, SolveError> {
+        let n = self.n;
+        let max = (0..n).fold(T::zero(), |max, k| max.max(self.r[k * n + k].norm()));
+        let threshold = max * T::epsilon() * T::from(n).unwrap();
+        let mut x = self.qtb.clone();
+        for i in (0..n).rev() {
+            let pivot = self.r[i * n + i];
+            if pivot.norm() <= threshold || pivot.norm() == T::zero() {
+                return Err(SolveError::Singular);
+            }
+            let sum = (i + 1..n).fold(x[i], |acc, j| acc - self.r[i * n + j] * x[j]);
+            x[i] = sum / pivot;
+        }
+        Ok(x)
+    }
+
+    /// The norm of the residual `Ax - b` at the least-squares solution.
+    pub fn residual_norm(&self) -> T {
+        self.residual_sqr.sqrt()
+    }
+
+    /// The number of rows appended so far.
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// The number of unknowns.
+    pub fn unknowns(&self) -> usize {
+        self.n
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl ComplexMatrix<f64> {
+    /// Solve the overdetermined system `Ax≈b` in the least-squares sense, minimizing
+    /// `‖Ax - b‖` with a sparse QR factorization, e.g. for state estimation from
+    /// redundant measurements.
+    ///
+    /// The matrix has one row per element of ```b``` and as many columns as its
+    /// largest column index plus one, which is the length of the solution.
+    ///
+    /// Returns `Err` if the matrix has a row beyond ```b```, more columns than rows, or
+    /// is rank deficient.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// // Three measurements of two unknowns.
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// m.add_element(2, 0, Complex::new(1., 0.));
+    ///
+    /// let b = vec![Complex::new(1., 0.), Complex::new(0., 2.), Complex::new(2., 0.)];
+    /// let x = m.solve_least_squares(&b).unwrap();
+    /// assert!((x[0] - Complex::new(1.5, 0.)).norm() < 1e-5);
+    /// assert!((x[1] - Complex::new(0., 2.)).norm() < 1e-5);
+    ///```
+    pub fn solve_least_squares(&self, b: &[Complex<f64>]) -> Result<Vec<Complex<f64>>, SolveError> {
+        let nrows = b.len();
+        let ncols = self.cols.iter().max().map_or(0, |c| c.index() + 1);
+        if self.rows.iter().any(|r| r.index() >= nrows) {
+            return Err(SolveError::DimensionMismatch);
+        }
+        if ncols > nrows {
+            return Err(SolveError::InvalidInput(
+                "the system has more unknowns than equations",
+            ));
+        }
+        let mut x = vec![Complex::new(0., 0.); ncols];
+        let info = unsafe {
+            solver::least_squares_cpp(
+                self.entries.as_ptr(),
+                self.rows.as_ptr(),
+                self.cols.as_ptr(),
+                self.entries.len(),
+                b.as_ptr(),
+                nrows,
+                ncols,
+                x.as_mut_ptr(),
+            )
+        };
+        solver::info_to_result(info)?;
+        Ok(x)
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl ComplexMatrix<f32> {
+    /// Solve the overdetermined system `Ax≈b` in the least-squares sense, minimizing
+    /// `‖Ax - b‖` with a sparse QR factorization, e.g. for state estimation from
+    /// redundant measurements.
+    ///
+    /// The matrix has one row per element of ```b``` and as many columns as its
+    /// largest column index plus one, which is the length of the solution.
+    ///
+    /// Returns `Err` if the matrix has a row beyond ```b```, more columns than rows, or
+    /// is rank deficient.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// // Three measurements of two unknowns.
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// m.add_element(2, 0, Complex::new(1., 0.));
+    ///
+    /// let b = vec![Complex::new(1., 0.), Complex::new(0., 2.), Complex::new(2., 0.)];
+    /// let x = m.solve_least_squares(&b).unwrap();
+    /// assert!((x[0] - Complex::new(1.5, 0.)).norm() < 1e-5);
+    /// assert!((x[1] - Complex::new(0., 2.)).norm() < 1e-5);
+    ///```
+    pub fn solve_least_squares(&self, b: &[Complex<f32>]) -> Result<Vec<Complex<f32>>, SolveError> {
+        let nrows = b.len();
+        let ncols = self.cols.iter().max().map_or(0, |c| c.index() + 1);
+        if self.rows.iter().any(|r| r.index() >= nrows) {
+            return Err(SolveError::DimensionMismatch);
+        }
+        if ncols > nrows {
+            return Err(SolveError::InvalidInput(
+                "the system has more unknowns than equations",
+            ));
+        }
+        let mut x = vec![Complex::new(0., 0.); ncols];
+        let info = unsafe {
+            solver::least_squares_cpp32(
+                self.entries.as_ptr(),
+                self.rows.as_ptr(),
+                self.cols.as_ptr(),
+                self.entries.len(),
+                b.as_ptr(),
+                nrows,
+                ncols,
+                x.as_mut_ptr(),
+            )
+        };
+        solver::info_to_result(info)?;
+        Ok(x)
+    }
+}
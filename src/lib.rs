@@ -51,18 +51,197 @@ The solution of this system is:
 ## Version Compatible
 The ```sparse_complex``` crate is tested for rustc 1.61 and greater.
 
+## `no_std`
+
+Without the default `std` feature the crate is `no_std + alloc`: matrices can be
+assembled, converted and combined, but solving needs `std`.
+
+## Without Eigen
+
+Without the default `eigen` feature nothing is compiled from C++ nor downloaded at
+build time: ```solve``` uses the pure-Rust ```SparseLu``` and the APIs tied to the
+Eigen solver are left out.
+
+## WebAssembly
+
+The crate builds for `wasm32-unknown-unknown` without the `eigen` feature:
+```text
+cargo build --target wasm32-unknown-unknown --no-default-features --features std,f64,f32
+```
+The C++ part needs a C++ toolchain for the target, e.g. `wasm32-unknown-emscripten`.
+
 */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(all(feature = "eigen", target_arch = "wasm32", not(target_os = "emscripten")))]
+compile_error!(
+    "the `eigen` feature cannot be built for this wasm target, disable the default features \
+     and enable `std,f64,f32` to use the pure-Rust solver"
+);
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 use num::complex::Complex;
 use num_traits::float::Float;
-use std::fmt;
+use index::{to_storage, AsIndex, StorageIndex};
+mod analysis;
+#[cfg(all(feature = "async", any(feature = "f64", feature = "f32")))]
+mod asynchronous;
+mod backend;
+mod banded;
+#[cfg(feature = "std")]
+mod batch;
+mod block;
+mod block_tridiagonal;
+#[cfg(feature = "std")]
+mod builder;
+mod cancel;
+mod checkpoint;
+mod compressed;
+#[cfg(feature = "cuda")]
+mod cuda;
+mod dense;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod equilibrate;
+mod error;
+#[cfg(feature = "exact")]
+mod exact;
+mod explain;
+mod fitting;
+mod graph;
+#[cfg(feature = "f16")]
+mod half_precision;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod hermitian;
+mod index;
+#[cfg(feature = "std")]
+mod info;
+mod iterative;
+#[cfg(feature = "klu")]
+mod klu;
+mod krylov;
+mod ldl;
+mod least_squares;
+mod lobpcg;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod lu;
+mod network;
+mod ops;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod permutation;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod pool;
+mod power;
+mod progress;
+mod preconditioner;
+mod real;
+mod recycling;
+mod residual;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod same_pattern;
+mod simd;
+mod sparse_lu;
+#[cfg(feature = "eigen")]
 mod solver;
+#[cfg(any(feature = "f64", feature = "f32"))]
+mod solvable;
+mod stencil;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod strided;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod stream;
+mod symmetric;
+mod symbolic;
+#[cfg(feature = "std")]
+mod threads;
+mod triplet;
+#[cfg(feature = "umfpack")]
+mod umfpack;
+mod validate;
+mod verified;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+mod workspace;
+mod ybus;
+
+pub use analysis::SpectrumBounds;
+#[cfg(all(feature = "async", any(feature = "f64", feature = "f32")))]
+pub use asynchronous::SolveFuture;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub use backend::EigenBackend;
+pub use backend::{Factorization, RustBackend, SolverBackend};
+pub use banded::{banded_solve_bandwidth, set_banded_solve_bandwidth, BandedMatrix};
+#[cfg(all(feature = "rayon", any(feature = "f64", feature = "f32")))]
+pub use batch::solve_batch_par;
+#[cfg(feature = "std")]
+pub use batch::{solve_batch_small, SmallSystem};
+pub use block::BlockMatrix;
+pub use block_tridiagonal::BlockTridiagonal;
+#[cfg(feature = "std")]
+pub use builder::{ConcurrentMatrixBuilder, MatrixShard};
+#[cfg(feature = "std")]
+pub use cancel::{with_cancellation, CancellationToken};
+pub use compressed::CompressedMatrix;
+#[cfg(feature = "cuda")]
+pub use cuda::{CudaBackend, CudaQr};
+pub use dense::{dense_solve_threshold, set_dense_solve_threshold};
+pub use error::SolveError;
+pub use explain::{Explanation, PivotStep};
+pub use fitting::{vector_fit, RationalModel};
+#[cfg(feature = "f16")]
+pub use half_precision::HalfMatrix;
+pub use index::{Col, Row};
+#[cfg(feature = "std")]
+pub use info::{solver_info, SolverInfo};
+pub use iterative::{ConvergenceReport, IterOptions};
+#[cfg(feature = "klu")]
+pub use klu::{KluBackend, KluLu};
+pub use ldl::HermitianLdl;
+pub use least_squares::UpdatingLeastSquares;
+pub use lobpcg::{HermitianEigenpairs, Lobpcg};
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub use lu::{ColumnOrdering, ComplexLu, FactorizationStats, LuFactors, LuOptions};
+pub use network::PortMatrix;
+pub use ops::{PatternSlots, ZeroPolicy};
+pub use permutation::Permutation;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub use pool::{PooledWorkspace, SolverPool};
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub use preconditioner::IncompleteLut;
+pub use preconditioner::{IdentityPreconditioner, Ilu0, Jacobi, Preconditioner, Spai};
+#[cfg(feature = "std")]
+pub use progress::with_progress;
+pub use progress::Phase;
+pub use real::RealMatrix;
+pub use recycling::RecyclingSolver;
+pub use residual::ResidualNorm;
+pub use sparse_lu::SparseLu;
+#[cfg(any(feature = "f64", feature = "f32"))]
+pub use solvable::SolvableFloat;
+pub use stencil::{Boundary, Grid};
+pub use symbolic::{FactorizationEstimate, SymbolicAnalysis};
+pub use symmetric::{Symmetry, SymmetricMatrix, Triangle};
+#[cfg(feature = "std")]
+pub use threads::{num_threads, set_num_threads, with_num_threads};
+pub use triplet::Triplet;
+#[cfg(feature = "umfpack")]
+pub use umfpack::{UmfpackBackend, UmfpackLu};
+pub use verified::Enclosure;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub use workspace::SolveWorkspace;
+pub use ybus::{Branch, Shunt};
 
 /// The complex matrix struct
 #[derive(Clone, PartialEq)]
 pub struct ComplexMatrix<T: Float> {
     entries: Vec<Complex<T>>,
-    rows: Vec<usize>,
-    cols: Vec<usize>,
+    rows: Vec<StorageIndex>,
+    cols: Vec<StorageIndex>,
 }
 
 impl<T: Float> ComplexMatrix<T> {
@@ -92,23 +271,127 @@ impl<T: Float> ComplexMatrix<T> {
         }
     }
 
-    /// Create a new ```ComplexMatrix``` from a vector of ```(row, col, Complex<T>)``` entries.
+    /// Create a new ```ComplexMatrix``` from a vector of ```Triplet```s or ```(row, col, Complex<T>)``` entries.
     ///```rust
-    /// use sparse_complex::ComplexMatrix;
+    /// use sparse_complex::{ComplexMatrix, Triplet};
     /// use num::Complex;
     /// let entries = vec![(0, 0, Complex::new(1., 1.)), (1, 1, Complex::new(1., 1.))];
     /// let mut m = ComplexMatrix::<f64>::from_entries(entries);
+    ///
+    /// let triplets = vec![Triplet::new(0, 0, Complex::new(1., 1.)), Triplet::new(1, 1, Complex::new(1., 1.))];
+    /// assert_eq!(ComplexMatrix::from_entries(triplets), m);
     ///```
-    pub fn from_entries(entries: Vec<(usize, usize, Complex<T>)>) -> Self {
+    pub fn from_entries<E: Into<Triplet<T>>>(entries: Vec<E>) -> Self {
         let mut m = ComplexMatrix::with_capacity(entries.len());
+        m.extend(entries);
+        m
+    }
 
-        for (row, col, value) in entries {
+    /// Create a new ```ComplexMatrix``` from entries sorted in column-major order,
+    /// i.e. by ```(col, row)```, without duplicated positions.
+    ///
+    /// The pure-Rust solvers, e.g. ```factorize_sparse```, compress entries in this
+    /// order with a single copy, without the sorting and duplicate summation of the
+    /// general assembly, so re-emitting a known pattern in this order on every time
+    /// step keeps the assembly linear. The Eigen solvers sort the entries regardless.
+    ///
+    /// Returns `Err` if the entries are not sorted or contain duplicates.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let entries = vec![
+    ///     (0, 0, Complex::new(2., 0.)),
+    ///     (1, 0, Complex::new(1., 0.)),
+    ///     (1, 1, Complex::new(0., 1.)),
+    /// ];
+    /// let m = ComplexMatrix::<f64>::from_sorted_entries(entries).unwrap();
+    /// assert_eq!(m.get(1, 0), Some(&Complex::new(1., 0.)));
+    ///
+    /// let unsorted = vec![(1, 1, Complex::new(1., 0.)), (0, 0, Complex::new(1., 0.))];
+    /// assert!(ComplexMatrix::<f64>::from_sorted_entries(unsorted).is_err());
+    ///```
+    pub fn from_sorted_entries<E: Into<Triplet<T>>>(entries: Vec<E>) -> Result<Self, SolveError> {
+        let mut m = ComplexMatrix::with_capacity(entries.len());
+        let mut previous = None;
+        for entry in entries {
+            let Triplet { row, col, value } = entry.into();
+            if matches!(previous, Some(previous) if previous >= (col, row)) {
+                return Err(SolveError::InvalidInput(
+                    "the entries are not sorted by (col, row) or contain duplicates",
+                ));
+            }
+            previous = Some((col, row));
             m.add_element(row, col, value);
         }
+        Ok(m)
+    }
+
+    /// Create a new ```ComplexMatrix``` with the element ```f(row, col)``` at every
+    /// position of an ```nrows × ncols``` grid where it returns `Some`.
+    ///
+    /// ```f``` is called for every position, so for large sparse matrices prefer
+    /// ```from_row_fn```, which only visits the nonzeros.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// // Tridiagonal matrix.
+    /// let m = ComplexMatrix::<f64>::from_fn(4, 4, |i, j| match i.abs_diff(j) {
+    ///     0 => Some(Complex::new(2., 0.)),
+    ///     1 => Some(Complex::new(-1., 0.)),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(m.get(2, 1), Some(&Complex::new(-1., 0.)));
+    /// assert_eq!(m.get(0, 3), None);
+    ///```
+    pub fn from_fn<F>(nrows: usize, ncols: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> Option<Complex<T>>,
+    {
+        Self::from_row_fn(nrows, |row| {
+            (0..ncols)
+                .filter_map(|col| f(row, col).map(|value| (col, value)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Create a new ```ComplexMatrix``` from the ```(col, value)``` elements that
+    /// ```f(row)``` returns for each of the ```nrows``` rows.
+    ///
+    /// Only the returned elements are visited, so the construction is proportional
+    /// to the number of nonzeros.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let n = 1000;
+    /// let m = ComplexMatrix::<f64>::from_row_fn(n, |i| {
+    ///     let mut row = vec![(i, Complex::new(2., 0.))];
+    ///     if i + 1 < n {
+    ///         row.push((i + 1, Complex::new(-1., 0.)));
+    ///     }
+    ///     row
+    /// });
+    /// assert_eq!(m.get(10, 11), Some(&Complex::new(-1., 0.)));
+    ///```
+    pub fn from_row_fn<F, I>(nrows: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize) -> I,
+        I: IntoIterator<Item = (usize, Complex<T>)>,
+    {
+        let mut m = ComplexMatrix::new();
+        for row in 0..nrows {
+            for (col, value) in f(row) {
+                m.add_element(row, col, value);
+            }
+        }
         m
     }
 
     /// Add or set an element at location ```(row, col)``` with value.
+    ///
+    /// With the `index32` feature, panics if an index does not fit in 32 bits.
     ///```rust
     /// use sparse_complex::ComplexMatrix;
     /// use num::Complex;
@@ -125,8 +408,46 @@ impl<T: Float> ComplexMatrix<T> {
     ///```
     pub fn add_element(&mut self, row: usize, col: usize, value: Complex<T>) {
         self.entries.push(value);
-        self.rows.push(row);
-        self.cols.push(col);
+        self.rows.push(to_storage(row));
+        self.cols.push(to_storage(col));
+    }
+
+    /// Add a slice of ```Triplet```s or ```(row, col, value)``` elements, reserving space once.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_elements(&[(0, 0, Complex::new(1., 0.)), (1, 1, Complex::new(0., 1.))]);
+    /// assert_eq!(m.get(1, 1), Some(&Complex::new(0., 1.)));
+    ///```
+    pub fn add_elements<E: Into<Triplet<T>> + Copy>(&mut self, elements: &[E]) {
+        self.entries.reserve(elements.len());
+        self.rows.reserve(elements.len());
+        self.cols.reserve(elements.len());
+        self.extend(elements.iter().copied());
+    }
+
+    /// Add elements given as separate ```rows```, ```cols``` and ```values``` slices, which
+    /// are copied as they are into the matrix storage.
+    ///
+    /// Panics if the slices have different lengths.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_elements_from_parts(&[0, 1], &[1, 0], &[Complex::new(2., 0.), Complex::new(3., 0.)]);
+    /// assert_eq!(m.get(0, 1), Some(&Complex::new(2., 0.)));
+    ///```
+    pub fn add_elements_from_parts(&mut self, rows: &[usize], cols: &[usize], values: &[Complex<T>]) {
+        assert!(
+            rows.len() == values.len() && cols.len() == values.len(),
+            "rows, cols and values must have the same length"
+        );
+        self.entries.extend_from_slice(values);
+        self.rows.extend(rows.iter().map(|r| to_storage(*r)));
+        self.cols.extend(cols.iter().map(|c| to_storage(*c)));
     }
 
     ///  Returns the Element-value at ```(row, col)``` if present, or None if not.
@@ -149,20 +470,83 @@ impl<T: Float> ComplexMatrix<T> {
             .iter()
             .zip(self.cols.iter())
             .zip(self.entries.iter())
-            .find(|&((r, c), _)| *r == row && *c == col)
+            .find(|&((r, c), _)| r.index() == row && c.index() == col)
             .map(|(_, v)| v)
     }
+
+    /// The fraction of nonzero positions, `nnz / n²`, counting duplicated entries once.
+    ///
+    /// Returns 0 for an empty matrix.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// assert_eq!(m.density(), 0.5);
+    ///```
+    pub fn density(&self) -> f64 {
+        let n = self.dimension();
+        if n == 0 {
+            return 0.;
+        }
+        self.to_csr().values.len() as f64 / (n as f64 * n as f64)
+    }
+
+    /// The heap memory held by the index and value arrays, including their spare capacity.
+    ///
+    /// Each stored index takes as many bytes as a `usize`, or 4 with the `index32` feature.
+    ///
+    /// The matrix caches no factorization; see ```SolveWorkspace::memory_usage_bytes```
+    /// for the memory held by the solver.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// let m = ComplexMatrix::<f64>::with_capacity(10);
+    /// assert!(m.memory_usage_bytes() >= 10 * (16 + 2 * 4));
+    ///```
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.entries.capacity() * core::mem::size_of::<Complex<T>>()
+            + (self.rows.capacity() + self.cols.capacity()) * core::mem::size_of::<StorageIndex>()
+    }
+
+    /// The order of the square matrix implied by the largest stored index.
+    pub(crate) fn dimension(&self) -> usize {
+        self.rows
+            .iter()
+            .chain(self.cols.iter())
+            .max()
+            .map_or(0, |max| max.index() + 1)
+    }
+
+    /// Checks that a right-hand side of length ```n``` matches the matrix, whose
+    /// dimension is its largest row or column index plus one.
+    pub(crate) fn check_rhs(&self, n: usize) -> Result<(), SolveError> {
+        if n != self.dimension() {
+            return Err(SolveError::DimensionMismatch);
+        }
+        Ok(())
+    }
 }
 
-impl ComplexMatrix<f64> {
+#[cfg(any(feature = "f64", feature = "f32"))]
+impl<T: SolvableFloat> ComplexMatrix<T> {
     /// Solve the system `Ax=b`, where:
     /// * `A` is a complex matrix
     /// * `b` is a complex vector
     ///
-    /// Returns a `Result`. `Ok(())` if the system was solved successfully, `Err(String)` if not. 
-    /// The result is stored in `b`.
+    /// Returns a `Result`. `Ok(())` if the system was solved successfully, `Err(SolveError)` if not.
+    /// The result is stored in `b`. A singular matrix returns `Err`, and so does a `b`
+    /// whose length is not the matrix dimension, i.e. its largest row or column index
+    /// plus one.
     ///
-    /// The solution use the [Eigen::SparseLU](https://eigen.tuxfamily.org/dox/classEigen_1_1SparseLU.html).
+    /// The solution uses the [Eigen::SparseLU](https://eigen.tuxfamily.org/dox/classEigen_1_1SparseLU.html),
+    /// or the pure-Rust ```SparseLu``` without the `eigen` feature.
+    /// Systems up to ```dense_solve_threshold()``` unknowns are solved with a dense LU instead.
+    /// Matrices with bandwidths up to ```banded_solve_bandwidth()``` are solved with a banded LU.
+    /// Under ```with_cancellation``` the Eigen solver checks the token before factorizing,
+    /// while ```SparseLu``` also checks it at each column.
     ///
     ///```rust
     /// use sparse_complex::ComplexMatrix;
@@ -181,66 +565,71 @@ impl ComplexMatrix<f64> {
     /// let expected = vec![Complex::new(0.5, 0.5), Complex::new(0.5, -0.5)];
     /// assert_eq!(b, expected);
     ///```
-    pub fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), &'static str> {
-        unsafe {
-            solver::solve_cpp(
-                self.entries.as_ptr(),
-                self.rows.as_ptr(),
-                self.cols.as_ptr(),
-                self.entries.len(),
-                b.as_mut_ptr(),
-                b.len(),
-            )
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        self.debug_validate(b.len());
+        cancel::check()?;
+        progress::report(Phase::Factorization, Some(0.));
+        if let Some(result) = self.try_solve_dense(b) {
+            if result.is_ok() {
+                progress::report(Phase::Solve, Some(1.));
+            }
+            return result;
+        }
+        if let Some(result) = self.try_solve_banded(b) {
+            if result.is_ok() {
+                progress::report(Phase::Solve, Some(1.));
+            }
+            return result;
         }
+        T::solve_sparse(self, b)?;
+        progress::report(Phase::Solve, Some(1.));
 
         Ok(())
     }
-}
 
-impl ComplexMatrix<f32> {
-    /// Solve the system `Ax=b`, where:
-    /// * `A` is a complex matrix
-    /// * `b` is a complex vector
+    /// Solve the system `Ax=b` and return the solution, leaving ```b``` untouched.
+    ///
+    /// Returns `Err` in the same cases as ```solve```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
     ///
-    /// Returns a `Result`. `Ok(())` if the system was solved successfully, `Err(String)` if not. 
-    /// The result is stored in `b`.
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
     ///
-    /// This solution use the [Eigen::SparseLU](https://eigen.tuxfamily.org/dox/classEigen_1_1SparseLU.html).
+    /// let b = vec![Complex::new(1., 0.), Complex::new(1., 0.)];
+    /// let x = m.solve_into(&b).unwrap();
+    /// assert_eq!(x, vec![Complex::new(0.5, 0.), Complex::new(0., -1.)]);
+    ///```
+    pub fn solve_into(&self, b: &[Complex<T>]) -> Result<Vec<Complex<T>>, SolveError> {
+        let mut x = b.to_vec();
+        self.solve(&mut x)?;
+        Ok(x)
+    }
+
+    /// Returns the pivot growth factor `max|U| / max|A|` of the LU factorization.
     ///
+    /// A large growth factor means the factorization lost accuracy, even if it did
+    /// not fail. Returns `Err` if the factorization fails.
     ///```rust
     /// use sparse_complex::ComplexMatrix;
     /// use num::Complex;
-    /// 
-    /// let Z1: Complex<f32> = Complex { re: 1., im: -1. };
-    /// let Z2: Complex<f32> = Complex { re: -1., im: 1. };
-    /// 
-    /// let mut m = ComplexMatrix::new();
-    /// m.add_element(0, 0, Z1);
-    /// m.add_element(1, 1, Z2);
-    /// 
-    /// let mut b = vec![Complex::new(1., 0.), Complex::new(0., 1.)];
-    /// m.solve(&mut b).unwrap();
-    /// 
-    /// let expected = vec![Complex::new(0.5, 0.5), Complex::new(0.5, -0.5)];
-    /// assert_eq!(b, expected);
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// assert_eq!(m.pivot_growth().unwrap(), 1.);
     ///```
-    pub fn solve(&self, b: &mut [Complex<f32>]) -> Result<(), &'static str> {
-        unsafe {
-            solver::solve_cpp32(
-                self.entries.as_ptr(),
-                self.rows.as_ptr(),
-                self.cols.as_ptr(),
-                self.entries.len(),
-                b.as_mut_ptr(),
-                b.len(),
-            )
-        }
-
-        Ok(())
+    pub fn pivot_growth(&self) -> Result<T, SolveError> {
+        self.debug_validate(self.dimension());
+        T::pivot_growth(self)
     }
 }
 
-impl<T: Float + std::fmt::Display> fmt::Debug for ComplexMatrix<T> {
+impl<T: Float + fmt::Display> fmt::Debug for ComplexMatrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut msg = String::from("ComplexMatrix { \n");
         let elements = self
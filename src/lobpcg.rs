@@ -0,0 +1,267 @@
+use crate::compressed::compress;
+use crate::dense::hermitian_eigen;
+use crate::krylov::{axpy, dot, norm};
+use crate::{ComplexMatrix, Preconditioner, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// Eigenvalues and eigenvectors of a Hermitian pencil computed by ```Lobpcg```.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HermitianEigenpairs<T: Float> {
+    values: Vec<T>,
+    vectors: Vec<Vec<Complex<T>>>,
+    iterations: usize,
+}
+
+impl<T: Float> HermitianEigenpairs<T> {
+    /// The eigenvalues, from the extreme one inwards.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The `B`-orthonormal eigenvectors, one per eigenvalue.
+    pub fn vectors(&self) -> &[Vec<Complex<T>>] {
+        &self.vectors
+    }
+
+    /// The number of iterations run.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+/// The locally optimal block preconditioned conjugate gradient (LOBPCG) eigensolver
+/// for the extreme eigenvalues of a Hermitian pencil `Ax = λBx` with `B` positive
+/// definite.
+///
+/// Each iteration only needs products with `A` and `B` and one application of the
+/// preconditioner, so very large problems can be handled where factorizing a
+/// shifted matrix is too expensive. A preconditioner approximating `A⁻¹`, or
+/// `(A - σB)⁻¹` for a shift `σ` below the wanted eigenvalues, speeds up the
+/// convergence to the smallest eigenvalues.
+#[derive(Clone, Debug)]
+pub struct Lobpcg<T: Float> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T: Float> Default for Lobpcg<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Lobpcg<T> {
+    /// Create a solver with a relative tolerance of `√ε` running at most 1000
+    /// iterations.
+    pub fn new() -> Self {
+        Lobpcg {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+        }
+    }
+
+    /// Sets the tolerance on the residual norm `‖Ax - λBx‖` relative to
+    /// `‖Ax‖ + |λ|‖Bx‖`.
+    pub fn set_tolerance(&mut self, tolerance: T) {
+        self.tolerance = tolerance;
+    }
+
+    /// Sets the maximum number of iterations.
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    /// Compute the ```count``` smallest eigenvalues of `Ax = λBx`, or of `Ax = λx` if
+    /// ```b``` is None.
+    ///
+    /// Both matrices must be Hermitian and `B` positive definite; only their stored
+    /// elements are used. Returns `Err` if ```count``` is zero or larger than the
+    /// matrix, or if the iteration did not converge.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IdentityPreconditioner, Lobpcg};
+    /// use num::Complex;
+    ///
+    /// // A Hermitian tridiagonal matrix with eigenvalues 2 - 2cos(kπ / 51).
+    /// let n = 50;
+    /// let mut a = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     a.add_element(i, i, Complex::new(2., 0.));
+    ///     if i + 1 < n {
+    ///         a.add_element(i, i + 1, Complex::new(0., -1.));
+    ///         a.add_element(i + 1, i, Complex::new(0., 1.));
+    ///     }
+    /// }
+    ///
+    /// let pairs = Lobpcg::new().smallest(&a, None, &IdentityPreconditioner, 2).unwrap();
+    /// let exact = 2. - 2. * (std::f64::consts::PI / 51.).cos();
+    /// assert!((pairs.values()[0] - exact).abs() < 1e-6);
+    ///```
+    pub fn smallest<P: Preconditioner<T>>(
+        &self,
+        a: &ComplexMatrix<T>,
+        b: Option<&ComplexMatrix<T>>,
+        preconditioner: &P,
+        count: usize,
+    ) -> Result<HermitianEigenpairs<T>, SolveError> {
+        self.solve(a, b, preconditioner, count, false)
+    }
+
+    /// Compute the ```count``` largest eigenvalues of `Ax = λBx`, or of `Ax = λx` if
+    /// ```b``` is None, see ```smallest```.
+    pub fn largest<P: Preconditioner<T>>(
+        &self,
+        a: &ComplexMatrix<T>,
+        b: Option<&ComplexMatrix<T>>,
+        preconditioner: &P,
+        count: usize,
+    ) -> Result<HermitianEigenpairs<T>, SolveError> {
+        self.solve(a, b, preconditioner, count, true)
+    }
+
+    fn solve<P: Preconditioner<T>>(
+        &self,
+        a: &ComplexMatrix<T>,
+        b: Option<&ComplexMatrix<T>>,
+        preconditioner: &P,
+        count: usize,
+        largest: bool,
+    ) -> Result<HermitianEigenpairs<T>, SolveError> {
+        let n = a.dimension().max(b.map_or(0, |b| b.dimension()));
+        if count == 0 || count > n {
+            return Err(SolveError::InvalidInput(
+                "the number of eigenvalues must be between 1 and the matrix size",
+            ));
+        }
+        let a = compress(n, &a.rows, &a.cols, &a.entries);
+        let b = b.map(|b| compress(n, &b.rows, &b.cols, &b.entries));
+        let apply_b = |v: &[Complex<T>]| match &b {
+            Some(b) => b.mul_vec(v),
+            None => v.to_vec(),
+        };
+        let zero = Complex::new(T::zero(), T::zero());
+
+        let mut x = start_vectors(n, count);
+        let mut w: Vec<Vec<Complex<T>>> = Vec::new();
+        let mut p: Vec<Vec<Complex<T>>> = Vec::new();
+        for iteration in 0..=self.max_iterations {
+            // Rayleigh-Ritz on the B-orthonormalized span of [X, W, P].
+            let kept_x = x.len();
+            let mut basis = x;
+            basis.append(&mut w);
+            basis.append(&mut p);
+            let (s, bs, kept) = b_orthonormalize(basis, &apply_b);
+            if s.len() < count {
+                return Err(SolveError::NumericalIssue);
+            }
+            let kept_x = kept.iter().filter(|i| **i < kept_x).count();
+            let a_s = s.iter().map(|si| a.mul_vec(si)).collect::<Vec<_>>();
+            let m = s.len();
+            let h = (0..m * m)
+                .map(|k| {
+                    let (i, j) = (k / m, k % m);
+                    (dot(&s[i], &a_s[j]) + dot(&s[j], &a_s[i]).conj()) / (T::one() + T::one())
+                })
+                .collect::<Vec<_>>();
+            let (values, vectors) = hermitian_eigen(m, h);
+            let wanted = (0..count)
+                .map(|j| if largest { m - 1 - j } else { j })
+                .collect::<Vec<_>>();
+
+            let combine = |columns: &[Vec<Complex<T>>], from: usize, j: usize| {
+                let mut y = vec![zero; n];
+                for (i, column) in columns.iter().enumerate().skip(from) {
+                    axpy(vectors[i * m + j], column, &mut y);
+                }
+                y
+            };
+            x = wanted.iter().map(|j| combine(&s, 0, *j)).collect();
+            // The search directions P are the components along W and the previous P.
+            p = wanted.iter().map(|j| combine(&s, kept_x, *j)).collect();
+
+            let mut converged = true;
+            for j in wanted.iter() {
+                let (ax, bx) = (combine(&a_s, 0, *j), combine(&bs, 0, *j));
+                let lambda = values[*j];
+                let mut r = ax.clone();
+                axpy(Complex::new(-lambda, T::zero()), &bx, &mut r);
+                if norm(&r) > self.tolerance * (norm(&ax) + lambda.abs() * norm(&bx)) {
+                    converged = false;
+                    let mut z = vec![zero; n];
+                    preconditioner.apply(&r, &mut z);
+                    w.push(z);
+                }
+            }
+            if converged {
+                return Ok(HermitianEigenpairs {
+                    values: wanted.iter().map(|j| values[*j]).collect(),
+                    vectors: x,
+                    iterations: iteration,
+                });
+            }
+        }
+        Err(SolveError::NoConvergence)
+    }
+}
+
+/// Deterministic pseudo-random start vectors.
+fn start_vectors<T: Float>(n: usize, count: usize) -> Vec<Vec<Complex<T>>> {
+    let mut state = 0x853c_49e6_748f_ea9b_u64;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        T::from((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5).unwrap()
+    };
+    (0..count)
+        .map(|_| (0..n).map(|_| Complex::new(next(), next())).collect())
+        .collect()
+}
+
+/// Orthonormalize ```columns``` in the `B` inner product with classical Gram-Schmidt
+/// applied twice, dropping the numerically dependent ones.
+///
+/// Returns the orthonormal columns, their products with `B` and the indices of the
+/// kept columns.
+#[allow(clippy::type_complexity)]
+fn b_orthonormalize<T, F>(
+    columns: Vec<Vec<Complex<T>>>,
+    apply_b: &F,
+) -> (Vec<Vec<Complex<T>>>, Vec<Vec<Complex<T>>>, Vec<usize>)
+where
+    T: Float,
+    F: Fn(&[Complex<T>]) -> Vec<Complex<T>>,
+{
+    let b_norm = |v: &[Complex<T>], bv: &[Complex<T>]| dot(v, bv).re.max(T::zero()).sqrt();
+    let mut q: Vec<Vec<Complex<T>>> = Vec::new();
+    let mut bq: Vec<Vec<Complex<T>>> = Vec::new();
+    let mut kept = Vec::new();
+    for (index, mut column) in columns.into_iter().enumerate() {
+        let original = b_norm(&column, &apply_b(&column));
+        if original == T::zero() || !original.is_finite() {
+            continue;
+        }
+        for _ in 0..2 {
+            let coefficients = bq
+                .iter()
+                .map(|bqi: &Vec<Complex<T>>| dot(bqi, &column))
+                .collect::<Vec<_>>();
+            for (qi, s) in q.iter().zip(coefficients) {
+                axpy(-s, qi, &mut column);
+            }
+        }
+        let mut b_column = apply_b(&column);
+        let length = b_norm(&column, &b_column);
+        if length <= T::epsilon().sqrt() * original {
+            continue;
+        }
+        column.iter_mut().for_each(|v| *v = *v / length);
+        b_column.iter_mut().for_each(|v| *v = *v / length);
+        q.push(column);
+        bq.push(b_column);
+        kept.push(index);
+    }
+    (q, bq, kept)
+}
@@ -0,0 +1,732 @@
+use crate::cancel;
+use crate::equilibrate::Equilibration;
+use crate::progress::{self, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, Permutation, SolvableFloat, SolveError, SparseLu};
+use num::complex::Complex;
+use num_traits::float::Float;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::time::{Duration, Instant};
+
+/// A sparse LU factorization `PAQ = LU` kept alive on the Eigen side, created by
+/// ```ComplexMatrix::factorize```.
+///
+/// Each ```solve``` only runs the triangular solves, so systems with the same matrix
+/// and many right-hand sides, e.g. the time steps of a circuit simulation, pay for
+/// the factorization once.
+///
+/// The solves only read the factors, so a factorization can be shared between
+/// threads that solve concurrently.
+///```rust
+/// use sparse_complex::ComplexMatrix;
+/// use num::Complex;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let mut m = ComplexMatrix::<f64>::new();
+/// m.add_element(0, 0, Complex::new(2., 0.));
+/// m.add_element(1, 1, Complex::new(0., 1.));
+/// let lu = Arc::new(m.factorize().unwrap());
+///
+/// let handles = (1..5)
+///     .map(|k| {
+///         let lu = Arc::clone(&lu);
+///         thread::spawn(move || {
+///             let mut b = vec![Complex::new(2. * k as f64, 0.), Complex::new(0., 1.)];
+///             lu.solve(&mut b).unwrap();
+///             b
+///         })
+///     })
+///     .collect::<Vec<_>>();
+/// for (k, handle) in (1..5).zip(handles) {
+///     assert_eq!(handle.join().unwrap()[0], Complex::new(k as f64, 0.));
+/// }
+///```
+pub struct ComplexLu<T: Float> {
+    context: solver::Context,
+    size: usize,
+    stats: FactorizationStats,
+    scaling: Option<Equilibration<T>>,
+    /// The largest entry modulus of the factorized matrix, for ```to_sparse_lu```.
+    max_abs: T,
+    marker: PhantomData<T>,
+}
+
+// The methods taking `&self` only call the const entry points of the context, which
+// read the factors without caching anything, so shared references may cross threads.
+unsafe impl<T: Float + Sync> Sync for ComplexLu<T> {}
+
+impl<T: Float> ComplexLu<T> {
+    /// The order of the factorized matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The statistics of the last successful factorization.
+    pub fn stats(&self) -> &FactorizationStats {
+        &self.stats
+    }
+
+    /// Returns `true` if the matrix was equilibrated before the factorization, see
+    /// ```LuOptions::set_equilibrate```.
+    pub fn is_equilibrated(&self) -> bool {
+        self.scaling.is_some()
+    }
+
+    /// Runs the triangular solves of ```kernel``` on ```b```. An equilibrated system is
+    /// solved on a scaled copy, so ```b``` is only overwritten once ```kernel``` succeeds.
+    fn solve_scaled<F>(&self, b: &mut [Complex<T>], transposed: bool, kernel: F) -> Result<(), SolveError>
+    where
+        F: FnOnce(&mut [Complex<T>]) -> c_int,
+    {
+        if b.len() != self.size {
+            return Err(SolveError::DimensionMismatch);
+        }
+        match &self.scaling {
+            Some(scaling) => {
+                let mut x = b.to_vec();
+                if transposed {
+                    scaling.scale_cols(&mut x);
+                } else {
+                    scaling.scale_rows(&mut x);
+                }
+                solver::info_to_result(kernel(&mut x))?;
+                if transposed {
+                    scaling.scale_rows(&mut x);
+                } else {
+                    scaling.scale_cols(&mut x);
+                }
+                b.copy_from_slice(&x);
+            }
+            None => solver::info_to_result(kernel(b))?,
+        }
+        progress::report(Phase::Solve, Some(1.));
+        Ok(())
+    }
+}
+
+/// Statistics of a sparse LU factorization, e.g. to compare orderings and pivot
+/// thresholds on a large model. See ```ComplexLu::stats```.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FactorizationStats {
+    /// Entries of `L`, including its unit diagonal.
+    pub nnz_l: usize,
+    /// Entries of `U`, including its diagonal.
+    pub nnz_u: usize,
+    /// `(nnz_l + nnz_u) / nnz(A)`, with the duplicates of `A` summed.
+    pub fill_ratio: f64,
+    /// Floating point operations of the numeric factorization, counted from the
+    /// factors as in ```FactorizationEstimate::flops```.
+    pub flops: f64,
+    /// Approximate heap usage of the factorization, in bytes.
+    pub memory_bytes: usize,
+    /// Wall-clock time of the factorization, including the symbolic analysis when
+    /// it ran.
+    pub elapsed: Duration,
+}
+
+/// The factors of a sparse LU factorization `PAQ = LU`, copied out of Eigen by
+/// ```ComplexLu::factors```, e.g. to inspect the fill-in or to reuse them elsewhere.
+///
+/// Row ```i``` of `A` is row ```row_permutation().get(i)``` of `LU` and column ```j```
+/// of `A` is column ```column_permutation().get(j)```.
+#[derive(Clone, PartialEq)]
+pub struct LuFactors<T: Float> {
+    lower: ComplexMatrix<T>,
+    upper: ComplexMatrix<T>,
+    row_permutation: Permutation,
+    column_permutation: Permutation,
+}
+
+impl<T: Float> LuFactors<T> {
+    /// The unit lower triangular factor `L`, with its unit diagonal stored.
+    pub fn lower(&self) -> &ComplexMatrix<T> {
+        &self.lower
+    }
+
+    /// The upper triangular factor `U`.
+    pub fn upper(&self) -> &ComplexMatrix<T> {
+        &self.upper
+    }
+
+    /// The row permutation `P`.
+    pub fn row_permutation(&self) -> &Permutation {
+        &self.row_permutation
+    }
+
+    /// The column permutation `Q`, i.e. the fill-reducing ordering.
+    pub fn column_permutation(&self) -> &Permutation {
+        &self.column_permutation
+    }
+}
+
+/// The fill-reducing column ordering of the sparse LU factorization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColumnOrdering {
+    /// Approximate minimum degree ordering of the columns of `A`, the default.
+    Colamd = 0,
+    /// Approximate minimum degree ordering of the pattern of `A + Aᵀ`, often better
+    /// for matrices with a nearly symmetric pattern, e.g. from finite elements.
+    Amd = 1,
+    /// No reordering, for matrices already ordered by the caller.
+    Natural = 2,
+}
+
+/// Options of the sparse LU factorization, see ```ComplexMatrix::factorize_with_options```.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LuOptions {
+    pivot_threshold: f64,
+    ordering: ColumnOrdering,
+    equilibrate: bool,
+}
+
+impl Default for LuOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LuOptions {
+    /// The default options: partial pivoting, the COLAMD ordering and no
+    /// equilibration.
+    pub fn new() -> Self {
+        LuOptions {
+            pivot_threshold: 1.,
+            ordering: ColumnOrdering::Colamd,
+            equilibrate: false,
+        }
+    }
+
+    /// Sets the threshold of the threshold partial pivoting: the diagonal element is
+    /// kept as pivot if its magnitude is at least ```threshold``` times the largest one
+    /// of its column. The default 1 is partial pivoting; smaller values, e.g. 0.1 for
+    /// circuit matrices, preserve the sparsity of the ordering at some cost in
+    /// stability.
+    ///
+    /// # Panics
+    /// Panics if ```threshold``` is not in `[0, 1]`.
+    pub fn set_pivot_threshold(&mut self, threshold: f64) {
+        assert!(
+            (0. ..=1.).contains(&threshold),
+            "the pivot threshold must be in [0, 1]"
+        );
+        self.pivot_threshold = threshold;
+    }
+
+    /// The pivot threshold.
+    pub fn pivot_threshold(&self) -> f64 {
+        self.pivot_threshold
+    }
+
+    /// Sets the fill-reducing column ordering.
+    pub fn set_ordering(&mut self, ordering: ColumnOrdering) {
+        self.ordering = ordering;
+    }
+
+    /// The fill-reducing column ordering.
+    pub fn ordering(&self) -> ColumnOrdering {
+        self.ordering
+    }
+
+    /// Sets whether to equilibrate the matrix before the factorization: its rows and
+    /// columns are scaled by powers of two so that their largest entries are close to
+    /// one, and the solutions are unscaled. This improves the pivoting and accuracy of
+    /// badly scaled matrices, e.g. mixing impedances and admittances of very
+    /// different magnitudes.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, LuOptions};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1e-9, 0.));
+    /// m.add_element(0, 1, Complex::new(1e-9, 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1e6));
+    ///
+    /// let mut options = LuOptions::new();
+    /// options.set_equilibrate(true);
+    /// let lu = m.factorize_with_options(&options).unwrap();
+    /// let mut b = vec![Complex::new(2e-9, 0.), Complex::new(0., 1e6)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    /// assert!((b[1] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn set_equilibrate(&mut self, equilibrate: bool) {
+        self.equilibrate = equilibrate;
+    }
+
+    /// Whether the matrix is equilibrated before the factorization.
+    pub fn equilibrate(&self) -> bool {
+        self.equilibrate
+    }
+}
+
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Compute the sparse LU factorization of the matrix, to solve it repeatedly.
+    ///
+    /// Returns `Err` if the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    /// let lu = m.factorize().unwrap();
+    ///
+    /// for k in 1..4 {
+    ///     let mut b = vec![Complex::new(2. * k as f64, 0.), Complex::new(0., k as f64)];
+    ///     lu.solve(&mut b).unwrap();
+    ///     assert_eq!(b, vec![Complex::new(k as f64, 0.); 2]);
+    /// }
+    ///```
+    pub fn factorize(&self) -> Result<ComplexLu<T>, SolveError> {
+        self.factorize_with_options(&LuOptions::new())
+    }
+
+    /// Compute the sparse LU factorization of the matrix with the given options.
+    ///
+    /// Returns `Err` if the matrix is singular. Under ```with_cancellation```, the token
+    /// is checked before the factorization starts: Eigen cannot be interrupted once it
+    /// runs.
+    ///```rust
+    /// use sparse_complex::{ColumnOrdering, ComplexMatrix, LuOptions};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let mut options = LuOptions::new();
+    /// options.set_pivot_threshold(0.1);
+    /// options.set_ordering(ColumnOrdering::Amd);
+    /// let lu = m.factorize_with_options(&options).unwrap();
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(3., 0.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn factorize_with_options(&self, options: &LuOptions) -> Result<ComplexLu<T>, SolveError> {
+        let size = self.dimension();
+        self.debug_validate(size);
+        cancel::check()?;
+        progress::report(Phase::Factorization, Some(0.));
+        let start = Instant::now();
+        let scaling = options.equilibrate.then(|| Equilibration::new(self));
+        let scaled = scaling.as_ref().map(|scaling| scaling.apply(self));
+        let matrix = scaled.as_ref().unwrap_or(self);
+        let mut context = T::new_context()?;
+        unsafe { T::context_set_options(&mut context, options.pivot_threshold, options.ordering as c_int) };
+        let info = unsafe { T::context_factorize(&mut context, matrix, size) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Factorization, Some(1.));
+        let mut lu = ComplexLu {
+            context,
+            size,
+            stats: FactorizationStats::default(),
+            scaling,
+            max_abs: max_abs(self),
+            marker: PhantomData,
+        };
+        lu.stats = lu.collect_stats(start.elapsed())?;
+        Ok(lu)
+    }
+
+    /// Solve `Ax=b` for each right-hand side of ```rhs```, factorizing the matrix once.
+    /// The solutions are stored in ```rhs```.
+    ///
+    /// Returns `Err`, leaving ```rhs``` untouched, if a right-hand side does not match
+    /// the matrix size or the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let mut rhs = vec![
+    ///     vec![Complex::new(2., 0.), Complex::new(0., 1.)],
+    ///     vec![Complex::new(4., 0.), Complex::new(0., 2.)],
+    /// ];
+    /// m.solve_multi(&mut rhs).unwrap();
+    /// assert_eq!(rhs[0], vec![Complex::new(1., 0.); 2]);
+    /// assert_eq!(rhs[1], vec![Complex::new(2., 0.); 2]);
+    ///```
+    pub fn solve_multi(&self, rhs: &mut [Vec<Complex<T>>]) -> Result<(), SolveError> {
+        let size = self.dimension();
+        if rhs.iter().any(|b| b.len() != size) {
+            return Err(SolveError::DimensionMismatch);
+        }
+        if rhs.is_empty() {
+            return Ok(());
+        }
+        let lu = self.factorize()?;
+        for b in rhs.iter_mut() {
+            lu.solve(b)?;
+        }
+        Ok(())
+    }
+
+    /// Solve the system `Ax=b` and return the statistics of the factorization. The
+    /// result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(2., 0.)];
+    /// let stats = m.solve_with_stats(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    /// assert!(stats.fill_ratio >= 1.);
+    ///```
+    pub fn solve_with_stats(&self, b: &mut [Complex<T>]) -> Result<FactorizationStats, SolveError> {
+        self.check_rhs(b.len())?;
+        let lu = self.factorize()?;
+        lu.solve(b)?;
+        Ok(*lu.stats())
+    }
+
+    /// Solve the system `Aᵀx=b` with the factorization of `A`, without forming the
+    /// transpose, e.g. for adjoint sensitivities of complex symmetric models. The
+    /// result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(1., 1.)];
+    /// m.solve_transposed(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.), Complex::new(1., 0.)]);
+    ///```
+    pub fn solve_transposed(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        self.factorize()?.solve_transposed(b)
+    }
+
+    /// Solve the system `Aᴴx=b` with the factorization of `A`, without forming the
+    /// conjugate transpose, e.g. for the adjoint method of frequency-domain
+    /// sensitivities. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size or the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(1., -1.)];
+    /// m.solve_adjoint(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.), Complex::new(1., 0.)]);
+    ///```
+    pub fn solve_adjoint(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        self.factorize()?.solve_adjoint(b)
+    }
+
+    /// Returns the determinant of the matrix, from the diagonal of its LU factors, or
+    /// zero if the matrix is singular, e.g. to locate the resonances of a frequency
+    /// sweep.
+    ///
+    /// The determinant of a large matrix easily overflows or underflows.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    ///
+    /// assert_eq!(m.determinant().unwrap(), Complex::new(0., -2.));
+    ///```
+    pub fn determinant(&self) -> Result<Complex<T>, SolveError> {
+        match self.factorize() {
+            Ok(lu) => lu.determinant(),
+            Err(SolveError::Singular) => Ok(Complex::new(T::zero(), T::zero())),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns `ln|det A|`, which does not overflow for large matrices, or `-∞` if
+    /// the matrix is singular. See ```determinant_phase``` for the phase, and
+    /// ```ComplexLu``` to compute both with one factorization.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// // det = 1e-400 underflows in f64.
+    /// let n = 400;
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..n {
+    ///     m.add_element(i, i, Complex::new(0., 0.1));
+    /// }
+    ///
+    /// assert!((m.log_abs_determinant().unwrap() - n as f64 * 0.1f64.ln()).abs() < 1e-9);
+    /// assert!((m.determinant_phase().unwrap() - Complex::new(1., 0.)).norm() < 1e-9);
+    ///```
+    pub fn log_abs_determinant(&self) -> Result<T, SolveError> {
+        match self.factorize() {
+            Ok(lu) => lu.log_abs_determinant(),
+            Err(SolveError::Singular) => Ok(T::neg_infinity()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the phase `det A / |det A|` of the determinant, or zero if the matrix is
+    /// singular.
+    pub fn determinant_phase(&self) -> Result<Complex<T>, SolveError> {
+        match self.factorize() {
+            Ok(lu) => lu.determinant_phase(),
+            Err(SolveError::Singular) => Ok(Complex::new(T::zero(), T::zero())),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T: SolvableFloat> ComplexLu<T> {
+    /// Refactorize with the values of ```matrix```, reusing the symbolic analysis
+    /// (the fill-reducing ordering and the elimination structure) of the previous
+    /// factorization, e.g. for the Jacobians of Newton–Raphson iterations.
+    ///
+    /// Only the numeric factorization runs while ```matrix``` has the same elements,
+    /// in the same order, as the factorized matrix; a new pattern is analyzed again.
+    /// Returns `Err` if the matrix is singular, after which ```solve``` fails until a
+    /// refactorization succeeds. A cancellation token is only checked before the
+    /// refactorization, as in ```ComplexMatrix::factorize_with_options```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let mut lu = m.factorize().unwrap();
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(4., 0.));
+    /// lu.refactorize(&m).unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(4., 0.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn refactorize(&mut self, matrix: &ComplexMatrix<T>) -> Result<(), SolveError> {
+        let size = matrix.dimension();
+        matrix.debug_validate(size);
+        cancel::check()?;
+        progress::report(Phase::Factorization, Some(0.));
+        let start = Instant::now();
+        self.size = size;
+        let max_abs = max_abs(matrix);
+        if self.scaling.is_some() {
+            self.scaling = Some(Equilibration::new(matrix));
+        }
+        let scaled = self.scaling.as_ref().map(|scaling| scaling.apply(matrix));
+        let matrix = scaled.as_ref().unwrap_or(matrix);
+        let info = unsafe { T::context_factorize(&mut self.context, matrix, size) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Factorization, Some(1.));
+        self.stats = self.collect_stats(start.elapsed())?;
+        self.max_abs = max_abs;
+        Ok(())
+    }
+
+    /// Solve the system `Ax=b` with the factorization. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.solve_scaled(b, false, |b| unsafe { T::context_solve(&self.context, b) })
+    }
+
+    /// Solve the system `Aᵀx=b` with the factorization. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_transposed(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.solve_scaled(b, true, |b| unsafe { T::context_solve_transposed(&self.context, b) })
+    }
+
+    /// Solve the system `Aᴴx=b` with the factorization. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_adjoint(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.solve_scaled(b, true, |b| unsafe { T::context_solve_adjoint(&self.context, b) })
+    }
+
+    /// Returns the determinant of the factorized matrix.
+    ///
+    /// Returns `Err` if the last refactorization failed.
+    pub fn determinant(&self) -> Result<Complex<T>, SolveError> {
+        let mut det = Complex::new(T::zero(), T::zero());
+        let info = unsafe { T::context_determinant(&self.context, &mut det) };
+        solver::info_to_result(info)?;
+        Ok(match &self.scaling {
+            Some(scaling) => scaling.unscale_determinant(det),
+            None => det,
+        })
+    }
+
+    /// Returns `ln|det A|` of the factorized matrix.
+    ///
+    /// Returns `Err` if the last refactorization failed.
+    pub fn log_abs_determinant(&self) -> Result<T, SolveError> {
+        self.log_determinant().map(|(log_abs, _)| log_abs)
+    }
+
+    /// Returns the phase `det A / |det A|` of the determinant of the factorized matrix.
+    ///
+    /// Returns `Err` if the last refactorization failed.
+    pub fn determinant_phase(&self) -> Result<Complex<T>, SolveError> {
+        self.log_determinant().map(|(_, phase)| phase)
+    }
+
+    fn log_determinant(&self) -> Result<(T, Complex<T>), SolveError> {
+        let (mut log_abs, mut phase) = (T::zero(), Complex::new(T::zero(), T::zero()));
+        let info = unsafe { T::context_log_determinant(&self.context, &mut log_abs, &mut phase) };
+        solver::info_to_result(info)?;
+        if let Some(scaling) = &self.scaling {
+            log_abs = scaling.unscale_log_abs_determinant(log_abs);
+        }
+        Ok((log_abs, phase))
+    }
+
+    fn collect_stats(&self, elapsed: Duration) -> Result<FactorizationStats, SolveError> {
+        let (mut nnz_a, mut nnz_l, mut nnz_u, mut flops) = (0, 0, 0, 0.);
+        let info = unsafe { T::context_factor_stats(&self.context, &mut nnz_a, &mut nnz_l, &mut nnz_u, &mut flops) };
+        solver::info_to_result(info)?;
+        Ok(FactorizationStats {
+            nnz_l,
+            nnz_u,
+            fill_ratio: (nnz_l + nnz_u) as f64 / nnz_a.max(1) as f64,
+            flops,
+            memory_bytes: unsafe { T::context_memory_usage(&self.context) },
+            elapsed,
+        })
+    }
+
+    /// Copy the factors `L` and `U` and the permutations out of the factorization.
+    /// The factors of an equilibrated matrix are those of the scaled matrix.
+    ///
+    /// Returns `Err` if the last refactorization failed.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let factors = m.factorize().unwrap().factors().unwrap();
+    /// assert_eq!(factors.lower().get(0, 0), Some(&Complex::new(1., 0.)));
+    /// assert_eq!(factors.row_permutation().len(), 2);
+    ///```
+    pub fn factors(&self) -> Result<LuFactors<T>, SolveError> {
+        let (mut l_nnz, mut u_nnz) = (0, 0);
+        let info = unsafe { T::context_factor_counts(&self.context, &mut l_nnz, &mut u_nnz) };
+        solver::info_to_result(info)?;
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut lower = ComplexMatrix {
+            entries: vec![zero; l_nnz],
+            rows: vec![0; l_nnz],
+            cols: vec![0; l_nnz],
+        };
+        let mut upper = ComplexMatrix {
+            entries: vec![zero; u_nnz],
+            rows: vec![0; u_nnz],
+            cols: vec![0; u_nnz],
+        };
+        let (mut row_perm, mut col_perm) = (vec![0; self.size], vec![0; self.size]);
+        let info = unsafe { T::context_factors(&self.context, &mut lower, &mut upper, &mut row_perm, &mut col_perm) };
+        solver::info_to_result(info)?;
+        Ok(LuFactors {
+            lower,
+            upper,
+            row_permutation: Permutation::from_vec(row_perm)?,
+            column_permutation: Permutation::from_vec(col_perm)?,
+        })
+    }
+
+    /// Copy the factorization into a pure-Rust ```SparseLu```, which solves the same
+    /// systems without Eigen and can be serialized with ```SparseLu::to_bytes```. The
+    /// factors of an equilibrated matrix are unscaled, so the copy factorizes `A`.
+    ///
+    /// Returns `Err` if the last refactorization failed.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SparseLu};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let lu = m.factorize().unwrap().to_sparse_lu().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(1., 1.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn to_sparse_lu(&self) -> Result<SparseLu<T>, SolveError> {
+        let LuFactors {
+            mut lower,
+            mut upper,
+            row_permutation,
+            column_permutation,
+        } = self.factors()?;
+        let pivots = row_permutation.inverse().as_slice().to_vec();
+        let columns = column_permutation.inverse().as_slice().to_vec();
+        if let Some(scaling) = &self.scaling {
+            scaling.unscale_factors(&mut lower, &mut upper, &pivots, &columns);
+        }
+        SparseLu::from_factors(&lower, &upper, pivots, columns, self.max_abs)
+    }
+
+    /// Write the factors and permutations to ```path```, to reuse the factorization in
+    /// another run without factorizing again. The file is written by
+    /// ```SparseLu::save``` from ```to_sparse_lu```, and is read back as a ```SparseLu```
+    /// with ```SparseLu::load```: the Eigen factorization itself cannot be restored.
+    ///
+    /// Returns an `InvalidInput` error if the last refactorization failed, or the error
+    /// writing the file.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SparseLu};
+    /// use num::Complex;
+    ///
+    /// let path = std::env::temp_dir().join("sparse_complex_doc_complex_lu.bin");
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    /// m.factorize().unwrap().save(&path).unwrap();
+    ///
+    /// let lu = SparseLu::<f64>::load(&path).unwrap();
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(0., 1.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    /// # std::fs::remove_file(&path).unwrap();
+    ///```
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        self.to_sparse_lu()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?
+            .save(path)
+    }
+}
+
+/// The largest entry modulus of ```matrix```, as stored.
+fn max_abs<T: Float>(matrix: &ComplexMatrix<T>) -> T {
+    matrix.entries.iter().fold(T::zero(), |max, v| max.max(v.norm()))
+}
@@ -0,0 +1,209 @@
+use crate::dense::DenseLu;
+use crate::SolveError;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A dense parameter matrix of a multi-port network, e.g. its impedance (Z),
+/// admittance (Y) or scattering (S) matrix, stored row-major.
+///
+/// Column ```j``` of the Z matrix is the vector of port voltages obtained by solving
+/// the network with a unit current injected at port ```j```.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortMatrix<T: Float> {
+    ports: usize,
+    values: Vec<Complex<T>>,
+}
+
+impl<T: Float> PortMatrix<T> {
+    /// Create a parameter matrix from its row-major ```ports × ports``` values.
+    ///
+    /// Panics if ```values``` does not have ```ports * ports``` elements.
+    pub fn new(ports: usize, values: Vec<Complex<T>>) -> Self {
+        assert_eq!(
+            values.len(),
+            ports * ports,
+            "the matrix must have ports * ports elements"
+        );
+        PortMatrix { ports, values }
+    }
+
+    /// The number of ports.
+    pub fn ports(&self) -> usize {
+        self.ports
+    }
+
+    /// The parameter between ports ```i``` and ```j```.
+    ///
+    /// Panics if a port is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Complex<T> {
+        assert!(
+            i < self.ports && j < self.ports,
+            "the port is out of bounds"
+        );
+        self.values[i * self.ports + j]
+    }
+
+    /// The row-major values.
+    pub fn as_slice(&self) -> &[Complex<T>] {
+        &self.values
+    }
+
+    /// Convert an impedance matrix into an admittance matrix, `Y = Z⁻¹`.
+    pub fn z_to_y(&self) -> Result<Self, SolveError> {
+        Ok(PortMatrix::new(
+            self.ports,
+            inverse(self.ports, self.values.clone())?,
+        ))
+    }
+
+    /// Convert an admittance matrix into an impedance matrix, `Z = Y⁻¹`.
+    pub fn y_to_z(&self) -> Result<Self, SolveError> {
+        self.z_to_y()
+    }
+
+    /// Convert an impedance matrix into a scattering matrix for the real positive
+    /// ```reference``` impedance of each port.
+    ///
+    /// With `G = diag(z₀)`, `S = G^{-1/2} (Z - G)(Z + G)⁻¹ G^{1/2}`, which reduces
+    /// to `(Z - z₀)(Z + z₀)⁻¹` for a common reference impedance.
+    ///```rust
+    /// use sparse_complex::PortMatrix;
+    /// use num::Complex;
+    ///
+    /// // A 100 Ω load on a 50 Ω port.
+    /// let z = PortMatrix::<f64>::new(1, vec![Complex::new(100., 0.)]);
+    ///
+    /// let s = z.z_to_s(&[50.]).unwrap();
+    /// assert!((s.get(0, 0) - Complex::new(1. / 3., 0.)).norm() < 1e-12);
+    /// assert_eq!(s.s_to_z(&[50.]).unwrap().get(0, 0), Complex::new(100., 0.));
+    ///```
+    pub fn z_to_s(&self, reference: &[T]) -> Result<Self, SolveError> {
+        let n = self.check_reference(reference)?;
+        let mut minus = self.values.clone();
+        let mut plus = self.values.clone();
+        for (i, z0) in reference.iter().enumerate() {
+            minus[i * n + i] = minus[i * n + i] - *z0;
+            plus[i * n + i] = plus[i * n + i] + *z0;
+        }
+        let m = product(n, &minus, &inverse(n, plus)?);
+        Ok(PortMatrix::new(n, scale_waves(n, m, reference, false)))
+    }
+
+    /// Convert an admittance matrix into a scattering matrix for the real positive
+    /// ```reference``` impedance of each port, see ```z_to_s```.
+    ///
+    /// Unlike going through ```y_to_z```, this works for networks without an
+    /// impedance matrix, e.g. a series element between two ports.
+    ///```rust
+    /// use sparse_complex::PortMatrix;
+    /// use num::Complex;
+    ///
+    /// // A 100 Ω series resistor between two 50 Ω ports.
+    /// let (y, zero) = (Complex::new(0.01, 0.), Complex::new(0., 0.));
+    /// let y = PortMatrix::<f64>::new(2, vec![y, zero - y, zero - y, y]);
+    ///
+    /// let s = y.y_to_s(&[50., 50.]).unwrap();
+    /// assert!((s.get(0, 0) - Complex::new(0.5, 0.)).norm() < 1e-12);
+    /// assert!((s.get(1, 0) - Complex::new(0.5, 0.)).norm() < 1e-12);
+    ///```
+    pub fn y_to_s(&self, reference: &[T]) -> Result<Self, SolveError> {
+        let n = self.check_reference(reference)?;
+        // (Z - G)(Z + G)⁻¹ = (I - GY)(I + GY)⁻¹.
+        let mut minus = self.values.iter().map(|y| -*y).collect::<Vec<_>>();
+        let mut plus = self.values.clone();
+        for (i, z0) in reference.iter().enumerate() {
+            for j in 0..n {
+                minus[i * n + j] = minus[i * n + j] * *z0;
+                plus[i * n + j] = plus[i * n + j] * *z0;
+            }
+            minus[i * n + i] = minus[i * n + i] + T::one();
+            plus[i * n + i] = plus[i * n + i] + T::one();
+        }
+        let m = product(n, &minus, &inverse(n, plus)?);
+        Ok(PortMatrix::new(n, scale_waves(n, m, reference, false)))
+    }
+
+    /// Convert a scattering matrix for the ```reference``` impedance of each port into
+    /// an impedance matrix, the inverse of ```z_to_s```.
+    pub fn s_to_z(&self, reference: &[T]) -> Result<Self, SolveError> {
+        let n = self.check_reference(reference)?;
+        let (minus, plus) = self.identity_shifts(reference);
+        // Z = (I - M)⁻¹ (I + M) G.
+        let mut z = product(n, &inverse(n, minus)?, &plus);
+        for (k, v) in z.iter_mut().enumerate() {
+            *v = *v * reference[k % n];
+        }
+        Ok(PortMatrix::new(n, z))
+    }
+
+    /// Convert a scattering matrix for the ```reference``` impedance of each port into
+    /// an admittance matrix, the inverse of ```y_to_s```.
+    pub fn s_to_y(&self, reference: &[T]) -> Result<Self, SolveError> {
+        let n = self.check_reference(reference)?;
+        let (minus, plus) = self.identity_shifts(reference);
+        // Y = G⁻¹ (I + M)⁻¹ (I - M).
+        let mut y = product(n, &inverse(n, plus)?, &minus);
+        for (k, v) in y.iter_mut().enumerate() {
+            *v = *v / reference[k / n];
+        }
+        Ok(PortMatrix::new(n, y))
+    }
+
+    /// Returns the number of ports if ```reference``` has a positive impedance per port.
+    fn check_reference(&self, reference: &[T]) -> Result<usize, SolveError> {
+        if reference.len() != self.ports {
+            return Err(SolveError::DimensionMismatch);
+        }
+        if reference
+            .iter()
+            .any(|z0| *z0 <= T::zero() || !z0.is_finite())
+        {
+            return Err(SolveError::InvalidInput(
+                "the reference impedances must be positive",
+            ));
+        }
+        Ok(self.ports)
+    }
+
+    /// Returns `I - M` and `I + M`, with `M = G^{1/2} S G^{-1/2}`.
+    fn identity_shifts(&self, reference: &[T]) -> (Vec<Complex<T>>, Vec<Complex<T>>) {
+        let n = self.ports;
+        let m = scale_waves(n, self.values.clone(), reference, true);
+        let mut minus = m.iter().map(|v| -*v).collect::<Vec<_>>();
+        let mut plus = m;
+        for i in 0..n {
+            minus[i * n + i] = minus[i * n + i] + T::one();
+            plus[i * n + i] = plus[i * n + i] + T::one();
+        }
+        (minus, plus)
+    }
+}
+
+/// Scales ```a[i][j]``` by `sqrt(z₀[j] / z₀[i])`, or by its inverse if ```inverse```.
+fn scale_waves<T: Float>(
+    n: usize,
+    mut a: Vec<Complex<T>>,
+    reference: &[T],
+    inverse: bool,
+) -> Vec<Complex<T>> {
+    for (k, v) in a.iter_mut().enumerate() {
+        let (zi, zj) = (reference[k / n], reference[k % n]);
+        let ratio = if inverse { zi / zj } else { zj / zi };
+        *v = *v * ratio.sqrt();
+    }
+    a
+}
+
+/// The inverse of the row-major ```n × n``` matrix ```a```.
+fn inverse<T: Float>(n: usize, a: Vec<Complex<T>>) -> Result<Vec<Complex<T>>, SolveError> {
+    Ok(DenseLu::new(n, a)?.inverse())
+}
+
+/// The product of the row-major ```n × n``` matrices ```a``` and ```b```.
+fn product<T: Float>(n: usize, a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    let zero = Complex::new(T::zero(), T::zero());
+    (0..n * n)
+        .map(|k| (0..n).fold(zero, |acc, l| acc + a[(k / n) * n + l] * b[l * n + k % n]))
+        .collect()
+}
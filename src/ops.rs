@@ -0,0 +1,400 @@
+use crate::compressed::compress;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// How elementwise divisions treat a zero or missing denominator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZeroPolicy<T: Float> {
+    /// The element is left out of the result.
+    Skip,
+    /// The operation returns `Err`.
+    Error,
+    /// The element is set to the given value.
+    Substitute(Complex<T>),
+}
+
+/// The stored element of a matrix at the position of each element of another, looked
+/// up once by ```ComplexMatrix::pattern_slots``` to repeat ```axpy_with_slots```.
+///
+/// The slots stay valid while the stored elements of both matrices keep their
+/// positions: only the values may change between the updates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternSlots {
+    slots: Vec<usize>,
+    nnz: usize,
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Transpose the matrix in place by swapping the row and column index arrays.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 2.));
+    /// m.transpose_in_place();
+    /// assert_eq!(m.get(1, 0), Some(&Complex::new(1., 2.)));
+    ///```
+    pub fn transpose_in_place(&mut self) {
+        core::mem::swap(&mut self.rows, &mut self.cols);
+    }
+
+    /// Conjugate every value in place.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 2.));
+    /// m.conj_in_place();
+    /// assert_eq!(m.get(0, 1), Some(&Complex::new(1., -2.)));
+    ///```
+    pub fn conj_in_place(&mut self) {
+        for value in self.entries.iter_mut() {
+            *value = value.conj();
+        }
+    }
+
+    /// Multiply every value by ```alpha``` in place.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 2.));
+    /// m.scale_in_place(Complex::new(0., 1.));
+    /// assert_eq!(m.get(0, 1), Some(&Complex::new(-2., 1.)));
+    ///```
+    pub fn scale_in_place(&mut self, alpha: Complex<T>) {
+        for value in self.entries.iter_mut() {
+            *value = *value * alpha;
+        }
+    }
+
+    /// Compute `self ← αA + βself`, appending the scaled elements of ```a```.
+    ///
+    /// The stored pattern grows by the elements of ```a```; positions present in both
+    /// matrices become duplicates, which are summed when the matrix is compressed.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut g = ComplexMatrix::<f64>::new();
+    /// g.add_element(0, 0, Complex::new(1., 0.));
+    /// let mut c = ComplexMatrix::<f64>::new();
+    /// c.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// g.axpy(Complex::new(10., 0.), &c, Complex::new(1., 0.));
+    /// assert_eq!(g.get(1, 1), Some(&Complex::new(20., 0.)));
+    ///```
+    pub fn axpy(&mut self, alpha: Complex<T>, a: &ComplexMatrix<T>, beta: Complex<T>) {
+        self.scale_in_place(beta);
+        self.entries.extend(a.entries.iter().map(|v| *v * alpha));
+        self.rows.extend_from_slice(&a.rows);
+        self.cols.extend_from_slice(&a.cols);
+    }
+
+    /// Compute `self ← αA + βself` without growing the stored pattern.
+    ///
+    /// Every element of ```a``` is accumulated into a stored element of ```self``` at the
+    /// same position. Returns `Err`, leaving ```self``` unchanged, if ```a``` has an element
+    /// outside the pattern of ```self```.
+    ///
+    /// The positions are looked up on every call, in `O(nnz log nnz)`; to repeat the
+    /// update, e.g. forming `G + C/h` in the storage of `G` on every step of a
+    /// time-stepper, look them up once with ```pattern_slots``` and use
+    /// ```axpy_with_slots```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut g = ComplexMatrix::<f64>::new();
+    /// g.add_element(0, 0, Complex::new(1., 0.));
+    /// g.add_element(1, 1, Complex::new(1., 0.));
+    /// let mut c = ComplexMatrix::<f64>::new();
+    /// c.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// g.axpy_in_pattern(Complex::new(10., 0.), &c, Complex::new(1., 0.)).unwrap();
+    /// assert_eq!(g.get(1, 1), Some(&Complex::new(21., 0.)));
+    ///
+    /// c.add_element(0, 1, Complex::new(1., 0.));
+    /// assert!(g.axpy_in_pattern(Complex::new(1., 0.), &c, Complex::new(1., 0.)).is_err());
+    ///```
+    pub fn axpy_in_pattern(
+        &mut self,
+        alpha: Complex<T>,
+        a: &ComplexMatrix<T>,
+        beta: Complex<T>,
+    ) -> Result<(), SolveError> {
+        let slots = self.pattern_slots(a)?;
+        self.axpy_with_slots(alpha, a, beta, &slots)
+    }
+
+    /// Look up the stored element of ```self``` at the position of each element of ```a```,
+    /// for ```axpy_with_slots```.
+    ///
+    /// Returns `Err` if ```a``` has an element outside the pattern of ```self```.
+    pub fn pattern_slots(&self, a: &ComplexMatrix<T>) -> Result<PatternSlots, SolveError> {
+        let mut order = (0..self.entries.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&k| (self.rows[k], self.cols[k]));
+        let slots = a
+            .rows
+            .iter()
+            .zip(a.cols.iter())
+            .map(|(row, col)| {
+                order
+                    .binary_search_by_key(&(*row, *col), |&k| (self.rows[k], self.cols[k]))
+                    .map(|position| order[position])
+                    .map_err(|_| {
+                        SolveError::InvalidInput("the matrix has an element outside of the pattern")
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PatternSlots {
+            slots,
+            nnz: self.entries.len(),
+        })
+    }
+
+    /// Compute `self ← αA + βself` in the stored pattern, with the positions looked up
+    /// by ```pattern_slots```. The update is `O(nnz(A) + nnz(self))` and does not
+    /// allocate.
+    ///
+    /// Returns `Err`, leaving ```self``` unchanged, if the number of stored elements of
+    /// either matrix changed since ```slots``` was computed.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut g = ComplexMatrix::<f64>::new();
+    /// g.add_element(0, 0, Complex::new(1., 0.));
+    /// g.add_element(1, 1, Complex::new(1., 0.));
+    /// let mut c = ComplexMatrix::<f64>::new();
+    /// c.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let slots = g.pattern_slots(&c).unwrap();
+    /// for _ in 0..3 {
+    ///     g.axpy_with_slots(Complex::new(1., 0.), &c, Complex::new(1., 0.), &slots).unwrap();
+    /// }
+    /// assert_eq!(g.get(1, 1), Some(&Complex::new(7., 0.)));
+    ///```
+    pub fn axpy_with_slots(
+        &mut self,
+        alpha: Complex<T>,
+        a: &ComplexMatrix<T>,
+        beta: Complex<T>,
+        slots: &PatternSlots,
+    ) -> Result<(), SolveError> {
+        if slots.nnz != self.entries.len() || slots.slots.len() != a.entries.len() {
+            return Err(SolveError::DimensionMismatch);
+        }
+        self.scale_in_place(beta);
+        for (slot, value) in slots.slots.iter().zip(a.entries.iter()) {
+            self.entries[*slot] = self.entries[*slot] + alpha * *value;
+        }
+        Ok(())
+    }
+
+    /// The elementwise product over the positions stored in both matrices.
+    ///
+    /// Duplicated entries are summed before multiplying, and the result has one
+    /// element per position of the pattern intersection.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut a = ComplexMatrix::<f64>::new();
+    /// a.add_element(0, 0, Complex::new(2., 0.));
+    /// a.add_element(0, 1, Complex::new(3., 0.));
+    /// let mut mask = ComplexMatrix::<f64>::new();
+    /// mask.add_element(0, 1, Complex::new(0., 1.));
+    /// mask.add_element(1, 1, Complex::new(1., 0.));
+    ///
+    /// let masked = a.hadamard(&mask);
+    /// assert_eq!(masked.get(0, 1), Some(&Complex::new(0., 3.)));
+    /// assert_eq!(masked.get(0, 0), None);
+    /// assert_eq!(masked.get(1, 1), None);
+    ///```
+    pub fn hadamard(&self, other: &ComplexMatrix<T>) -> ComplexMatrix<T> {
+        let mut product = ComplexMatrix::new();
+        self.for_each_with(other, |row, col, a, b| {
+            if let Some(b) = b {
+                product.add_element(row, col, a * b);
+            }
+        });
+        product
+    }
+
+    /// The reciprocal of every stored element, summing duplicated entries first.
+    ///
+    /// Zero elements are handled according to ```policy```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, ZeroPolicy};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(0., 2.));
+    /// m.add_element(1, 1, Complex::new(0., 0.));
+    ///
+    /// let r = m.recip(ZeroPolicy::Skip).unwrap();
+    /// assert_eq!(r.get(0, 0), Some(&Complex::new(0., -0.5)));
+    /// assert_eq!(r.get(1, 1), None);
+    /// assert!(m.recip(ZeroPolicy::Error).is_err());
+    ///```
+    pub fn recip(&self, policy: ZeroPolicy<T>) -> Result<ComplexMatrix<T>, SolveError> {
+        let one = Complex::new(T::one(), T::zero());
+        let csr = compress(self.dimension(), &self.rows, &self.cols, &self.entries);
+        let mut reciprocal = ComplexMatrix::with_capacity(csr.values.len());
+        for row in 0..csr.n {
+            for (col, value) in csr.row(row) {
+                divide_into(&mut reciprocal, row, col, one, Some(*value), policy)?;
+            }
+        }
+        Ok(reciprocal)
+    }
+
+    /// The elementwise quotient `self ./ other` over the pattern of ```self```.
+    ///
+    /// Duplicated entries are summed first. Positions of ```self``` that are missing or
+    /// zero in ```other``` are handled according to ```policy```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, ZeroPolicy};
+    /// use num::Complex;
+    ///
+    /// let mut a = ComplexMatrix::<f64>::new();
+    /// a.add_element(0, 0, Complex::new(4., 0.));
+    /// a.add_element(0, 1, Complex::new(1., 0.));
+    /// let mut d = ComplexMatrix::<f64>::new();
+    /// d.add_element(0, 0, Complex::new(2., 0.));
+    ///
+    /// let q = a.component_div(&d, ZeroPolicy::Substitute(Complex::new(0., 0.))).unwrap();
+    /// assert_eq!(q.get(0, 0), Some(&Complex::new(2., 0.)));
+    /// assert_eq!(q.get(0, 1), Some(&Complex::new(0., 0.)));
+    ///```
+    pub fn component_div(
+        &self,
+        other: &ComplexMatrix<T>,
+        policy: ZeroPolicy<T>,
+    ) -> Result<ComplexMatrix<T>, SolveError> {
+        let mut quotient = ComplexMatrix::new();
+        let mut result = Ok(());
+        self.for_each_with(other, |row, col, a, b| {
+            if result.is_ok() {
+                result = divide_into(&mut quotient, row, col, a, b, policy);
+            }
+        });
+        result.map(|_| quotient)
+    }
+
+    /// Lists the positions where the two matrices disagree, as
+    /// ```(row, col, self value, other value)``` sorted by position.
+    ///
+    /// Duplicated entries are summed first. A position stored in both matrices is
+    /// reported if the values differ by more than ```tol``` in absolute value. A position
+    /// stored in only one of them is always reported, with zero for the missing value.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut reference = ComplexMatrix::<f64>::new();
+    /// reference.add_element(0, 0, Complex::new(1., 0.));
+    /// reference.add_element(1, 1, Complex::new(2., 0.));
+    /// let mut assembled = ComplexMatrix::<f64>::new();
+    /// assembled.add_element(0, 0, Complex::new(1. + 1e-14, 0.));
+    /// assembled.add_element(0, 1, Complex::new(3., 0.));
+    ///
+    /// let zero = Complex::new(0., 0.);
+    /// assert_eq!(
+    ///     assembled.diff(&reference, 1e-12),
+    ///     vec![(0, 1, Complex::new(3., 0.), zero), (1, 1, zero, Complex::new(2., 0.))]
+    /// );
+    ///```
+    pub fn diff(&self, other: &ComplexMatrix<T>, tol: T) -> Vec<(usize, usize, Complex<T>, Complex<T>)> {
+        let zero = Complex::new(T::zero(), T::zero());
+        let n = self.dimension().max(other.dimension());
+        let a = compress(n, &self.rows, &self.cols, &self.entries);
+        let b = compress(n, &other.rows, &other.cols, &other.entries);
+
+        let mut differences = Vec::new();
+        for row in 0..n {
+            let mut a_row = a.row(row).peekable();
+            let mut b_row = b.row(row).peekable();
+            loop {
+                let (col, a_value, b_value) = match (a_row.peek(), b_row.peek()) {
+                    (None, None) => break,
+                    (Some((ca, _)), Some((cb, _))) if ca == cb => {
+                        let (col, a_value) = a_row.next().unwrap();
+                        let (_, b_value) = b_row.next().unwrap();
+                        if (*a_value - *b_value).norm() <= tol {
+                            continue;
+                        }
+                        (col, *a_value, *b_value)
+                    }
+                    (Some((ca, _)), Some((cb, _))) if ca > cb => {
+                        let (col, b_value) = b_row.next().unwrap();
+                        (col, zero, *b_value)
+                    }
+                    (Some(_), _) => {
+                        let (col, a_value) = a_row.next().unwrap();
+                        (col, *a_value, zero)
+                    }
+                    (None, Some(_)) => {
+                        let (col, b_value) = b_row.next().unwrap();
+                        (col, zero, *b_value)
+                    }
+                };
+                differences.push((row, col, a_value, b_value));
+            }
+        }
+        differences
+    }
+
+    /// Calls ```f(row, col, a, b)``` for each compressed element `a` of ```self```, with `b`
+    /// the compressed element of ```other``` at the same position, if any.
+    fn for_each_with<F>(&self, other: &ComplexMatrix<T>, mut f: F)
+    where
+        F: FnMut(usize, usize, Complex<T>, Option<Complex<T>>),
+    {
+        let n = self.dimension().max(other.dimension());
+        let a = compress(n, &self.rows, &self.cols, &self.entries);
+        let b = compress(n, &other.rows, &other.cols, &other.entries);
+        for row in 0..n {
+            let mut b_row = b.row(row).peekable();
+            for (col, a_value) in a.row(row) {
+                while let Some((c, _)) = b_row.peek() {
+                    if *c >= col {
+                        break;
+                    }
+                    b_row.next();
+                }
+                let b_value = match b_row.peek() {
+                    Some((c, value)) if *c == col => Some(**value),
+                    _ => None,
+                };
+                f(row, col, *a_value, b_value);
+            }
+        }
+    }
+}
+
+/// Adds `a / b` at ```(row, col)``` of ```m```, following ```policy``` if `b` is zero or missing.
+fn divide_into<T: Float>(
+    m: &mut ComplexMatrix<T>,
+    row: usize,
+    col: usize,
+    a: Complex<T>,
+    b: Option<Complex<T>>,
+    policy: ZeroPolicy<T>,
+) -> Result<(), SolveError> {
+    match b.filter(|b| b.re != T::zero() || b.im != T::zero()) {
+        Some(b) => m.add_element(row, col, a / b),
+        None => match policy {
+            ZeroPolicy::Skip => {}
+            ZeroPolicy::Error => return Err(SolveError::InvalidInput("division by a zero element")),
+            ZeroPolicy::Substitute(value) => m.add_element(row, col, value),
+        },
+    }
+    Ok(())
+}
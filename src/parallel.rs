@@ -0,0 +1,53 @@
+use crate::index::AsIndex;
+use crate::ComplexMatrix;
+use num::complex::Complex;
+use num_traits::float::Float;
+use rayon::prelude::*;
+
+impl<T: Float + Send + Sync> ComplexMatrix<T> {
+    /// A parallel iterator over the stored ```(row, col, value)``` triplets, in
+    /// insertion order. Duplicated entries are yielded separately.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(3., 4.));
+    /// m.add_element(1, 1, Complex::new(0., 2.));
+    ///
+    /// let frobenius = m.par_iter().map(|(_, _, v)| v.norm_sqr()).sum::<f64>().sqrt();
+    /// assert!((frobenius - 29f64.sqrt()).abs() < 1e-12);
+    ///```
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (usize, usize, &Complex<T>)> {
+        self.rows
+            .par_iter()
+            .zip(self.cols.par_iter())
+            .zip(self.entries.par_iter())
+            .map(|((row, col), value)| (row.index(), col.index(), value))
+    }
+
+    /// A parallel iterator over the stored triplets that allows modifying the values.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 1.));
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    ///
+    /// m.par_iter_mut()
+    ///     .filter(|(row, col, _)| row == col)
+    ///     .for_each(|(_, _, v)| *v *= 2.);
+    /// assert_eq!(m.get(0, 0), Some(&Complex::new(2., 2.)));
+    /// assert_eq!(m.get(0, 1), Some(&Complex::new(2., 0.)));
+    ///```
+    pub fn par_iter_mut(&mut self) -> impl IndexedParallelIterator<Item = (usize, usize, &mut Complex<T>)> {
+        self.rows
+            .par_iter()
+            .zip(self.cols.par_iter())
+            .zip(self.entries.par_iter_mut())
+            .map(|((row, col), value)| (row.index(), col.index(), value))
+    }
+}
@@ -0,0 +1,170 @@
+use crate::index::AsIndex;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::float::Float;
+
+/// A permutation of `0..n`, mapping each original index `i` to its new position
+/// ```permutation[i]```.
+///
+/// The orderings of the crate are returned and accepted as ```Permutation```s, so the
+/// direction of the map is always the same: use ```inverse``` for the other one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    pub(crate) forward: Vec<usize>,
+}
+
+impl Permutation {
+    /// The identity permutation of `0..n`.
+    ///```rust
+    /// use sparse_complex::Permutation;
+    /// assert_eq!(Permutation::identity(3).as_slice(), &[0, 1, 2]);
+    ///```
+    pub fn identity(n: usize) -> Self {
+        Permutation {
+            forward: (0..n).collect(),
+        }
+    }
+
+    /// Create the permutation moving index `i` to ```forward[i]```.
+    ///
+    /// Returns `Err` if ```forward``` is not a permutation of `0..forward.len()`.
+    ///```rust
+    /// use sparse_complex::Permutation;
+    ///
+    /// assert!(Permutation::from_vec(vec![2, 0, 1]).is_ok());
+    /// assert!(Permutation::from_vec(vec![0, 0, 1]).is_err());
+    /// assert!(Permutation::from_vec(vec![0, 3]).is_err());
+    ///```
+    pub fn from_vec(forward: Vec<usize>) -> Result<Self, SolveError> {
+        let mut seen = vec![false; forward.len()];
+        for p in &forward {
+            if *p >= forward.len() || seen[*p] {
+                return Err(SolveError::InvalidInput(
+                    "the indices are not a permutation",
+                ));
+            }
+            seen[*p] = true;
+        }
+        Ok(Permutation { forward })
+    }
+
+    /// The number of permuted indices.
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    /// Returns `true` if the permutation is of the empty set.
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    /// The new position of each original index.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.forward
+    }
+
+    /// The new position of the original index ```i```.
+    ///
+    /// Panics if ```i``` is out of bounds.
+    pub fn get(&self, i: usize) -> usize {
+        self.forward[i]
+    }
+
+    /// The inverse permutation, mapping each new position back to its original index.
+    ///```rust
+    /// use sparse_complex::Permutation;
+    ///
+    /// let p = Permutation::from_vec(vec![2, 0, 1]).unwrap();
+    /// assert_eq!(p.inverse().as_slice(), &[1, 2, 0]);
+    /// assert_eq!(p.then(&p.inverse()), Permutation::identity(3));
+    ///```
+    pub fn inverse(&self) -> Permutation {
+        let mut inverse = vec![0; self.forward.len()];
+        for (i, p) in self.forward.iter().enumerate() {
+            inverse[*p] = i;
+        }
+        Permutation { forward: inverse }
+    }
+
+    /// The permutation applying ```self``` first and then ```next```, i.e. mapping `i`
+    /// to ```next[self[i]]```.
+    ///
+    /// Panics if the permutations have different lengths.
+    pub fn then(&self, next: &Permutation) -> Permutation {
+        assert_eq!(
+            self.len(),
+            next.len(),
+            "the permutations have different lengths"
+        );
+        Permutation {
+            forward: self.forward.iter().map(|p| next.forward[*p]).collect(),
+        }
+    }
+
+    /// Move each element ```x[i]``` to position ```self[i]```.
+    ///
+    /// Panics if ```x``` does not have the length of the permutation.
+    ///```rust
+    /// use sparse_complex::Permutation;
+    ///
+    /// let p = Permutation::from_vec(vec![2, 0, 1]).unwrap();
+    /// let y = p.apply(&['a', 'b', 'c']);
+    /// assert_eq!(y, vec!['b', 'c', 'a']);
+    /// assert_eq!(p.apply_inverse(&y), vec!['a', 'b', 'c']);
+    ///```
+    pub fn apply<V: Clone>(&self, x: &[V]) -> Vec<V> {
+        assert_eq!(
+            x.len(),
+            self.len(),
+            "the vector does not match the permutation"
+        );
+        let mut y = x.to_vec();
+        for (xi, p) in x.iter().zip(self.forward.iter()) {
+            y[*p] = xi.clone();
+        }
+        y
+    }
+
+    /// Undo ```apply```: element ```x[i]``` is taken from position ```self[i]``` of ```y```.
+    ///
+    /// Panics if ```y``` does not have the length of the permutation.
+    pub fn apply_inverse<V: Clone>(&self, y: &[V]) -> Vec<V> {
+        assert_eq!(
+            y.len(),
+            self.len(),
+            "the vector does not match the permutation"
+        );
+        self.forward.iter().map(|p| y[*p].clone()).collect()
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// The matrix with its rows and columns permuted: element ```(i, j)``` moves to
+    /// ```(rows[i], cols[j])```.
+    ///
+    /// Panics if an index of the matrix is out of bounds of its permutation.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Permutation};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    ///
+    /// let p = Permutation::from_vec(vec![1, 0]).unwrap();
+    /// let permuted = m.permute(&p, &p);
+    /// assert_eq!(permuted.get(1, 0), Some(&Complex::new(1., 0.)));
+    ///```
+    pub fn permute(&self, rows: &Permutation, cols: &Permutation) -> ComplexMatrix<T> {
+        let mut m = ComplexMatrix::with_capacity(self.entries.len());
+        for ((row, col), value) in self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter())
+        {
+            m.add_element(rows.get(row.index()), cols.get(col.index()), *value);
+        }
+        m
+    }
+}
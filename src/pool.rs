@@ -0,0 +1,114 @@
+use crate::{SolvableFloat, SolveError, SolveWorkspace};
+use num_traits::float::Float;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+/// A pool of reusable ```SolveWorkspace```s shared between threads.
+///
+/// Each thread checks a workspace out, solves with it and returns it when the
+/// ```PooledWorkspace``` guard is dropped, so the Eigen solver objects are built once
+/// per concurrently solving thread instead of once per solve. A thread gets back the
+/// workspace it returned last when possible, which keeps its cached sparsity pattern.
+pub struct SolverPool<T: Float> {
+    idle: Mutex<Vec<(ThreadId, SolveWorkspace<T>)>>,
+}
+
+impl<T: SolvableFloat> SolverPool<T> {
+    /// Create an empty pool. Workspaces are created on demand.
+    ///```rust
+    /// use sparse_complex::SolverPool;
+    /// let pool = SolverPool::<f64>::new();
+    ///```
+    pub fn new() -> Self {
+        SolverPool {
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Check a workspace out of the pool, creating one if none is idle.
+    ///
+    /// The workspace returns to the pool when the guard is dropped. Returns `Err` if a
+    /// new workspace cannot be allocated, see ```SolveWorkspace::new```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SolverPool};
+    /// use num::Complex;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let pool = Arc::new(SolverPool::<f64>::new());
+    /// let handles = (1..5)
+    ///     .map(|k| {
+    ///         let pool = Arc::clone(&pool);
+    ///         thread::spawn(move || {
+    ///             let mut m = ComplexMatrix::<f64>::new();
+    ///             m.add_element(0, 0, Complex::new(k as f64, 0.));
+    ///             let mut b = vec![Complex::new(k as f64, 0.)];
+    ///             m.solve_in_place(&mut pool.checkout().unwrap(), &mut b).unwrap();
+    ///             b[0]
+    ///         })
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// for handle in handles {
+    ///     assert_eq!(handle.join().unwrap(), Complex::new(1., 0.));
+    /// }
+    ///```
+    pub fn checkout(&self) -> Result<PooledWorkspace<'_, T>, SolveError> {
+        let id = thread::current().id();
+        let workspace = {
+            let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+            match idle.iter().rposition(|(owner, _)| *owner == id) {
+                Some(position) => Some(idle.swap_remove(position).1),
+                None => idle.pop().map(|(_, workspace)| workspace),
+            }
+        };
+        let workspace = match workspace {
+            Some(workspace) => workspace,
+            None => SolveWorkspace::new()?,
+        };
+        Ok(PooledWorkspace {
+            pool: self,
+            workspace: Some(workspace),
+        })
+    }
+
+    /// The number of workspaces currently waiting in the pool.
+    pub fn idle(&self) -> usize {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+impl<T: SolvableFloat> Default for SolverPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A ```SolveWorkspace``` checked out of a ```SolverPool```, returned to it on drop.
+pub struct PooledWorkspace<'a, T: Float> {
+    pool: &'a SolverPool<T>,
+    workspace: Option<SolveWorkspace<T>>,
+}
+
+impl<'a, T: Float> Deref for PooledWorkspace<'a, T> {
+    type Target = SolveWorkspace<T>;
+
+    fn deref(&self) -> &SolveWorkspace<T> {
+        self.workspace.as_ref().unwrap()
+    }
+}
+
+impl<'a, T: Float> DerefMut for PooledWorkspace<'a, T> {
+    fn deref_mut(&mut self) -> &mut SolveWorkspace<T> {
+        self.workspace.as_mut().unwrap()
+    }
+}
+
+impl<'a, T: Float> Drop for PooledWorkspace<'a, T> {
+    fn drop(&mut self) {
+        if let Some(workspace) = self.workspace.take() {
+            let mut idle = self.pool.idle.lock().unwrap_or_else(|e| e.into_inner());
+            idle.push((thread::current().id(), workspace));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::compressed::compress;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::progress::{self, Phase};
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::solver;
+use crate::ComplexMatrix;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::{SolvableFloat, SolveError};
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Computes `Aᵏx` by ```k``` sparse matrix-vector products, without forming `Aᵏ`.
+    ///
+    /// Panics if the matrix has an index beyond the length of ```x```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    ///
+    /// let x = vec![Complex::new(1., 0.), Complex::new(0., 0.)];
+    /// assert_eq!(m.pow_apply(2, &x), vec![Complex::new(-1., 0.), Complex::new(0., 0.)]);
+    /// assert_eq!(m.pow_apply(0, &x), x);
+    ///```
+    pub fn pow_apply(&self, k: usize, x: &[Complex<T>]) -> Vec<Complex<T>> {
+        assert!(self.dimension() <= x.len(), "the matrix is larger than the vector");
+        let csr = compress(x.len(), &self.rows, &self.cols, &self.entries);
+        (0..k).fold(x.to_vec(), |y, _| csr.mul_vec(&y))
+    }
+}
+
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Computes `A⁻ᵏx` by ```k``` solves with a single LU factorization.
+    ///
+    /// Returns `Err` if the factorization fails.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let x = vec![Complex::new(4., 0.), Complex::new(1., 0.)];
+    /// let y = m.inverse_pow_apply(2, &x).unwrap();
+    /// assert_eq!(y, vec![Complex::new(1., 0.), Complex::new(-1., 0.)]);
+    ///```
+    pub fn inverse_pow_apply(&self, k: usize, x: &[Complex<T>]) -> Result<Vec<Complex<T>>, SolveError> {
+        let mut y = x.to_vec();
+        if k == 0 {
+            return Ok(y);
+        }
+        self.check_rhs(y.len())?;
+        progress::report(Phase::Factorization, Some(0.));
+        self.debug_validate(y.len());
+        let mut context = T::new_context()?;
+        let info = unsafe { T::context_factorize(&mut context, self, y.len()) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Factorization, Some(1.));
+        for i in 0..k {
+            let info = unsafe { T::context_solve_factorized(&mut context, &mut y) };
+            solver::info_to_result(info)?;
+            progress::report(Phase::Solve, Some((i + 1) as f64 / k as f64));
+        }
+        Ok(y)
+    }
+}
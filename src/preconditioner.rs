@@ -0,0 +1,486 @@
+use crate::compressed::{compress, Csr};
+use crate::krylov::least_squares;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::solver;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use std::marker::PhantomData;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use std::os::raw::c_int;
+
+/// A preconditioner `M ≈ A` for the iterative solvers.
+///
+/// Implement this trait to plug problem-specific preconditioners (e.g. FFT-based or
+/// multigrid) into the iterative solvers.
+pub trait Preconditioner<T: Float> {
+    /// Compute `z = M⁻¹ r`.
+    fn apply(&self, r: &[Complex<T>], z: &mut [Complex<T>]);
+
+    /// Compute `z = M⁻ᴴ r`, needed by solvers working with the adjoint system.
+    ///
+    /// Returns `Err` by default, for preconditioners that cannot apply their adjoint.
+    fn apply_adjoint(&self, r: &[Complex<T>], z: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let _ = (r, z);
+        Err(SolveError::Unsupported("the preconditioner does not support the adjoint application"))
+    }
+}
+
+/// The identity preconditioner, `M = I`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IdentityPreconditioner;
+
+impl<T: Float> Preconditioner<T> for IdentityPreconditioner {
+    fn apply(&self, r: &[Complex<T>], z: &mut [Complex<T>]) {
+        z.copy_from_slice(r);
+    }
+
+    fn apply_adjoint(&self, r: &[Complex<T>], z: &mut [Complex<T>]) -> Result<(), SolveError> {
+        z.copy_from_slice(r);
+        Ok(())
+    }
+}
+
+/// The Jacobi (diagonal) preconditioner, `M = diag(A)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jacobi<T: Float> {
+    inverse_diagonal: Vec<Complex<T>>,
+}
+
+impl<T: Float> Jacobi<T> {
+    /// Build the preconditioner from the diagonal of ```matrix```.
+    ///
+    /// Returns `Err` if a diagonal entry is zero.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Jacobi, Preconditioner};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 4.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    ///
+    /// let jacobi = Jacobi::new(&m).unwrap();
+    /// let mut z = vec![Complex::new(0., 0.); 2];
+    /// jacobi.apply(&[Complex::new(1., 0.), Complex::new(1., 0.)], &mut z);
+    /// assert_eq!(z, vec![Complex::new(0.5, 0.), Complex::new(0., -0.25)]);
+    ///```
+    pub fn new(matrix: &ComplexMatrix<T>) -> Result<Self, SolveError> {
+        let csr = matrix.to_csr();
+        let inverse_diagonal = (0..csr.n)
+            .map(|i| {
+                csr.row(i)
+                    .find(|(j, _)| *j == i)
+                    .map(|(_, v)| *v)
+                    .filter(|v| v.norm() > T::zero())
+                    .map(|v| v.inv())
+                    .ok_or(SolveError::Singular)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Jacobi { inverse_diagonal })
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Jacobi<T> {
+    fn apply(&self, r: &[Complex<T>], z: &mut [Complex<T>]) {
+        for ((zi, ri), d) in z.iter_mut().zip(r.iter()).zip(self.inverse_diagonal.iter()) {
+            *zi = *ri * *d;
+        }
+    }
+
+    fn apply_adjoint(&self, r: &[Complex<T>], z: &mut [Complex<T>]) -> Result<(), SolveError> {
+        for ((zi, ri), d) in z.iter_mut().zip(r.iter()).zip(self.inverse_diagonal.iter()) {
+            *zi = *ri * d.conj();
+        }
+        Ok(())
+    }
+}
+
+/// Incomplete LU factorization with zero fill-in, `M = LU` restricted to the pattern of `A`.
+pub struct Ilu0<T: Float> {
+    factors: Csr<T>,
+    diagonal: Vec<usize>,
+}
+
+impl<T: Float> Ilu0<T> {
+    /// Compute the incomplete factorization of ```matrix```.
+    ///
+    /// Returns `Err` if a pivot is zero or a diagonal entry is missing from the pattern.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Ilu0, Preconditioner};
+    /// use num::Complex;
+    ///
+    /// // For a tridiagonal matrix ILU(0) is the exact LU factorization.
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(-1., 0.));
+    /// m.add_element(1, 0, Complex::new(-1., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 1.));
+    ///
+    /// let ilu = Ilu0::new(&m).unwrap();
+    /// let mut z = vec![Complex::new(0., 0.); 2];
+    /// ilu.apply(&[Complex::new(1., 0.), Complex::new(1., 1.)], &mut z);
+    /// assert!((z[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    /// assert!((z[1] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn new(matrix: &ComplexMatrix<T>) -> Result<Self, SolveError> {
+        let mut factors = matrix.to_csr();
+        let n = factors.n;
+        let mut diagonal = Vec::with_capacity(n);
+        for i in 0..n {
+            let range = factors.indptr[i]..factors.indptr[i + 1];
+            let position = factors.indices[range.clone()]
+                .binary_search(&i)
+                .map_err(|_| SolveError::Singular)?;
+            diagonal.push(range.start + position);
+        }
+
+        // IKJ variant on the pattern of A: row i is updated by the previous rows only
+        // where the pattern of row i already has an entry.
+        let mut position = vec![usize::MAX; n];
+        for i in 0..n {
+            let (start, end) = (factors.indptr[i], factors.indptr[i + 1]);
+            for p in start..end {
+                position[factors.indices[p]] = p;
+            }
+            for p in start..diagonal[i] {
+                let k = factors.indices[p];
+                let pivot = factors.values[diagonal[k]];
+                if pivot.norm() == T::zero() {
+                    return Err(SolveError::Singular);
+                }
+                let lik = factors.values[p] / pivot;
+                factors.values[p] = lik;
+                for q in diagonal[k] + 1..factors.indptr[k + 1] {
+                    let target = position[factors.indices[q]];
+                    if target != usize::MAX {
+                        factors.values[target] = factors.values[target] - lik * factors.values[q];
+                    }
+                }
+            }
+            for p in start..end {
+                position[factors.indices[p]] = usize::MAX;
+            }
+            if factors.values[diagonal[i]].norm() == T::zero() {
+                return Err(SolveError::Singular);
+            }
+        }
+
+        Ok(Ilu0 { factors, diagonal })
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Ilu0<T> {
+    fn apply(&self, r: &[Complex<T>], z: &mut [Complex<T>]) {
+        let f = &self.factors;
+        z.copy_from_slice(r);
+        for i in 0..f.n {
+            let mut sum = z[i];
+            for p in f.indptr[i]..self.diagonal[i] {
+                sum = sum - f.values[p] * z[f.indices[p]];
+            }
+            z[i] = sum;
+        }
+        for i in (0..f.n).rev() {
+            let mut sum = z[i];
+            for p in self.diagonal[i] + 1..f.indptr[i + 1] {
+                sum = sum - f.values[p] * z[f.indices[p]];
+            }
+            z[i] = sum / f.values[self.diagonal[i]];
+        }
+    }
+
+    fn apply_adjoint(&self, r: &[Complex<T>], z: &mut [Complex<T>]) -> Result<(), SolveError> {
+        // (LU)ᴴ = UᴴLᴴ: the rows of U and L are the columns of their adjoints, so both
+        // substitutions are column oriented.
+        let f = &self.factors;
+        z.copy_from_slice(r);
+        for i in 0..f.n {
+            z[i] = z[i] / f.values[self.diagonal[i]].conj();
+            let zi = z[i];
+            for p in self.diagonal[i] + 1..f.indptr[i + 1] {
+                z[f.indices[p]] = z[f.indices[p]] - f.values[p].conj() * zi;
+            }
+        }
+        for i in (0..f.n).rev() {
+            let zi = z[i];
+            for p in f.indptr[i]..self.diagonal[i] {
+                z[f.indices[p]] = z[f.indices[p]] - f.values[p].conj() * zi;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Incomplete LU factorization with threshold dropping (ILUT) computed by
+/// [Eigen::IncompleteLUT](https://eigen.tuxfamily.org/dox/classEigen_1_1IncompleteLUT.html).
+///
+/// Unlike ```Ilu0```, it keeps the largest fill-in entries, so it stays effective on
+/// matrices whose pattern is far from their LU pattern, at the cost of more memory.
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+pub struct IncompleteLut<T: Float> {
+    context: solver::Context,
+    size: usize,
+    marker: PhantomData<T>,
+}
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl IncompleteLut<f64> {
+    /// Compute the incomplete factorization of ```matrix```, dropping the entries
+    /// smaller than ```drop_tolerance``` times the norm of their row and keeping at
+    /// most ```fill_factor``` times the entries of each row of `A`.
+    ///
+    /// Returns `Err` if the factorization fails.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IncompleteLut, Preconditioner};
+    /// use num::Complex;
+    ///
+    /// // Without dropping, the incomplete factorization of a small matrix is exact.
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let ilut = IncompleteLut::new(&m, 0., 10).unwrap();
+    /// let mut z = vec![Complex::new(0., 0.); 2];
+    /// ilut.apply(&[Complex::new(3., 0.), Complex::new(3., 0.)], &mut z);
+    /// assert!((z[0] - Complex::new(1., 0.)).norm() < 1e-5);
+    /// assert!((z[1] - Complex::new(1., 0.)).norm() < 1e-5);
+    ///```
+    pub fn new(matrix: &ComplexMatrix<f64>, drop_tolerance: f64, fill_factor: usize) -> Result<Self, SolveError> {
+        let size = matrix.dimension();
+        let mut info = 0;
+        let ptr = unsafe {
+            solver::ilut_new_cpp(
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                size,
+                drop_tolerance,
+                fill_factor.min(c_int::MAX as usize) as c_int,
+                &mut info,
+            )
+        };
+        let context = unsafe { solver::Context::from_raw(ptr, solver::ilut_free_cpp) };
+        solver::info_to_result(info)?;
+        Ok(IncompleteLut {
+            context,
+            size,
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f64"))]
+impl Preconditioner<f64> for IncompleteLut<f64> {
+    fn apply(&self, r: &[Complex<f64>], z: &mut [Complex<f64>]) {
+        assert!(
+            r.len() == self.size && z.len() == self.size,
+            "the vectors do not match the size of the preconditioner"
+        );
+        unsafe { solver::ilut_solve_cpp(self.context.as_const_ptr(), r.as_ptr(), z.as_mut_ptr(), self.size) }
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl IncompleteLut<f32> {
+    /// Compute the incomplete factorization of ```matrix```, dropping the entries
+    /// smaller than ```drop_tolerance``` times the norm of their row and keeping at
+    /// most ```fill_factor``` times the entries of each row of `A`.
+    ///
+    /// Returns `Err` if the factorization fails.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IncompleteLut, Preconditioner};
+    /// use num::Complex;
+    ///
+    /// // Without dropping, the incomplete factorization of a small matrix is exact.
+    /// let mut m = ComplexMatrix::<f32>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let ilut = IncompleteLut::new(&m, 0., 10).unwrap();
+    /// let mut z = vec![Complex::new(0., 0.); 2];
+    /// ilut.apply(&[Complex::new(3., 0.), Complex::new(3., 0.)], &mut z);
+    /// assert!((z[0] - Complex::new(1., 0.)).norm() < 1e-5);
+    /// assert!((z[1] - Complex::new(1., 0.)).norm() < 1e-5);
+    ///```
+    pub fn new(matrix: &ComplexMatrix<f32>, drop_tolerance: f32, fill_factor: usize) -> Result<Self, SolveError> {
+        let size = matrix.dimension();
+        let mut info = 0;
+        let ptr = unsafe {
+            solver::ilut_new_cpp32(
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                size,
+                drop_tolerance,
+                fill_factor.min(c_int::MAX as usize) as c_int,
+                &mut info,
+            )
+        };
+        let context = unsafe { solver::Context::from_raw(ptr, solver::ilut_free_cpp32) };
+        solver::info_to_result(info)?;
+        Ok(IncompleteLut {
+            context,
+            size,
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(all(feature = "eigen", feature = "f32"))]
+impl Preconditioner<f32> for IncompleteLut<f32> {
+    fn apply(&self, r: &[Complex<f32>], z: &mut [Complex<f32>]) {
+        assert!(
+            r.len() == self.size && z.len() == self.size,
+            "the vectors do not match the size of the preconditioner"
+        );
+        unsafe { solver::ilut_solve_cpp32(self.context.as_const_ptr(), r.as_ptr(), z.as_mut_ptr(), self.size) }
+    }
+}
+
+/// Sparse approximate inverse, `M⁻¹ ≈ A⁻¹` restricted to a target pattern.
+///
+/// Each column `mⱼ` minimizes `‖Amⱼ - eⱼ‖` over the entries allowed by the pattern,
+/// independently of the others, so the construction runs over ```num_threads()```
+/// threads when the `rayon` feature is enabled. Unlike ```Ilu0``` it has no pivots to
+/// break down on highly indefinite systems, and applying it is a single product.
+pub struct Spai<T: Float> {
+    /// Row ```j``` holds column ```j``` of the approximate inverse.
+    columns: Csr<T>,
+}
+
+impl<T: Float + Send + Sync> Spai<T> {
+    /// Compute the approximate inverse of ```matrix``` on its own pattern.
+    ///
+    /// Returns `Err` if a column of the matrix restricted to the pattern is rank
+    /// deficient, e.g. if the matrix has an empty column.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Preconditioner, Spai};
+    /// use num::Complex;
+    ///
+    /// // The inverse of a diagonal matrix is on its pattern, so it is found exactly.
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 4.));
+    ///
+    /// let spai = Spai::new(&m).unwrap();
+    /// let mut z = vec![Complex::new(0., 0.); 2];
+    /// spai.apply(&[Complex::new(1., 0.), Complex::new(1., 0.)], &mut z);
+    /// assert!((z[0] - Complex::new(0.5, 0.)).norm() < 1e-12);
+    /// assert!((z[1] - Complex::new(0., -0.25)).norm() < 1e-12);
+    ///```
+    pub fn new(matrix: &ComplexMatrix<T>) -> Result<Self, SolveError> {
+        Self::with_pattern(matrix, matrix)
+    }
+
+    /// Compute the approximate inverse of ```matrix``` on the positions stored in
+    /// ```pattern```, whose values are ignored. A wider pattern, e.g. that of `A²`,
+    /// gives a better but more expensive approximation.
+    ///
+    /// Returns `Err` if a column of the matrix restricted to the pattern is rank
+    /// deficient.
+    pub fn with_pattern(matrix: &ComplexMatrix<T>, pattern: &ComplexMatrix<T>) -> Result<Self, SolveError> {
+        let n = matrix.dimension().max(pattern.dimension());
+        // Row k of the transposed compressions is column k of the matrices.
+        let a = compress(n, &matrix.cols, &matrix.rows, &matrix.entries);
+        let pattern = compress(n, &pattern.cols, &pattern.rows, &pattern.entries);
+        let fits = fit_columns(n, |j| fit_column(&a, &pattern, j))?;
+
+        let mut columns = Csr {
+            n,
+            indptr: Vec::with_capacity(n + 1),
+            indices: Vec::new(),
+            values: Vec::new(),
+        };
+        columns.indptr.push(0);
+        for (indices, values) in fits {
+            columns.indices.extend(indices);
+            columns.values.extend(values);
+            columns.indptr.push(columns.indices.len());
+        }
+        Ok(Spai { columns })
+    }
+
+    /// The number of stored entries of the approximate inverse.
+    pub fn nnz(&self) -> usize {
+        self.columns.values.len()
+    }
+}
+
+impl<T: Float> Preconditioner<T> for Spai<T> {
+    fn apply(&self, r: &[Complex<T>], z: &mut [Complex<T>]) {
+        z.iter_mut().for_each(|v| *v = Complex::new(T::zero(), T::zero()));
+        for (j, rj) in r.iter().enumerate().take(self.columns.n) {
+            for (i, v) in self.columns.row(j) {
+                z[i] = z[i] + *v * *rj;
+            }
+        }
+    }
+
+    fn apply_adjoint(&self, r: &[Complex<T>], z: &mut [Complex<T>]) -> Result<(), SolveError> {
+        for (j, zj) in z.iter_mut().enumerate().take(self.columns.n) {
+            *zj = self
+                .columns
+                .row(j)
+                .fold(Complex::new(T::zero(), T::zero()), |acc, (i, v)| acc + v.conj() * r[i]);
+        }
+        Ok(())
+    }
+}
+
+/// The rows and values of a column of the approximate inverse.
+type SparseColumn<T> = (Vec<usize>, Vec<Complex<T>>);
+
+/// Fits column ```j``` of the approximate inverse: `min ‖A(:, J) m - eⱼ‖` over the
+/// rows `I` reached by the columns `J` allowed by the pattern.
+fn fit_column<T: Float>(a: &Csr<T>, pattern: &Csr<T>, j: usize) -> Result<SparseColumn<T>, SolveError> {
+    let allowed = pattern.row(j).map(|(k, _)| k).collect::<Vec<_>>();
+    let mut reached = allowed.iter().flat_map(|k| a.row(*k).map(|(i, _)| i)).collect::<Vec<_>>();
+    reached.push(j);
+    reached.sort_unstable();
+    reached.dedup();
+
+    let (rows, cols) = (reached.len(), allowed.len());
+    let zero = Complex::new(T::zero(), T::zero());
+    let mut g = vec![zero; rows * cols];
+    for (c, k) in allowed.iter().enumerate() {
+        for (i, v) in a.row(*k) {
+            let r = reached.binary_search(&i).unwrap();
+            g[r * cols + c] = *v;
+        }
+    }
+    let mut e = vec![zero; rows];
+    e[reached.binary_search(&j).unwrap()] = Complex::new(T::one(), T::zero());
+    let values = least_squares(rows, cols, &g, &e).ok_or(SolveError::Singular)?;
+    Ok((allowed, values))
+}
+
+/// Fits the ```n``` columns over ```num_threads()``` threads.
+#[cfg(feature = "rayon")]
+fn fit_columns<T, F>(n: usize, fit: F) -> Result<Vec<SparseColumn<T>>, SolveError>
+where
+    T: Float + Send,
+    F: Fn(usize) -> Result<SparseColumn<T>, SolveError> + Sync,
+{
+    use rayon::prelude::*;
+    crate::threads::run(|| (0..n).into_par_iter().map(&fit).collect())
+}
+
+/// Fits the ```n``` columns in turn.
+#[cfg(not(feature = "rayon"))]
+fn fit_columns<T, F>(n: usize, fit: F) -> Result<Vec<SparseColumn<T>>, SolveError>
+where
+    T: Float,
+    F: Fn(usize) -> Result<SparseColumn<T>, SolveError>,
+{
+    (0..n).map(fit).collect()
+}
@@ -0,0 +1,133 @@
+use core::sync::atomic::AtomicUsize;
+#[cfg(feature = "std")]
+use core::sync::atomic::Ordering;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// The stage of a long-running operation reported to a progress callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Conversion of the triplets into a compressed form.
+    Assembly,
+    /// LU factorization of the matrix.
+    Factorization,
+    /// Forward and back substitution with the factors.
+    Solve,
+    /// Solution of a batch of independent systems.
+    Batch,
+}
+
+#[cfg(feature = "std")]
+pub(crate) type Callback = Arc<dyn Fn(Phase, Option<f64>) + Send + Sync>;
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CALLBACK: RefCell<Option<Callback>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "std")]
+/// Runs ```f``` reporting the progress of its operations to ```callback```.
+///
+/// The callback receives the current phase and the completed fraction of that
+/// phase, or None when it cannot be estimated. It may be called from the worker
+/// threads of the parallel features, so it must be `Send + Sync`.
+///
+/// The pure-Rust factorization reports its fraction as the columns are eliminated,
+/// at most once per percent. The Eigen factorization runs as a single call, so it
+/// only reports its start and its end.
+///```rust
+/// use sparse_complex::{with_progress, ComplexMatrix, Phase};
+/// use num::Complex;
+/// use std::sync::{Arc, Mutex};
+///
+/// let mut m = ComplexMatrix::<f64>::new();
+/// m.add_element(0, 0, Complex::new(1., 0.));
+///
+/// let phases = Arc::new(Mutex::new(Vec::new()));
+/// let log = Arc::clone(&phases);
+/// with_progress(move |phase, _| log.lock().unwrap().push(phase), || m.to_compressed());
+/// assert_eq!(phases.lock().unwrap().last(), Some(&Phase::Assembly));
+///```
+pub fn with_progress<R, P, F>(callback: P, f: F) -> R
+where
+    P: Fn(Phase, Option<f64>) + Send + Sync + 'static,
+    F: FnOnce() -> R,
+{
+    scoped(Some(Arc::new(callback)), f)
+}
+
+#[cfg(feature = "std")]
+/// The callback of the enclosing ```with_progress```, if any.
+pub(crate) fn current() -> Option<Callback> {
+    CALLBACK.with(|c| c.borrow().clone())
+}
+
+#[cfg(feature = "std")]
+/// Runs ```f``` with ```callback``` as the current callback of this thread.
+pub(crate) fn scoped<R, F: FnOnce() -> R>(callback: Option<Callback>, f: F) -> R {
+    let _guard = CallbackGuard(CALLBACK.with(|c| c.replace(callback)));
+    f()
+}
+
+/// Reports ```phase``` to the current callback, if any.
+pub(crate) fn report(phase: Phase, fraction: Option<f64>) {
+    #[cfg(feature = "std")]
+    if let Some(callback) = current() {
+        callback(phase, fraction);
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = (phase, fraction);
+}
+
+/// Counts the completed items of a phase, reporting at most once per percent.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) struct Counter {
+    #[cfg(feature = "std")]
+    callback: Option<Callback>,
+    phase: Phase,
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl Counter {
+    pub(crate) fn new(phase: Phase, total: usize) -> Self {
+        #[cfg(feature = "std")]
+        let callback = current();
+        #[cfg(feature = "std")]
+        if let Some(callback) = &callback {
+            callback(phase, Some(0.));
+        }
+        Counter {
+            #[cfg(feature = "std")]
+            callback,
+            phase,
+            total,
+            done: AtomicUsize::new(0),
+        }
+    }
+
+    /// Marks one more item as completed. Safe to call from several threads.
+    pub(crate) fn tick(&self) {
+        #[cfg(feature = "std")]
+        if let Some(callback) = &self.callback {
+            let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+            if done * 100 / self.total != (done - 1) * 100 / self.total {
+                callback(self.phase, Some(done as f64 / self.total as f64));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Restores the previous callback when dropped, even on panic.
+struct CallbackGuard(Option<Callback>);
+
+#[cfg(feature = "std")]
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CALLBACK.with(|c| *c.borrow_mut() = previous);
+    }
+}
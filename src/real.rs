@@ -0,0 +1,161 @@
+use crate::compressed::compress;
+use crate::index::{to_storage, AsIndex, StorageIndex};
+use crate::ComplexMatrix;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A real sparse matrix in triplet form, such as a part of a ```ComplexMatrix```.
+///
+/// Like ```ComplexMatrix```, duplicated entries stand for their sum.
+#[derive(Clone, PartialEq)]
+pub struct RealMatrix<T: Float> {
+    entries: Vec<T>,
+    rows: Vec<StorageIndex>,
+    cols: Vec<StorageIndex>,
+}
+
+impl<T: Float> RealMatrix<T> {
+    /// Create a new, initially empty ```RealMatrix```
+    ///```rust
+    /// use sparse_complex::RealMatrix;
+    /// let m = RealMatrix::<f64>::new();
+    ///```
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create a new, initially empty ```RealMatrix``` with a given capacity
+    ///```rust
+    /// use sparse_complex::RealMatrix;
+    /// let m = RealMatrix::<f64>::with_capacity(5);
+    ///```
+    pub fn with_capacity(capacity: usize) -> Self {
+        RealMatrix {
+            entries: Vec::with_capacity(capacity),
+            rows: Vec::with_capacity(capacity),
+            cols: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add an element at location ```(row, col)```.
+    ///```rust
+    /// use sparse_complex::RealMatrix;
+    /// let mut m = RealMatrix::<f64>::new();
+    /// m.add_element(0, 1, 2.5);
+    /// assert_eq!(m.get(0, 1), Some(&2.5));
+    ///```
+    pub fn add_element(&mut self, row: usize, col: usize, value: T) {
+        self.entries.push(value);
+        self.rows.push(to_storage(row));
+        self.cols.push(to_storage(col));
+    }
+
+    /// Returns the element at ```(row, col)``` if present, or None if not.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.iter()
+            .find(|&(r, c, _)| r == row && c == col)
+            .map(|(_, _, v)| v)
+    }
+
+    /// Iterate over the stored ```(row, col, value)``` triplets in insertion order.
+    ///```rust
+    /// use sparse_complex::RealMatrix;
+    /// let mut m = RealMatrix::<f64>::new();
+    /// m.add_element(0, 0, 1.);
+    /// m.add_element(1, 0, -2.);
+    /// assert_eq!(m.iter().map(|(_, _, v)| v.abs()).sum::<f64>(), 3.);
+    ///```
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter())
+            .map(|((row, col), value)| (row.index(), col.index(), value))
+    }
+
+    /// Convert into a ```ComplexMatrix``` with zero imaginary parts.
+    pub fn to_complex_matrix(&self) -> ComplexMatrix<T> {
+        let mut m = ComplexMatrix::with_capacity(self.entries.len());
+        for (row, col, value) in self.iter() {
+            m.add_element(row, col, Complex::new(*value, T::zero()));
+        }
+        m
+    }
+}
+
+impl<T: Float> Default for RealMatrix<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// The real parts of the stored elements, e.g. the conductance of an admittance matrix.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut y = ComplexMatrix::<f64>::new();
+    /// y.add_element(0, 0, Complex::new(2., -5.));
+    /// assert_eq!(y.real_part().get(0, 0), Some(&2.));
+    ///```
+    pub fn real_part(&self) -> RealMatrix<T> {
+        self.map_to_real(|v| v.re)
+    }
+
+    /// The imaginary parts of the stored elements, e.g. the susceptance of an admittance matrix.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut y = ComplexMatrix::<f64>::new();
+    /// y.add_element(0, 0, Complex::new(2., -5.));
+    /// assert_eq!(y.imag_part().get(0, 0), Some(&-5.));
+    ///```
+    pub fn imag_part(&self) -> RealMatrix<T> {
+        self.map_to_real(|v| v.im)
+    }
+
+    /// The absolute values of the elements, with duplicated entries summed first.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(3., 0.));
+    /// m.add_element(0, 0, Complex::new(0., 4.));
+    /// assert_eq!(m.magnitude_matrix().get(0, 0), Some(&5.));
+    ///```
+    pub fn magnitude_matrix(&self) -> RealMatrix<T> {
+        let csr = compress(self.dimension(), &self.rows, &self.cols, &self.entries);
+        let mut m = RealMatrix::with_capacity(csr.values.len());
+        for row in 0..csr.n {
+            for (col, value) in csr.row(row) {
+                m.add_element(row, col, value.norm());
+            }
+        }
+        m
+    }
+
+    fn map_to_real<F: Fn(&Complex<T>) -> T>(&self, f: F) -> RealMatrix<T> {
+        RealMatrix {
+            entries: self.entries.iter().map(f).collect(),
+            rows: self.rows.clone(),
+            cols: self.cols.clone(),
+        }
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Debug for RealMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut msg = String::from("RealMatrix { \n");
+        for (row, col, value) in self.iter() {
+            msg = format!("{}  ({},{}) -> {}\n", msg, row, col, value)
+        }
+        write!(f, "{}}}", msg)
+    }
+}
@@ -0,0 +1,330 @@
+use crate::compressed::compress;
+use crate::dense::{eigenvalues, eigenvector, DenseLu};
+use crate::krylov::{axpy, dot, least_squares, norm, orthonormalize, times_inverse_upper};
+use crate::{ComplexMatrix, Preconditioner, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A restarted GMRES solver recycling a deflation subspace across related solves
+/// (GCRO-DR).
+///
+/// At the end of each restart cycle the solver keeps the harmonic Ritz vectors of
+/// the eigenvalues of smallest magnitude, which are the modes that slow GMRES down.
+/// The next solve, typically the next point of a frequency sweep or the next Newton
+/// step, starts by projecting them out instead of rebuilding them from scratch, so
+/// slowly varying sequences of systems need far fewer iterations than cold-started
+/// GMRES.
+#[derive(Clone, Debug)]
+pub struct RecyclingSolver<T: Float> {
+    restart: usize,
+    recycle: usize,
+    tolerance: T,
+    max_iterations: usize,
+    recycled: Vec<Vec<Complex<T>>>,
+}
+
+impl<T: Float> RecyclingSolver<T> {
+    /// Create a solver building Krylov subspaces of ```restart``` vectors, of which
+    /// ```recycle``` are carried over between cycles and solves.
+    ///
+    /// The default relative tolerance is `√ε` and at most 1000 iterations are run.
+    /// Panics if ```recycle``` is not smaller than ```restart```.
+    ///```rust
+    /// use sparse_complex::RecyclingSolver;
+    /// let solver = RecyclingSolver::<f64>::new(30, 10);
+    ///```
+    pub fn new(restart: usize, recycle: usize) -> Self {
+        assert!(
+            recycle < restart,
+            "the recycled subspace must be smaller than the restart length"
+        );
+        RecyclingSolver {
+            restart,
+            recycle,
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+            recycled: Vec::new(),
+        }
+    }
+
+    /// Sets the tolerance on the residual norm relative to the norm of `b`.
+    pub fn set_tolerance(&mut self, tolerance: T) {
+        self.tolerance = tolerance;
+    }
+
+    /// Sets the maximum number of iterations of a solve.
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    /// The dimension of the subspace currently recycled into the next solve.
+    pub fn recycled_dimension(&self) -> usize {
+        self.recycled.len()
+    }
+
+    /// Forget the recycled subspace, e.g. before an unrelated sequence of systems.
+    pub fn clear(&mut self) {
+        self.recycled.clear();
+    }
+
+    /// Solve `Ax=b` iteratively, starting from ```x``` and reusing the subspace recycled
+    /// by the previous solves. The system is right-preconditioned, so the tolerance
+    /// applies to the true residual.
+    ///
+    /// Returns the number of iterations, i.e. of products with `A`, or `Err` if ```x```
+    /// or ```b``` do not match the matrix size or the solver did not converge. The
+    /// solution is stored in ```x``` and the recycled subspace is kept either way.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, IdentityPreconditioner, RecyclingSolver};
+    /// use num::Complex;
+    ///
+    /// let n = 100;
+    /// let mut solver = RecyclingSolver::<f64>::new(20, 5);
+    /// let b = vec![Complex::new(1., 0.); n];
+    /// let mut iterations = Vec::new();
+    /// for k in 0..3 {
+    ///     // A slowly varying family of shifted Laplacians.
+    ///     let mut m = ComplexMatrix::<f64>::new();
+    ///     for i in 0..n {
+    ///         m.add_element(i, i, Complex::new(2., 1e-3 * k as f64));
+    ///         if i + 1 < n {
+    ///             m.add_element(i, i + 1, Complex::new(-1., 0.));
+    ///             m.add_element(i + 1, i, Complex::new(-1., 0.));
+    ///         }
+    ///     }
+    ///     let mut x = vec![Complex::new(0., 0.); n];
+    ///     iterations.push(solver.solve(&m, &IdentityPreconditioner, &b, &mut x).unwrap());
+    /// }
+    /// assert!(iterations[2] < iterations[0]);
+    ///```
+    pub fn solve<P: Preconditioner<T>>(
+        &mut self,
+        matrix: &ComplexMatrix<T>,
+        preconditioner: &P,
+        b: &[Complex<T>],
+        x: &mut [Complex<T>],
+    ) -> Result<usize, SolveError> {
+        let n = b.len();
+        if x.len() != n || matrix.dimension() > n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let a = compress(n, &matrix.rows, &matrix.cols, &matrix.entries);
+        let zero = Complex::new(T::zero(), T::zero());
+        let precondition = |v: &[Complex<T>]| {
+            let mut z = vec![zero; n];
+            preconditioner.apply(v, &mut z);
+            z
+        };
+        let apply = |v: &[Complex<T>]| a.mul_vec(&precondition(v));
+
+        let target = self.tolerance * norm(b);
+        let mut r = a.residual(b, x);
+        let mut iterations = 0;
+
+        // The recycled subspace U of the previous solves, rescaled so that C = AM⁻¹U has
+        // orthonormal columns, after which the residual is projected out of C.
+        let mut u = mem::take(&mut self.recycled);
+        if u.iter().any(|ui| ui.len() != n) {
+            u.clear();
+        }
+        let mut c = u.iter().map(|ui| apply(ui)).collect::<Vec<_>>();
+        iterations += c.len();
+        match orthonormalize(&mut c, T::epsilon().sqrt()) {
+            Some(rc) => {
+                u = times_inverse_upper(u, &rc);
+                let mut correction = vec![zero; n];
+                for (ui, ci) in u.iter().zip(c.iter()) {
+                    let s = dot(ci, &r);
+                    axpy(s, ui, &mut correction);
+                    axpy(-s, ci, &mut r);
+                }
+                axpy(
+                    Complex::new(T::one(), T::zero()),
+                    &precondition(&correction),
+                    x,
+                );
+            }
+            None => {
+                u.clear();
+                c.clear();
+            }
+        }
+
+        let result = loop {
+            let r_norm = norm(&r);
+            if r_norm <= target {
+                break Ok(iterations);
+            }
+            if iterations >= self.max_iterations {
+                break Err(SolveError::NoConvergence);
+            }
+
+            // Arnoldi process for (I - CCᴴ)AM⁻¹, with Bₖ = CᴴAM⁻¹V.
+            let k = c.len();
+            let steps = (self.restart - k).min(self.max_iterations - iterations);
+            let mut v = vec![r.iter().map(|ri| *ri / r_norm).collect::<Vec<_>>()];
+            let mut h = vec![zero; (steps + 1) * steps];
+            let mut bk = vec![zero; k * steps];
+            let mut breakdown = false;
+            while v.len() <= steps && !breakdown {
+                let p = v.len() - 1;
+                let mut w = apply(&v[p]);
+                iterations += 1;
+                let before = norm(&w);
+                for (i, ci) in c.iter().enumerate() {
+                    let s = dot(ci, &w);
+                    bk[i * steps + p] = s;
+                    axpy(-s, ci, &mut w);
+                }
+                for (i, vi) in v.iter().enumerate() {
+                    let s = dot(vi, &w);
+                    h[i * steps + p] = s;
+                    axpy(-s, vi, &mut w);
+                }
+                let w_norm = norm(&w);
+                if w_norm <= T::epsilon() * before {
+                    // The Krylov subspace is invariant: the last basis vector is zero.
+                    breakdown = true;
+                    w.iter_mut().for_each(|wi| *wi = zero);
+                } else {
+                    h[(p + 1) * steps + p] = Complex::new(w_norm, T::zero());
+                    w.iter_mut().for_each(|wi| *wi = *wi / w_norm);
+                }
+                v.push(w);
+            }
+            let p = v.len() - 1;
+
+            // AM⁻¹Ŵ = V̂Ḡ with Ŵ = [UD, V], V̂ = [C, V] and Ḡ = [D Bₖ; 0 H].
+            let (rows, cols) = (k + p + 1, k + p);
+            let mut basis = Vec::with_capacity(cols);
+            let mut g = vec![zero; rows * cols];
+            for (i, ui) in u.iter().enumerate() {
+                let d = T::one() / norm(ui);
+                basis.push(ui.iter().map(|uj| *uj * d).collect::<Vec<_>>());
+                g[i * cols + i] = Complex::new(d, T::zero());
+                for j in 0..p {
+                    g[i * cols + k + j] = bk[i * steps + j];
+                }
+            }
+            basis.extend(v[..p].iter().cloned());
+            for i in 0..=p {
+                for j in 0..p {
+                    g[(k + i) * cols + k + j] = h[i * steps + j];
+                }
+            }
+            let projected = c
+                .iter()
+                .chain(v.iter())
+                .map(|vi| dot(vi, &r))
+                .collect::<Vec<_>>();
+            let y = match least_squares(rows, cols, &g, &projected) {
+                Some(y) => y,
+                None => break Err(SolveError::NumericalIssue),
+            };
+            let mut correction = vec![zero; n];
+            for (wj, yj) in basis.iter().zip(y.iter()) {
+                axpy(*yj, wj, &mut correction);
+            }
+            axpy(
+                Complex::new(T::one(), T::zero()),
+                &precondition(&correction),
+                x,
+            );
+            r = a.residual(b, x);
+
+            if self.recycle > 0 && !breakdown {
+                let hat = c.iter().chain(v.iter()).collect::<Vec<_>>();
+                if let Some((new_u, new_c)) = harmonic_ritz(self.recycle, &basis, &hat, &g) {
+                    u = new_u;
+                    c = new_c;
+                }
+            }
+        };
+        self.recycled = u;
+        result
+    }
+}
+
+/// The new recycled subspace `U` and `C = AM⁻¹U` with orthonormal columns, spanned by
+/// the ```recycle``` harmonic Ritz vectors of smallest magnitude of `AM⁻¹` in the span of
+/// ```basis```, where `AM⁻¹Ŵ = V̂Ḡ` for `Ŵ = basis` and `V̂ = hat`.
+///
+/// The harmonic Ritz pairs solve `ḠᴴḠz = θḠᴴV̂ᴴŴz`. Returns None if the problem is too
+/// ill-conditioned, in which case the previous subspace is kept.
+#[allow(clippy::type_complexity)]
+fn harmonic_ritz<T: Float>(
+    recycle: usize,
+    basis: &[Vec<Complex<T>>],
+    hat: &[&Vec<Complex<T>>],
+    g: &[Complex<T>],
+) -> Option<(Vec<Vec<Complex<T>>>, Vec<Vec<Complex<T>>>)> {
+    let zero = Complex::new(T::zero(), T::zero());
+    let (rows, cols) = (hat.len(), basis.len());
+    let phi = hat
+        .iter()
+        .flat_map(|vi| basis.iter().map(move |wj| dot(vi, wj)))
+        .collect::<Vec<_>>();
+    let gram = |m: &[Complex<T>]| {
+        (0..cols)
+            .flat_map(|i| {
+                (0..cols).map(move |j| {
+                    (0..rows).fold(zero, |acc, l| {
+                        acc + g[l * cols + i].conj() * m[l * cols + j]
+                    })
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    let (lhs, rhs) = (gram(g), gram(&phi));
+
+    // The standard eigenproblem (ḠᴴV̂ᴴŴ)⁻¹ḠᴴḠz = θz.
+    let lu = DenseLu::new(cols, rhs).ok()?;
+    let mut m = vec![zero; cols * cols];
+    for j in 0..cols {
+        let mut column = (0..cols).map(|i| lhs[i * cols + j]).collect::<Vec<_>>();
+        lu.solve(&mut column);
+        for (i, v) in column.into_iter().enumerate() {
+            m[i * cols + j] = v;
+        }
+    }
+    let mut theta = eigenvalues(cols, m.clone()).ok()?;
+    theta.sort_by(|x, y| {
+        x.norm()
+            .partial_cmp(&y.norm())
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+    theta.dedup_by(|x, y| (*x - *y).norm() <= T::epsilon().sqrt() * y.norm());
+
+    let mut u = Vec::with_capacity(recycle);
+    let mut c = Vec::with_capacity(recycle);
+    for lambda in theta.into_iter().take(recycle) {
+        let z = eigenvector(cols, &m, lambda).ok()?;
+        let mut y = vec![zero; basis[0].len()];
+        for (wj, zj) in basis.iter().zip(z.iter()) {
+            axpy(*zj, wj, &mut y);
+        }
+        u.push(y);
+        c.push(
+            (0..rows)
+                .map(|i| (0..cols).fold(zero, |acc, j| acc + g[i * cols + j] * z[j]))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    // ḠP = QR, C = V̂Q and U = ŴPR⁻¹, so that AM⁻¹U = C.
+    let r = orthonormalize(&mut c, T::epsilon().sqrt())?;
+    let c = c
+        .iter()
+        .map(|q| {
+            let mut column = vec![zero; basis[0].len()];
+            for (vi, qi) in hat.iter().zip(q.iter()) {
+                axpy(*qi, vi, &mut column);
+            }
+            column
+        })
+        .collect();
+    Some((times_inverse_upper(u, &r), c))
+}
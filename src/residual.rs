@@ -0,0 +1,54 @@
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// The norms of the residual `Ax - b` of an approximate solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResidualNorm<T: Float> {
+    /// The 2-norm `‖Ax - b‖₂`.
+    pub norm2: T,
+    /// The infinity norm `‖Ax - b‖∞`, the largest modulus of a component.
+    pub norm_inf: T,
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Returns the 2-norm and infinity norm of the residual `Ax - b`, e.g. to check the
+    /// quality of a solution. Duplicated entries are summed.
+    ///
+    /// Returns `Err` if ```x``` or ```b``` does not match the matrix size.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let x = vec![Complex::new(1., 0.), Complex::new(1., 0.)];
+    /// let b = vec![Complex::new(5., 0.), Complex::new(0., -3.)];
+    /// let r = m.residual_norm(&x, &b).unwrap();
+    /// assert_eq!(r.norm2, 5.);
+    /// assert_eq!(r.norm_inf, 4.);
+    ///```
+    pub fn residual_norm(
+        &self,
+        x: &[Complex<T>],
+        b: &[Complex<T>],
+    ) -> Result<ResidualNorm<T>, SolveError> {
+        let n = self.dimension();
+        if x.len() != n || b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let (sum, norm_inf) = self
+            .to_csr()
+            .residual(b, x)
+            .iter()
+            .fold((T::zero(), T::zero()), |(sum, max), r| {
+                (sum + r.norm_sqr(), max.max(r.norm()))
+            });
+        Ok(ResidualNorm {
+            norm2: sum.sqrt(),
+            norm_inf,
+        })
+    }
+}
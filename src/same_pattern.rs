@@ -0,0 +1,253 @@
+use crate::cancel::Watcher;
+use crate::progress::{Counter, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, SolvableFloat, SolveError, SymbolicAnalysis};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve many systems that share the sparsity pattern of this matrix and its
+    /// symbolic analysis, e.g. the contingencies of a network.
+    ///
+    /// ```values[k]``` holds the values of system ```k``` in the order of the elements
+    /// of this matrix, whose own values are not used. Only the fill-reducing ordering
+    /// of ```analysis``` is shared: each system runs its own factorization, over
+    /// ```num_threads()``` threads when the `rayon` feature is enabled. Each solution is
+    /// stored in ```rhs[k]```.
+    ///
+    /// Returns the status of each system; a singular system does not stop the others,
+    /// and every system fails if ```analysis``` does not match the size of this matrix.
+    /// Panics if ```values``` and ```rhs``` have different lengths.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut pattern = ComplexMatrix::<f64>::new();
+    /// pattern.add_element(0, 0, Complex::new(1., 0.));
+    /// pattern.add_element(1, 1, Complex::new(1., 0.));
+    /// let analysis = pattern.analyze_pattern().unwrap();
+    ///
+    /// let values = (1..10)
+    ///     .map(|k| vec![Complex::new(k as f64, 0.), Complex::new(0., k as f64)])
+    ///     .collect::<Vec<_>>();
+    /// let mut rhs = vec![vec![Complex::new(1., 0.), Complex::new(1., 0.)]; values.len()];
+    ///
+    /// let status = pattern.solve_pattern_batch(&analysis, &values, &mut rhs);
+    /// assert!(status.iter().all(|s| s.is_ok()));
+    /// assert_eq!(rhs[1], vec![Complex::new(0.5, 0.), Complex::new(0., -0.5)]);
+    ///```
+    pub fn solve_pattern_batch(
+        &self,
+        analysis: &SymbolicAnalysis,
+        values: &[Vec<Complex<T>>],
+        rhs: &mut [Vec<Complex<T>>],
+    ) -> Vec<Result<(), SolveError>> {
+        let pattern = self.check_rhs(analysis.size());
+        if pattern.is_ok() {
+            self.debug_validate(analysis.size());
+        }
+        solve_each(self.entries.len(), analysis.size(), values, rhs, |v, b| {
+            pattern?;
+            self.solve_ordered(analysis, v, b)
+        })
+    }
+
+    /// Solve the system at each of ```frequencies```, e.g. for an AC analysis, and
+    /// return the solutions.
+    ///
+    /// ```values(f)``` returns the values of the matrix at frequency ```f```, in the order
+    /// of the elements of this matrix, whose own values are not used; ```rhs``` is the
+    /// right-hand side at every frequency. The pattern is analyzed once and the
+    /// numeric factorizations run over ```num_threads()``` threads when the `rayon`
+    /// feature is enabled.
+    ///
+    /// Returns `Err` if ```rhs``` does not match the matrix size or the analysis fails,
+    /// otherwise the status of each frequency; a singular system does not stop the
+    /// others.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// // A series RL branch to ground: (R + jωL) i = v.
+    /// let mut pattern = ComplexMatrix::<f64>::new();
+    /// pattern.add_element(0, 0, Complex::new(1., 0.));
+    /// let frequencies = [0., 1., 2.];
+    /// let impedance = |f: f64| vec![Complex::new(1., f)];
+    ///
+    /// let currents = pattern.solve_sweep(&frequencies, impedance, &[Complex::new(1., 0.)]).unwrap();
+    /// assert_eq!(currents[0], Ok(vec![Complex::new(1., 0.)]));
+    /// assert_eq!(currents[1], Ok(vec![Complex::new(0.5, -0.5)]));
+    ///```
+    #[allow(clippy::type_complexity)]
+    pub fn solve_sweep<F>(
+        &self,
+        frequencies: &[T],
+        values: F,
+        rhs: &[Complex<T>],
+    ) -> Result<Vec<Result<Vec<Complex<T>>, SolveError>>, SolveError>
+    where
+        F: Fn(T) -> Vec<Complex<T>> + Sync,
+    {
+        self.check_rhs(rhs.len())?;
+        let analysis = self.analyze_pattern()?;
+        self.debug_validate(analysis.size());
+        Ok(sweep_each(
+            self.entries.len(),
+            analysis.size(),
+            frequencies,
+            values,
+            rhs,
+            |v, b| self.solve_ordered(&analysis, v, b),
+        ))
+    }
+
+    /// Solves the pattern of this matrix with the given values and column ordering.
+    fn solve_ordered(
+        &self,
+        analysis: &SymbolicAnalysis,
+        values: &[Complex<T>],
+        b: &mut [Complex<T>],
+    ) -> Result<(), SolveError> {
+        let info = unsafe { T::solve_ordered(self, values, b, analysis.column_permutation().as_slice()) };
+        solver::info_to_result(info)
+    }
+}
+
+/// Checks the dimensions of one system and solves it.
+fn solve_checked<T, F>(
+    nnz: usize,
+    size: usize,
+    values: &[Complex<T>],
+    rhs: &mut [Complex<T>],
+    solve: &F,
+    watcher: &Watcher,
+) -> Result<(), SolveError>
+where
+    T: Float,
+    F: Fn(&[Complex<T>], &mut [Complex<T>]) -> Result<(), SolveError>,
+{
+    watcher.check()?;
+    if values.len() != nnz {
+        return Err(SolveError::DimensionMismatch);
+    }
+    if rhs.len() != size {
+        return Err(SolveError::DimensionMismatch);
+    }
+    solve(values, rhs)
+}
+
+/// Solves each ```(values[k], rhs[k])``` system over ```num_threads()``` threads.
+#[cfg(feature = "rayon")]
+fn solve_each<T, F>(
+    nnz: usize,
+    size: usize,
+    values: &[Vec<Complex<T>>],
+    rhs: &mut [Vec<Complex<T>>],
+    solve: F,
+) -> Vec<Result<(), SolveError>>
+where
+    T: Float + Send + Sync,
+    F: Fn(&[Complex<T>], &mut [Complex<T>]) -> Result<(), SolveError> + Sync,
+{
+    use rayon::prelude::*;
+    assert_eq!(values.len(), rhs.len(), "values and rhs must have the same length");
+    let counter = Counter::new(Phase::Batch, rhs.len());
+    let watcher = Watcher::new();
+    crate::threads::run(|| {
+        values
+            .par_iter()
+            .zip(rhs.par_iter_mut())
+            .map(|(v, b)| {
+                let result = solve_checked(nnz, size, v, b, &solve, &watcher);
+                counter.tick();
+                result
+            })
+            .collect()
+    })
+}
+
+/// Solves each ```(values[k], rhs[k])``` system in turn.
+#[cfg(not(feature = "rayon"))]
+fn solve_each<T, F>(
+    nnz: usize,
+    size: usize,
+    values: &[Vec<Complex<T>>],
+    rhs: &mut [Vec<Complex<T>>],
+    solve: F,
+) -> Vec<Result<(), SolveError>>
+where
+    T: Float,
+    F: Fn(&[Complex<T>], &mut [Complex<T>]) -> Result<(), SolveError>,
+{
+    assert_eq!(values.len(), rhs.len(), "values and rhs must have the same length");
+    let counter = Counter::new(Phase::Batch, rhs.len());
+    let watcher = Watcher::new();
+    values
+        .iter()
+        .zip(rhs.iter_mut())
+        .map(|(v, b)| {
+            let result = solve_checked(nnz, size, v, b, &solve, &watcher);
+            counter.tick();
+            result
+        })
+        .collect()
+}
+
+/// Solves the system at each of ```frequencies``` over ```num_threads()``` threads.
+#[cfg(feature = "rayon")]
+fn sweep_each<T, V, F>(
+    nnz: usize,
+    size: usize,
+    frequencies: &[T],
+    values: V,
+    rhs: &[Complex<T>],
+    solve: F,
+) -> Vec<Result<Vec<Complex<T>>, SolveError>>
+where
+    T: Float + Send + Sync,
+    V: Fn(T) -> Vec<Complex<T>> + Sync,
+    F: Fn(&[Complex<T>], &mut [Complex<T>]) -> Result<(), SolveError> + Sync,
+{
+    use rayon::prelude::*;
+    let counter = Counter::new(Phase::Batch, frequencies.len());
+    let watcher = Watcher::new();
+    crate::threads::run(|| {
+        frequencies
+            .par_iter()
+            .map(|f| {
+                let mut x = rhs.to_vec();
+                let result = solve_checked(nnz, size, &values(*f), &mut x, &solve, &watcher).map(|_| x);
+                counter.tick();
+                result
+            })
+            .collect()
+    })
+}
+
+/// Solves the system at each of ```frequencies``` in turn.
+#[cfg(not(feature = "rayon"))]
+fn sweep_each<T, V, F>(
+    nnz: usize,
+    size: usize,
+    frequencies: &[T],
+    values: V,
+    rhs: &[Complex<T>],
+    solve: F,
+) -> Vec<Result<Vec<Complex<T>>, SolveError>>
+where
+    T: Float,
+    V: Fn(T) -> Vec<Complex<T>>,
+    F: Fn(&[Complex<T>], &mut [Complex<T>]) -> Result<(), SolveError>,
+{
+    let counter = Counter::new(Phase::Batch, frequencies.len());
+    let watcher = Watcher::new();
+    frequencies
+        .iter()
+        .map(|f| {
+            let mut x = rhs.to_vec();
+            let result = solve_checked(nnz, size, &values(*f), &mut x, &solve, &watcher).map(|_| x);
+            counter.tick();
+            result
+        })
+        .collect()
+}
@@ -0,0 +1,43 @@
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Whether the CPU supports AVX2 and FMA: 0 until detected, then 1 if not and 2 if so.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+static AVX2: AtomicU8 = AtomicU8::new(0);
+
+/// Runs ```kernel``` inside a function compiled with AVX2 and FMA when the CPU running
+/// the program supports them, so the compiler may vectorize the complex products of
+/// the sparse matrix-vector kernels with wider instructions.
+///
+/// The kernels are plain Rust without intrinsics, and the features only apply to the
+/// code inlined into that function, so ```kernel``` and the functions it calls should be
+/// `#[inline]`. Without the `std` feature the CPU cannot be queried, and only the
+/// features enabled at compile time are used.
+#[inline(always)]
+pub(crate) fn dispatch<R, F: FnOnce() -> R>(kernel: F) -> R {
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+    if has_avx2() {
+        // SAFETY: the CPU supports AVX2 and FMA.
+        return unsafe { avx2(kernel) };
+    }
+    kernel()
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn has_avx2() -> bool {
+    match AVX2.load(Ordering::Relaxed) {
+        0 => {
+            let detected =
+                std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("fma");
+            AVX2.store(detected as u8 + 1, Ordering::Relaxed);
+            detected
+        }
+        stored => stored == 2,
+    }
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn avx2<R, F: FnOnce() -> R>(kernel: F) -> R {
+    kernel()
+}
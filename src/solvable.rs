@@ -0,0 +1,485 @@
+#[cfg(feature = "eigen")]
+use crate::solver;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use num_traits::float::Float;
+#[cfg(feature = "eigen")]
+use std::os::raw::c_int;
+
+/// The scalar types with compiled sparse solver kernels: `f64` and `f32`, each
+/// behind its feature of the same name.
+///
+/// The trait is sealed. It lets generic code solve a ```ComplexMatrix``` without
+/// specializing on the scalar type. The kernels are Eigen's, or the pure-Rust
+/// ```SparseLu``` without the `eigen` feature.
+///```rust
+/// use sparse_complex::{ComplexMatrix, SolvableFloat, SolveError};
+/// use num::Complex;
+///
+/// fn halve<T: SolvableFloat>(m: &ComplexMatrix<T>, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+///     m.solve(b)?;
+///     let two = T::one() + T::one();
+///     b.iter_mut().for_each(|x| *x = *x / two);
+///     Ok(())
+/// }
+///
+/// let mut m = ComplexMatrix::<f32>::new();
+/// m.add_element(0, 0, Complex::new(1., 0.));
+/// let mut b = vec![Complex::new(4., 0.)];
+/// halve(&m, &mut b).unwrap();
+/// assert_eq!(b, vec![Complex::new(2., 0.)]);
+///```
+pub trait SolvableFloat: Float + Send + Sync + sealed::Kernels {}
+
+#[cfg(feature = "f64")]
+impl SolvableFloat for f64 {}
+
+#[cfg(feature = "f32")]
+impl SolvableFloat for f32 {}
+
+pub(crate) mod sealed {
+    use super::*;
+
+    /// The sparse solver entry points for a scalar type.
+    pub trait Kernels: Float {
+        fn solve_sparse(matrix: &ComplexMatrix<Self>, b: &mut [Complex<Self>]) -> Result<(), SolveError>;
+        fn pivot_growth(matrix: &ComplexMatrix<Self>) -> Result<Self, SolveError>;
+        /// Allocates an Eigen solver context for this scalar type.
+        #[cfg(feature = "eigen")]
+        fn new_context() -> Result<solver::Context, SolveError>;
+
+        // The entry points of a context. Safety: ```context``` was created by
+        // ```new_context``` of the same type, and the buffers have the sizes reported by
+        // the context.
+        #[cfg(feature = "eigen")]
+        unsafe fn context_set_options(context: &mut solver::Context, pivot_threshold: f64, ordering: c_int);
+        #[cfg(feature = "eigen")]
+        unsafe fn context_factorize(context: &mut solver::Context, matrix: &ComplexMatrix<Self>, size: usize) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_factorize_solve(
+            context: &mut solver::Context,
+            matrix: &ComplexMatrix<Self>,
+            b: &mut [Complex<Self>],
+        ) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_solve(context: &solver::Context, b: &mut [Complex<Self>]) -> c_int;
+        /// Like ```context_solve```, reusing the solution buffer of the context.
+        #[cfg(feature = "eigen")]
+        unsafe fn context_solve_factorized(context: &mut solver::Context, b: &mut [Complex<Self>]) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_solve_transposed(context: &solver::Context, b: &mut [Complex<Self>]) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_solve_adjoint(context: &solver::Context, b: &mut [Complex<Self>]) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_determinant(context: &solver::Context, det: &mut Complex<Self>) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_log_determinant(
+            context: &solver::Context,
+            log_abs: &mut Self,
+            phase: &mut Complex<Self>,
+        ) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_factor_stats(
+            context: &solver::Context,
+            nnz_a: &mut usize,
+            nnz_l: &mut usize,
+            nnz_u: &mut usize,
+            flops: &mut f64,
+        ) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_memory_usage(context: &solver::Context) -> usize;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_factor_counts(context: &solver::Context, l_nnz: &mut usize, u_nnz: &mut usize) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn context_factors(
+            context: &solver::Context,
+            lower: &mut ComplexMatrix<Self>,
+            upper: &mut ComplexMatrix<Self>,
+            row_perm: &mut [usize],
+            col_perm: &mut [usize],
+        ) -> c_int;
+
+        // One-shot solves. Safety: ```values``` has an entry per element of ```matrix```
+        // and ```col_perm``` the length of ```b```; ```b``` holds ```nrhs``` columns of
+        // length ```n``` with leading dimension ```ld```.
+        #[cfg(feature = "eigen")]
+        unsafe fn solve_ordered(
+            matrix: &ComplexMatrix<Self>,
+            values: &[Complex<Self>],
+            b: &mut [Complex<Self>],
+            col_perm: &[usize],
+        ) -> c_int;
+        #[cfg(feature = "eigen")]
+        unsafe fn solve_strided(
+            matrix: &ComplexMatrix<Self>,
+            b: &mut [Complex<Self>],
+            n: usize,
+            nrhs: usize,
+            ld: usize,
+        ) -> c_int;
+    }
+
+    #[cfg(all(feature = "eigen", feature = "f64"))]
+    impl Kernels for f64 {
+        fn solve_sparse(matrix: &ComplexMatrix<f64>, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+            let info = unsafe {
+                solver::solve_cpp(
+                    matrix.entries.as_ptr(),
+                    matrix.rows.as_ptr(),
+                    matrix.cols.as_ptr(),
+                    matrix.entries.len(),
+                    b.as_mut_ptr(),
+                    b.len(),
+                )
+            };
+            solver::info_to_result(info)
+        }
+
+        fn pivot_growth(matrix: &ComplexMatrix<f64>) -> Result<f64, SolveError> {
+            let mut growth = 0.;
+            let info = unsafe {
+                solver::pivot_growth_cpp(
+                    matrix.entries.as_ptr(),
+                    matrix.rows.as_ptr(),
+                    matrix.cols.as_ptr(),
+                    matrix.entries.len(),
+                    matrix.dimension(),
+                    &mut growth,
+                )
+            };
+            solver::info_to_result(info)?;
+            Ok(growth)
+        }
+
+        fn new_context() -> Result<solver::Context, SolveError> {
+            solver::Context::f64()
+        }
+
+        unsafe fn context_set_options(context: &mut solver::Context, pivot_threshold: f64, ordering: c_int) {
+            solver::context_set_pivot_threshold_cpp(context.as_ptr(), pivot_threshold);
+            solver::context_set_ordering_cpp(context.as_ptr(), ordering);
+        }
+
+        unsafe fn context_factorize(context: &mut solver::Context, matrix: &ComplexMatrix<f64>, size: usize) -> c_int {
+            solver::context_factorize_cpp(
+                context.as_ptr(),
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                size,
+            )
+        }
+
+        unsafe fn context_factorize_solve(
+            context: &mut solver::Context,
+            matrix: &ComplexMatrix<f64>,
+            b: &mut [Complex<f64>],
+        ) -> c_int {
+            solver::context_solve_cpp(
+                context.as_ptr(),
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                b.as_mut_ptr(),
+                b.len(),
+            )
+        }
+
+        unsafe fn context_solve(context: &solver::Context, b: &mut [Complex<f64>]) -> c_int {
+            solver::context_solve_shared_cpp(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_factorized(context: &mut solver::Context, b: &mut [Complex<f64>]) -> c_int {
+            solver::context_solve_factorized_cpp(context.as_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_transposed(context: &solver::Context, b: &mut [Complex<f64>]) -> c_int {
+            solver::context_solve_transposed_cpp(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_adjoint(context: &solver::Context, b: &mut [Complex<f64>]) -> c_int {
+            solver::context_solve_adjoint_cpp(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_determinant(context: &solver::Context, det: &mut Complex<f64>) -> c_int {
+            solver::context_determinant_cpp(context.as_const_ptr(), det)
+        }
+
+        unsafe fn context_log_determinant(
+            context: &solver::Context,
+            log_abs: &mut f64,
+            phase: &mut Complex<f64>,
+        ) -> c_int {
+            solver::context_log_determinant_cpp(context.as_const_ptr(), log_abs, phase)
+        }
+
+        unsafe fn context_factor_stats(
+            context: &solver::Context,
+            nnz_a: &mut usize,
+            nnz_l: &mut usize,
+            nnz_u: &mut usize,
+            flops: &mut f64,
+        ) -> c_int {
+            solver::context_factor_stats_cpp(context.as_const_ptr(), nnz_a, nnz_l, nnz_u, flops)
+        }
+
+        unsafe fn context_memory_usage(context: &solver::Context) -> usize {
+            solver::context_memory_usage_cpp(context.as_const_ptr())
+        }
+
+        unsafe fn context_factor_counts(context: &solver::Context, l_nnz: &mut usize, u_nnz: &mut usize) -> c_int {
+            solver::context_factor_counts_cpp(context.as_const_ptr(), l_nnz, u_nnz)
+        }
+
+        unsafe fn context_factors(
+            context: &solver::Context,
+            lower: &mut ComplexMatrix<f64>,
+            upper: &mut ComplexMatrix<f64>,
+            row_perm: &mut [usize],
+            col_perm: &mut [usize],
+        ) -> c_int {
+            solver::context_factors_cpp(
+                context.as_const_ptr(),
+                lower.rows.as_mut_ptr(),
+                lower.cols.as_mut_ptr(),
+                lower.entries.as_mut_ptr(),
+                upper.rows.as_mut_ptr(),
+                upper.cols.as_mut_ptr(),
+                upper.entries.as_mut_ptr(),
+                row_perm.as_mut_ptr(),
+                col_perm.as_mut_ptr(),
+            )
+        }
+
+        unsafe fn solve_ordered(
+            matrix: &ComplexMatrix<f64>,
+            values: &[Complex<f64>],
+            b: &mut [Complex<f64>],
+            col_perm: &[usize],
+        ) -> c_int {
+            solver::solve_ordered_cpp(
+                values.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                values.len(),
+                b.as_mut_ptr(),
+                b.len(),
+                col_perm.as_ptr(),
+            )
+        }
+
+        unsafe fn solve_strided(
+            matrix: &ComplexMatrix<f64>,
+            b: &mut [Complex<f64>],
+            n: usize,
+            nrhs: usize,
+            ld: usize,
+        ) -> c_int {
+            solver::solve_strided_cpp(
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                b.as_mut_ptr(),
+                n,
+                nrhs,
+                ld,
+            )
+        }
+    }
+
+    #[cfg(all(feature = "eigen", feature = "f32"))]
+    impl Kernels for f32 {
+        fn solve_sparse(matrix: &ComplexMatrix<f32>, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+            let info = unsafe {
+                solver::solve_cpp32(
+                    matrix.entries.as_ptr(),
+                    matrix.rows.as_ptr(),
+                    matrix.cols.as_ptr(),
+                    matrix.entries.len(),
+                    b.as_mut_ptr(),
+                    b.len(),
+                )
+            };
+            solver::info_to_result(info)
+        }
+
+        fn pivot_growth(matrix: &ComplexMatrix<f32>) -> Result<f32, SolveError> {
+            let mut growth = 0.;
+            let info = unsafe {
+                solver::pivot_growth_cpp32(
+                    matrix.entries.as_ptr(),
+                    matrix.rows.as_ptr(),
+                    matrix.cols.as_ptr(),
+                    matrix.entries.len(),
+                    matrix.dimension(),
+                    &mut growth,
+                )
+            };
+            solver::info_to_result(info)?;
+            Ok(growth)
+        }
+
+        fn new_context() -> Result<solver::Context, SolveError> {
+            solver::Context::f32()
+        }
+
+        unsafe fn context_set_options(context: &mut solver::Context, pivot_threshold: f64, ordering: c_int) {
+            solver::context_set_pivot_threshold_cpp32(context.as_ptr(), pivot_threshold as f32);
+            solver::context_set_ordering_cpp32(context.as_ptr(), ordering);
+        }
+
+        unsafe fn context_factorize(context: &mut solver::Context, matrix: &ComplexMatrix<f32>, size: usize) -> c_int {
+            solver::context_factorize_cpp32(
+                context.as_ptr(),
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                size,
+            )
+        }
+
+        unsafe fn context_factorize_solve(
+            context: &mut solver::Context,
+            matrix: &ComplexMatrix<f32>,
+            b: &mut [Complex<f32>],
+        ) -> c_int {
+            solver::context_solve_cpp32(
+                context.as_ptr(),
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                b.as_mut_ptr(),
+                b.len(),
+            )
+        }
+
+        unsafe fn context_solve(context: &solver::Context, b: &mut [Complex<f32>]) -> c_int {
+            solver::context_solve_shared_cpp32(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_factorized(context: &mut solver::Context, b: &mut [Complex<f32>]) -> c_int {
+            solver::context_solve_factorized_cpp32(context.as_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_transposed(context: &solver::Context, b: &mut [Complex<f32>]) -> c_int {
+            solver::context_solve_transposed_cpp32(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_solve_adjoint(context: &solver::Context, b: &mut [Complex<f32>]) -> c_int {
+            solver::context_solve_adjoint_cpp32(context.as_const_ptr(), b.as_mut_ptr(), b.len())
+        }
+
+        unsafe fn context_determinant(context: &solver::Context, det: &mut Complex<f32>) -> c_int {
+            solver::context_determinant_cpp32(context.as_const_ptr(), det)
+        }
+
+        unsafe fn context_log_determinant(
+            context: &solver::Context,
+            log_abs: &mut f32,
+            phase: &mut Complex<f32>,
+        ) -> c_int {
+            solver::context_log_determinant_cpp32(context.as_const_ptr(), log_abs, phase)
+        }
+
+        unsafe fn context_factor_stats(
+            context: &solver::Context,
+            nnz_a: &mut usize,
+            nnz_l: &mut usize,
+            nnz_u: &mut usize,
+            flops: &mut f64,
+        ) -> c_int {
+            solver::context_factor_stats_cpp32(context.as_const_ptr(), nnz_a, nnz_l, nnz_u, flops)
+        }
+
+        unsafe fn context_memory_usage(context: &solver::Context) -> usize {
+            solver::context_memory_usage_cpp32(context.as_const_ptr())
+        }
+
+        unsafe fn context_factor_counts(context: &solver::Context, l_nnz: &mut usize, u_nnz: &mut usize) -> c_int {
+            solver::context_factor_counts_cpp32(context.as_const_ptr(), l_nnz, u_nnz)
+        }
+
+        unsafe fn context_factors(
+            context: &solver::Context,
+            lower: &mut ComplexMatrix<f32>,
+            upper: &mut ComplexMatrix<f32>,
+            row_perm: &mut [usize],
+            col_perm: &mut [usize],
+        ) -> c_int {
+            solver::context_factors_cpp32(
+                context.as_const_ptr(),
+                lower.rows.as_mut_ptr(),
+                lower.cols.as_mut_ptr(),
+                lower.entries.as_mut_ptr(),
+                upper.rows.as_mut_ptr(),
+                upper.cols.as_mut_ptr(),
+                upper.entries.as_mut_ptr(),
+                row_perm.as_mut_ptr(),
+                col_perm.as_mut_ptr(),
+            )
+        }
+
+        unsafe fn solve_ordered(
+            matrix: &ComplexMatrix<f32>,
+            values: &[Complex<f32>],
+            b: &mut [Complex<f32>],
+            col_perm: &[usize],
+        ) -> c_int {
+            solver::solve_ordered_cpp32(
+                values.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                values.len(),
+                b.as_mut_ptr(),
+                b.len(),
+                col_perm.as_ptr(),
+            )
+        }
+
+        unsafe fn solve_strided(
+            matrix: &ComplexMatrix<f32>,
+            b: &mut [Complex<f32>],
+            n: usize,
+            nrhs: usize,
+            ld: usize,
+        ) -> c_int {
+            solver::solve_strided_cpp32(
+                matrix.entries.as_ptr(),
+                matrix.rows.as_ptr(),
+                matrix.cols.as_ptr(),
+                matrix.entries.len(),
+                b.as_mut_ptr(),
+                n,
+                nrhs,
+                ld,
+            )
+        }
+    }
+
+    #[cfg(all(not(feature = "eigen"), feature = "f64"))]
+    impl Kernels for f64 {
+        fn solve_sparse(matrix: &ComplexMatrix<f64>, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+            matrix.factorize_sparse()?.solve(b)
+        }
+
+        fn pivot_growth(matrix: &ComplexMatrix<f64>) -> Result<f64, SolveError> {
+            Ok(matrix.factorize_sparse()?.pivot_growth())
+        }
+    }
+
+    #[cfg(all(not(feature = "eigen"), feature = "f32"))]
+    impl Kernels for f32 {
+        fn solve_sparse(matrix: &ComplexMatrix<f32>, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+            matrix.factorize_sparse()?.solve(b)
+        }
+
+        fn pivot_growth(matrix: &ComplexMatrix<f32>) -> Result<f32, SolveError> {
+            Ok(matrix.factorize_sparse()?.pivot_growth())
+        }
+    }
+}
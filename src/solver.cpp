@@ -1,14 +1,32 @@
 #include <complex>
 #include <Eigen/Sparse>
 #include <Eigen/SparseLU>
+#include <Eigen/SparseQR>
+#include <Eigen/SparseCholesky>
+#include <Eigen/IterativeLinearSolvers>
 #include <vector>
+#include <new>
+#include <cmath>
 #include <iostream>
+#include <algorithm>
+#include <type_traits>
+#include <cstdint>
 
 typedef std::complex<double> cdouble;
 typedef std::complex<float> cfloat;
 typedef Eigen::Triplet<cdouble> T;
 typedef Eigen::Triplet<cfloat> T32;
 
+// The entry points of each scalar type are only compiled when its cargo feature
+// (`f64`, `f32`) is enabled.
+
+// The row and column indices of the triplets, 32-bit with the `index32` feature.
+#ifdef SPARSE_COMPLEX_INDEX32
+typedef uint32_t index_t;
+#else
+typedef size_t index_t;
+#endif
+
 extern "C" struct cmplx64
 {
     double re;
@@ -21,102 +39,1189 @@ extern "C" struct cmplx32
     float im;
 };
 
-extern "C" void solve_cpp(const cmplx64 *values, const size_t *rows, const size_t *cols, const size_t n_value, cmplx64 *b, const size_t size)
+// Iterator presenting the triplet arrays to setFromTriplets without copying them
+// into a vector of Eigen::Triplet first.
+template <typename Scalar>
+struct TripletView
+{
+    const std::complex<Scalar> *values;
+    const index_t *rows;
+    const index_t *cols;
+    size_t i;
+
+    Eigen::Index row() const { return static_cast<Eigen::Index>(rows[i]); }
+    Eigen::Index col() const { return static_cast<Eigen::Index>(cols[i]); }
+    std::complex<Scalar> value() const { return values[i]; }
+
+    const TripletView *operator->() const { return this; }
+    const TripletView &operator*() const { return *this; }
+    TripletView &operator++()
+    {
+        ++i;
+        return *this;
+    }
+    bool operator!=(const TripletView &other) const { return i != other.i; }
+};
+
+// True if the triplets are unique and in column-major order, the storage order of A.
+static bool is_column_sorted(const index_t *rows, const index_t *cols, const size_t n_value)
+{
+    for (size_t i = 1; i < n_value; i++)
+    {
+        if (cols[i] < cols[i - 1] || (cols[i] == cols[i - 1] && rows[i] <= rows[i - 1]))
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
+// Sorted triplets are appended directly in storage order. Otherwise setFromTriplets
+// compresses with counting passes over the outer index and sums the duplicated
+// entries, which is linear in the number of triplets.
+template <typename Scalar>
+static void set_from_triplets(Eigen::SparseMatrix<std::complex<Scalar>> &A, const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value)
+{
+    if (is_column_sorted(rows, cols, n_value))
+    {
+        A.setZero();
+        A.reserve(static_cast<Eigen::Index>(n_value));
+        size_t k = 0;
+        for (Eigen::Index j = 0; j < A.outerSize(); ++j)
+        {
+            A.startVec(j);
+            for (; k < n_value && cols[k] == static_cast<size_t>(j); ++k)
+            {
+                A.insertBack(static_cast<Eigen::Index>(rows[k]), j) = values[k];
+            }
+        }
+        A.finalize();
+        return;
+    }
+
+    TripletView<Scalar> begin = {values, rows, cols, 0};
+    TripletView<Scalar> end = {values, rows, cols, n_value};
+    A.setFromTriplets(begin, end);
+}
+
+// Status codes of the entry points beyond the values of Eigen::ComputationInfo.
+static const int OUT_OF_MEMORY = 4;
+static const int INTERNAL_ERROR = 5;
+
+// Runs the body of an entry point, translating the exceptions thrown by Eigen or the
+// standard library into status codes: an exception unwinding into Rust through an
+// extern "C" function aborts the process.
+template <typename F>
+static int guarded(F body) noexcept
+{
+    try
+    {
+        return body();
+    }
+    catch (const std::bad_alloc &)
+    {
+        return OUT_OF_MEMORY;
+    }
+    catch (...)
+    {
+        return INTERNAL_ERROR;
+    }
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int solve_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        Eigen::SparseMatrix<cdouble> A(size, size);
+
+        Eigen::VectorXcd b_(size);
+        Eigen::VectorXcd x(size);
+
+        Eigen::SparseLU< Eigen::SparseMatrix<cdouble> > solver;
+
+        double re, im;
+        cdouble value;
+        size_t i;
+
+        for (i = 0; i < size; i++)
+        {
+
+            re = (b + i)->re;
+            im = (b + i)->im;
+            value = cdouble(re, im);
+            b_[i] = value;
+        }
+
+        set_from_triplets(A, reinterpret_cast<const cdouble *>(values), rows, cols, n_value);
+
+        solver.compute(A);
+        if (solver.info() != Eigen::Success)
+        {
+            return solver.info();
+        }
+        x = solver.solve(b_);
+
+        for (size_t i = 0; i < size; i++)
+        {
+            re = x[i].real();
+            im = x[i].imag();
+            (b + i)->re = re;
+            (b + i)->im = im;
+        }
+        return Eigen::Success;
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int solve_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx32 *b, const size_t size)
 {
-    Eigen::SparseMatrix<cdouble> A(size, size);
-    A.reserve(n_value);
+    return guarded([&]() -> int {
+        Eigen::SparseMatrix<cfloat> A(size, size);
+
+        Eigen::VectorXcf b_(size);
+        Eigen::VectorXcf x(size);
+
+        Eigen::SparseLU< Eigen::SparseMatrix<cfloat> > solver;
+
+        float re, im;
+        cfloat value;
+        size_t i;
+
+        for (i = 0; i < size; i++)
+        {
 
-    std::vector<T> triplets;
-    triplets.reserve(n_value);
+            re = (b + i)->re;
+            im = (b + i)->im;
+            value = cfloat(re, im);
+            b_[i] = value;
+        }
 
-    Eigen::VectorXcd b_(size);
-    Eigen::VectorXcd x(size);
+        set_from_triplets(A, reinterpret_cast<const cfloat *>(values), rows, cols, n_value);
 
-    Eigen::SparseLU< Eigen::SparseMatrix<cdouble> > solver;
+        solver.compute(A);
+        if (solver.info() != Eigen::Success)
+        {
+            return solver.info();
+        }
+        x = solver.solve(b_);
 
-    double re, im;
-    cdouble value;
-    size_t row, col, i;
+        for (size_t i = 0; i < size; i++)
+        {
+            re = x[i].real();
+            im = x[i].imag();
+            (b + i)->re = re;
+            (b + i)->im = im;
+        }
+        return Eigen::Success;
+    });
+}
+#endif
 
-    for (i = 0; i < n_value; i++)
+template <typename Scalar>
+static Scalar max_abs(const Eigen::SparseMatrix<std::complex<Scalar>> &A)
+{
+    Scalar max = 0;
+    for (Eigen::Index k = 0; k < A.outerSize(); ++k)
     {
-        re = (values + i)->re;
-        im = (values + i)->im;
-        value = cdouble(re, im);
-        row = *(rows + i);
-        col = *(cols + i);
-        triplets.push_back(T(row, col, value));
+        for (typename Eigen::SparseMatrix<std::complex<Scalar>>::InnerIterator it(A, k); it; ++it)
+        {
+            max = std::max(max, std::abs(it.value()));
+        }
     }
+    return max;
+}
+
+// The U factor of SparseLU is split between the diagonal blocks of the supernodes,
+// stored along with L, and the remaining off-supernode entries.
+template <typename Scalar, typename Solver>
+static Scalar max_abs_u(const Solver &solver)
+{
+    auto U = solver.matrixU();
+    typedef typename std::decay<decltype(U.m_mapL)>::type SupernodalType;
+    typedef typename std::decay<decltype(U.m_mapU)>::type OffSupernodalType;
 
-    for (i = 0; i < size; i++)
+    Scalar max = 0;
+    for (Eigen::Index j = 0; j < U.cols(); ++j)
     {
+        for (typename SupernodalType::InnerIterator it(U.m_mapL, j); it; ++it)
+        {
+            if (it.row() <= j)
+            {
+                max = std::max(max, std::abs(it.value()));
+            }
+        }
+        for (typename OffSupernodalType::InnerIterator it(U.m_mapU, j); it; ++it)
+        {
+            max = std::max(max, std::abs(it.value()));
+        }
+    }
+    return max;
+}
 
-        re = (b + i)->re;
-        im = (b + i)->im;
-        value = cdouble(re, im);
-        b_[i] = value;
+// Entries of the L and U factors: the supernodal storage holds L with the diagonal
+// blocks of U, the remaining entries of U are stored separately.
+template <typename Solver>
+static size_t factor_nonzeros(const Solver &solver)
+{
+    auto U = solver.matrixU();
+    typedef typename std::decay<decltype(U.m_mapL)>::type SupernodalType;
+
+    size_t count = 0;
+    for (Eigen::Index j = 0; j < U.cols(); ++j)
+    {
+        for (typename SupernodalType::InnerIterator it(U.m_mapL, j); it; ++it)
+        {
+            ++count;
+        }
     }
+    return count + static_cast<size_t>(U.m_mapU.nonZeros());
+}
 
-    A.setFromTriplets(triplets.begin(), triplets.end());
+template <typename Scalar>
+static int pivot_growth(const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, Scalar *growth)
+{
+    Eigen::SparseMatrix<std::complex<Scalar>> A(size, size);
+    set_from_triplets(A, values, rows, cols, n_value);
 
+    Eigen::SparseLU<Eigen::SparseMatrix<std::complex<Scalar>>> solver;
     solver.compute(A);
-    x = solver.solve(b_);
-
-    for (size_t i = 0; i < size; i++)
+    if (solver.info() != Eigen::Success)
     {
-        re = x[i].real();
-        im = x[i].imag();
-        (b + i)->re = re;
-        (b + i)->im = im;
+        return solver.info();
     }
+
+    Scalar max_a = max_abs(A);
+    *growth = max_a > 0 ? max_abs_u<Scalar>(solver) / max_a : 0;
+    return Eigen::Success;
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int pivot_growth_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, double *growth)
+{
+    return guarded([&]() -> int {
+        return pivot_growth(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, size, growth);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int pivot_growth_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, float *growth)
+{
+    return guarded([&]() -> int {
+        return pivot_growth(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, size, growth);
+    });
 }
+#endif
 
-extern "C" void solve_cpp32(const cmplx32 *values, const size_t *rows, const size_t *cols, const size_t n_value, cmplx32 *b, const size_t size)
+// Column ordering of the pattern only: the values are irrelevant to the analysis.
+extern "C" int analyze_pattern_cpp(const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, size_t *col_perm)
 {
-    Eigen::SparseMatrix<cfloat> A(size, size);
-    A.reserve(n_value);
+    return guarded([&]() -> int {
+        std::vector<cdouble> ones(n_value, cdouble(1., 0.));
+        Eigen::SparseMatrix<cdouble> A(size, size);
+        set_from_triplets(A, ones.data(), rows, cols, n_value);
+        A.makeCompressed();
 
-    std::vector<T32> triplets;
-    triplets.reserve(n_value);
+        Eigen::SparseLU<Eigen::SparseMatrix<cdouble>> solver;
+        solver.analyzePattern(A);
 
-    Eigen::VectorXcf b_(size);
-    Eigen::VectorXcf x(size);
+        const auto &indices = solver.colsPermutation().indices();
+        for (size_t i = 0; i < size; i++)
+        {
+            col_perm[i] = indices[i];
+        }
+        return Eigen::Success;
+    });
+}
+
+// SparseLU default-constructs its ordering functor inside analyzePattern, so a
+// precomputed column ordering is handed over through a thread-local pointer.
+static thread_local const size_t *preset_col_perm = nullptr;
 
-    Eigen::SparseLU< Eigen::SparseMatrix<cfloat> > solver;
+struct PresetOrdering
+{
+    typedef Eigen::PermutationMatrix<Eigen::Dynamic, Eigen::Dynamic, int> PermutationType;
+
+    template <typename MatrixType>
+    void operator()(const MatrixType &mat, PermutationType &perm)
+    {
+        perm.resize(mat.cols());
+        for (Eigen::Index i = 0; i < mat.cols(); i++)
+        {
+            perm.indices()(i) = static_cast<int>(preset_col_perm[i]);
+        }
+    }
+};
 
-    float re, im;
-    cfloat value;
-    size_t row, col, i;
+template <typename Scalar>
+static int solve_ordered(const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value, std::complex<Scalar> *b, const size_t size, const size_t *col_perm)
+{
+    typedef std::complex<Scalar> C;
+    Eigen::SparseMatrix<C> A(size, size);
+    set_from_triplets(A, values, rows, cols, n_value);
+    A.makeCompressed();
 
-    for (i = 0; i < n_value; i++)
+    Eigen::SparseLU<Eigen::SparseMatrix<C>, PresetOrdering> solver;
+    preset_col_perm = col_perm;
+    solver.compute(A);
+    preset_col_perm = nullptr;
+    if (solver.info() != Eigen::Success)
     {
-        re = (values + i)->re;
-        im = (values + i)->im;
-        value = cfloat(re, im);
-        row = *(rows + i);
-        col = *(cols + i);
-        triplets.push_back(T32(row, col, value));
+        return solver.info();
     }
 
-    for (i = 0; i < size; i++)
+    Eigen::Map<Eigen::Matrix<C, Eigen::Dynamic, 1>> b_(b, size);
+    Eigen::Matrix<C, Eigen::Dynamic, 1> x = solver.solve(b_);
+    b_ = x;
+    return Eigen::Success;
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int solve_ordered_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx64 *b, const size_t size, const size_t *col_perm)
+{
+    return guarded([&]() -> int {
+        return solve_ordered(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, reinterpret_cast<cdouble *>(b), size, col_perm);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int solve_ordered_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx32 *b, const size_t size, const size_t *col_perm)
+{
+    return guarded([&]() -> int {
+        return solve_ordered(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, reinterpret_cast<cfloat *>(b), size, col_perm);
+    });
+}
+#endif
+
+// Solves for a column-major block of right-hand sides mapped in place: column j
+// starts at b + j * ld, so LAPACK-style buffers are used without repacking.
+template <typename Scalar>
+static int solve_strided(const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value, std::complex<Scalar> *b, const size_t size, const size_t nrhs, const size_t ld)
+{
+    typedef std::complex<Scalar> C;
+    typedef Eigen::Matrix<C, Eigen::Dynamic, Eigen::Dynamic> Block;
+    Eigen::SparseMatrix<C> A(size, size);
+    set_from_triplets(A, values, rows, cols, n_value);
+    A.makeCompressed();
+
+    Eigen::SparseLU<Eigen::SparseMatrix<C>> solver;
+    solver.compute(A);
+    if (solver.info() != Eigen::Success)
     {
+        return solver.info();
+    }
+
+    Eigen::Map<Block, 0, Eigen::OuterStride<>> b_(b, size, nrhs, Eigen::OuterStride<>(ld));
+    Block x = solver.solve(b_);
+    b_ = x;
+    return Eigen::Success;
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int solve_strided_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx64 *b, const size_t size, const size_t nrhs, const size_t ld)
+{
+    return guarded([&]() -> int {
+        return solve_strided(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, reinterpret_cast<cdouble *>(b), size, nrhs, ld);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int solve_strided_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx32 *b, const size_t size, const size_t nrhs, const size_t ld)
+{
+    return guarded([&]() -> int {
+        return solve_strided(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, reinterpret_cast<cfloat *>(b), size, nrhs, ld);
+    });
+}
+#endif
+
+// Least-squares solution of an overdetermined nrows x ncols system by sparse QR with
+// a COLAMD column ordering. A rank deficient matrix is reported as a numerical issue
+// since its solution is not unique.
+template <typename Scalar>
+static int least_squares(const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value, const std::complex<Scalar> *b, const size_t nrows, const size_t ncols, std::complex<Scalar> *x)
+{
+    typedef std::complex<Scalar> C;
+    typedef Eigen::Matrix<C, Eigen::Dynamic, 1> Vector;
+    Eigen::SparseMatrix<C> A(nrows, ncols);
+    set_from_triplets(A, values, rows, cols, n_value);
+    A.makeCompressed();
 
-        re = (b + i)->re;
-        im = (b + i)->im;
-        value = cfloat(re, im);
-        b_[i] = value;
+    Eigen::SparseQR<Eigen::SparseMatrix<C>, Eigen::COLAMDOrdering<int>> solver;
+    solver.compute(A);
+    if (solver.info() != Eigen::Success)
+    {
+        return solver.info();
+    }
+    if (solver.rank() < static_cast<Eigen::Index>(ncols))
+    {
+        return Eigen::NumericalIssue;
     }
 
-    A.setFromTriplets(triplets.begin(), triplets.end());
+    Eigen::Map<const Vector> b_(b, nrows);
+    Eigen::Map<Vector> x_(x, ncols);
+    x_ = solver.solve(b_);
+    return solver.info();
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int least_squares_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, const cmplx64 *b, const size_t nrows, const size_t ncols, cmplx64 *x)
+{
+    return guarded([&]() -> int {
+        return least_squares(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, reinterpret_cast<const cdouble *>(b), nrows, ncols, reinterpret_cast<cdouble *>(x));
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int least_squares_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, const cmplx32 *b, const size_t nrows, const size_t ncols, cmplx32 *x)
+{
+    return guarded([&]() -> int {
+        return least_squares(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, reinterpret_cast<const cfloat *>(b), nrows, ncols, reinterpret_cast<cfloat *>(x));
+    });
+}
+#endif
 
+// Solves a Hermitian positive definite system with a simplicial LDL^H factorization
+// of its lower triangle, the upper triangle is not read. A zero or negative pivot is
+// reported as invalid input: the matrix is not positive definite.
+template <typename Scalar>
+static int solve_hermitian(const std::complex<Scalar> *values, const index_t *rows, const index_t *cols, const size_t n_value, std::complex<Scalar> *b, const size_t size)
+{
+    typedef std::complex<Scalar> C;
+    typedef Eigen::Matrix<C, Eigen::Dynamic, 1> Vector;
+    Eigen::SparseMatrix<C> A(size, size);
+    set_from_triplets(A, values, rows, cols, n_value);
+    A.makeCompressed();
+
+    Eigen::SimplicialLDLT<Eigen::SparseMatrix<C>, Eigen::Lower> solver;
     solver.compute(A);
-    x = solver.solve(b_);
+    if (solver.info() != Eigen::Success)
+    {
+        return Eigen::InvalidInput;
+    }
+    for (Eigen::Index i = 0; i < solver.vectorD().size(); ++i)
+    {
+        if (!(solver.vectorD()[i].real() > 0))
+        {
+            return Eigen::InvalidInput;
+        }
+    }
+
+    Eigen::Map<Vector> b_(b, size);
+    Vector x = solver.solve(b_);
+    b_ = x;
+    return Eigen::Success;
+}
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int solve_hermitian_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return solve_hermitian(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, reinterpret_cast<cdouble *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int solve_hermitian_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return solve_hermitian(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, reinterpret_cast<cfloat *>(b), size);
+    });
+}
+#endif
+
+// Incomplete LU factorization with dual threshold dropping, applied as the
+// preconditioner of the iterative solvers on the Rust side.
+template <typename Scalar>
+struct IlutContext
+{
+    typedef std::complex<Scalar> C;
+    typedef Eigen::Matrix<C, Eigen::Dynamic, 1> Vector;
+    Eigen::IncompleteLUT<C> ilut;
+
+    int compute(const C *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, const Scalar drop_tolerance, const int fill_factor)
+    {
+        Eigen::SparseMatrix<C> A(size, size);
+        set_from_triplets(A, values, rows, cols, n_value);
+        A.makeCompressed();
+        ilut.setDroptol(drop_tolerance);
+        ilut.setFillfactor(fill_factor);
+        ilut.compute(A);
+        return ilut.info();
+    }
+
+    void solve(const C *r, C *z, const size_t size) const
+    {
+        Eigen::Map<const Vector> r_(r, size);
+        Eigen::Map<Vector> z_(z, size);
+        z_ = ilut.solve(r_);
+    }
+};
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" void *ilut_new_cpp(const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, const double drop_tolerance, const int fill_factor, int *info)
+{
+    IlutContext<double> *context = new (std::nothrow) IlutContext<double>();
+    if (context == nullptr)
+    {
+        *info = OUT_OF_MEMORY;
+        return nullptr;
+    }
+    *info = guarded([&] { return context->compute(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, size, drop_tolerance, fill_factor); });
+    return context;
+}
+
+extern "C" void ilut_free_cpp(void *context)
+{
+    delete static_cast<IlutContext<double> *>(context);
+}
+
+extern "C" void ilut_solve_cpp(const void *context, const cmplx64 *r, cmplx64 *z, const size_t size)
+{
+    // A failed application yields NaN, which stops the iterative solver.
+    int info = guarded([&] {
+        static_cast<const IlutContext<double> *>(context)->solve(reinterpret_cast<const cdouble *>(r), reinterpret_cast<cdouble *>(z), size);
+        return 0;
+    });
+    if (info != 0)
+    {
+        std::fill(z, z + size, cmplx64{NAN, NAN});
+    }
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" void *ilut_new_cpp32(const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size, const float drop_tolerance, const int fill_factor, int *info)
+{
+    IlutContext<float> *context = new (std::nothrow) IlutContext<float>();
+    if (context == nullptr)
+    {
+        *info = OUT_OF_MEMORY;
+        return nullptr;
+    }
+    *info = guarded([&] { return context->compute(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, size, drop_tolerance, fill_factor); });
+    return context;
+}
+
+extern "C" void ilut_free_cpp32(void *context)
+{
+    delete static_cast<IlutContext<float> *>(context);
+}
 
-    for (size_t i = 0; i < size; i++)
+extern "C" void ilut_solve_cpp32(const void *context, const cmplx32 *r, cmplx32 *z, const size_t size)
+{
+    // A failed application yields NaN, which stops the iterative solver.
+    int info = guarded([&] {
+        static_cast<const IlutContext<float> *>(context)->solve(reinterpret_cast<const cfloat *>(r), reinterpret_cast<cfloat *>(z), size);
+        return 0;
+    });
+    if (info != 0)
     {
-        re = x[i].real();
-        im = x[i].imag();
-        (b + i)->re = re;
-        (b + i)->im = im;
+        std::fill(z, z + size, cmplx32{NAN, NAN});
     }
-}
\ No newline at end of file
+}
+#endif
+
+// Column orderings selectable at run time, matching `ColumnOrdering` on the Rust side.
+enum
+{
+    ORDERING_COLAMD = 0,
+    ORDERING_AMD = 1,
+    ORDERING_NATURAL = 2
+};
+
+// Like PresetOrdering, the ordering chosen by a context is handed over to the functor
+// default-constructed by analyzePattern through a thread-local.
+static thread_local int selected_ordering = ORDERING_COLAMD;
+
+struct RuntimeOrdering
+{
+    typedef Eigen::PermutationMatrix<Eigen::Dynamic, Eigen::Dynamic, int> PermutationType;
+
+    template <typename MatrixType>
+    void operator()(const MatrixType &mat, PermutationType &perm)
+    {
+        switch (selected_ordering)
+        {
+        case ORDERING_AMD:
+            Eigen::AMDOrdering<int>()(mat, perm);
+            break;
+        case ORDERING_NATURAL:
+            Eigen::NaturalOrdering<int>()(mat, perm);
+            break;
+        default:
+            Eigen::COLAMDOrdering<int>()(mat, perm);
+        }
+    }
+};
+
+// Selects the ordering of the analyses run in its scope, restoring COLAMD on exit,
+// including when the analysis throws.
+struct OrderingScope
+{
+    explicit OrderingScope(const int ordering) { selected_ordering = ordering; }
+    ~OrderingScope() { selected_ordering = ORDERING_COLAMD; }
+};
+
+// Persistent solver state reused across solves: the compressed matrix, the slot of
+// each triplet in it and the solver, whose symbolic analysis is only redone when the
+// pattern changes.
+template <typename Scalar>
+struct SolverContext
+{
+    typedef std::complex<Scalar> C;
+    typedef Eigen::Matrix<C, Eigen::Dynamic, 1> Vector;
+    typedef Eigen::SparseLU<Eigen::SparseMatrix<C>, RuntimeOrdering> Solver;
+
+    std::vector<index_t> rows;
+    std::vector<index_t> cols;
+    std::vector<size_t> slots;
+    Eigen::SparseMatrix<C> A;
+    Solver solver;
+    Vector x;
+    int ordering = ORDERING_COLAMD;
+    bool has_pattern = false;
+    bool factorized = false;
+
+    void update_pattern(const index_t *rows_, const index_t *cols_, const size_t n_value, const size_t size)
+    {
+        if (has_pattern && static_cast<size_t>(A.rows()) == size && rows.size() == n_value &&
+            std::equal(rows.begin(), rows.end(), rows_) && std::equal(cols.begin(), cols.end(), cols_))
+        {
+            return;
+        }
+        // Any step below may throw, so the context stays invalid until the new
+        // pattern is complete and a retry analyzes it again.
+        has_pattern = false;
+        factorized = false;
+        rows.assign(rows_, rows_ + n_value);
+        cols.assign(cols_, cols_ + n_value);
+
+        std::vector<C> ones(n_value, C(1, 0));
+        A.resize(size, size);
+        set_from_triplets(A, ones.data(), rows_, cols_, n_value);
+        A.makeCompressed();
+
+        slots.resize(n_value);
+        for (size_t i = 0; i < n_value; i++)
+        {
+            slots[i] = &A.coeffRef(rows[i], cols[i]) - A.valuePtr();
+        }
+        {
+            OrderingScope scope(ordering);
+            solver.analyzePattern(A);
+        }
+        x.resize(size);
+        has_pattern = true;
+    }
+
+    int factorize(const C *values, const index_t *rows_, const index_t *cols_, const size_t n_value, const size_t size)
+    {
+        update_pattern(rows_, cols_, n_value, size);
+        factorized = false;
+        std::fill(A.valuePtr(), A.valuePtr() + A.nonZeros(), C(0, 0));
+        for (size_t i = 0; i < n_value; i++)
+        {
+            A.valuePtr()[slots[i]] += values[i];
+        }
+        solver.factorize(A);
+        factorized = solver.info() == Eigen::Success;
+        return solver.info();
+    }
+
+    void set_pivot_threshold(const Scalar threshold)
+    {
+        solver.setPivotThreshold(threshold);
+    }
+
+    // A new ordering takes effect at the next analysis.
+    void set_ordering(const int ordering_)
+    {
+        if (ordering_ != ordering)
+        {
+            ordering = ordering_;
+            has_pattern = false;
+            factorized = false;
+        }
+    }
+
+    // Solves with the last successful factorization.
+    int solve_factorized(C *b, const size_t size)
+    {
+        if (!factorized || static_cast<size_t>(A.rows()) != size)
+        {
+            return Eigen::InvalidInput;
+        }
+        Eigen::Map<Vector> b_(b, size);
+        x = solver.solve(b_);
+        b_ = x;
+        return Eigen::Success;
+    }
+
+    // Solves with the last successful factorization without touching the context,
+    // so concurrent callers may share it.
+    int solve_shared(C *b, const size_t size) const
+    {
+        if (!factorized || static_cast<size_t>(A.rows()) != size)
+        {
+            return Eigen::InvalidInput;
+        }
+        Eigen::Map<Vector> b_(b, size);
+        Vector x_ = solver.solve(b_);
+        b_ = x_;
+        return Eigen::Success;
+    }
+
+    // Solves with the transpose, or the adjoint, of the last successful factorization.
+    // The views of Eigen only read the factors, whatever the constness of their accessor.
+    int solve_transposed(C *b, const size_t size, const bool conjugate) const
+    {
+        if (!factorized || static_cast<size_t>(A.rows()) != size)
+        {
+            return Eigen::InvalidInput;
+        }
+        Solver &lu = const_cast<Solver &>(solver);
+        Eigen::Map<Vector> b_(b, size);
+        Vector x_ = conjugate ? Vector(lu.adjoint().solve(b_)) : Vector(lu.transpose().solve(b_));
+        b_ = x_;
+        return Eigen::Success;
+    }
+
+    // The determinant of the last successful factorization.
+    int determinant(C *det) const
+    {
+        if (!factorized)
+        {
+            return Eigen::InvalidInput;
+        }
+        *det = const_cast<Solver &>(solver).determinant();
+        return Eigen::Success;
+    }
+
+    // The logarithm of the absolute value and the phase of the determinant of the last
+    // successful factorization, accumulated over the diagonal of U so that neither
+    // overflows.
+    int log_determinant(Scalar *log_abs, C *phase) const
+    {
+        typedef typename Solver::SCMatrix SCMatrix;
+        if (!factorized)
+        {
+            return Eigen::InvalidInput;
+        }
+        // The diagonal blocks of U are stored in the supernodes of L.
+        const SCMatrix &L = solver.matrixL().m_mapL;
+        Scalar sum = 0;
+        C unit(1, 0);
+        for (Eigen::Index j = 0; j < A.cols(); ++j)
+        {
+            for (typename SCMatrix::InnerIterator it(L, j); it; ++it)
+            {
+                if (it.index() == j)
+                {
+                    Scalar magnitude = std::abs(it.value());
+                    sum += std::log(magnitude);
+                    unit *= it.value() / magnitude;
+                    break;
+                }
+            }
+        }
+        if (solver.rowsPermutation().determinant() * solver.colsPermutation().determinant() < 0)
+        {
+            unit = -unit;
+        }
+        *log_abs = sum;
+        *phase = unit / std::abs(unit);
+        return Eigen::Success;
+    }
+
+    // Calls visit(lower, row, col, value) for each stored entry of L, with its unit
+    // diagonal, and of U, in the permuted numbering of the factorization.
+    template <typename Visit>
+    void visit_factors(Visit visit) const
+    {
+        auto U = solver.matrixU();
+        typedef typename std::decay<decltype(U.m_mapL)>::type SupernodalType;
+        typedef typename std::decay<decltype(U.m_mapU)>::type UpperType;
+        for (Eigen::Index j = 0; j < A.cols(); ++j)
+        {
+            visit(true, j, j, C(1, 0));
+            // The supernodes hold the columns of L and the diagonal blocks of U.
+            for (typename SupernodalType::InnerIterator it(U.m_mapL, j); it; ++it)
+            {
+                visit(it.index() > j, it.index(), j, it.value());
+            }
+            for (typename UpperType::InnerIterator it(U.m_mapU, j); it; ++it)
+            {
+                visit(false, it.index(), j, it.value());
+            }
+        }
+    }
+
+    int factor_counts(size_t *l_nnz, size_t *u_nnz) const
+    {
+        if (!factorized)
+        {
+            return Eigen::InvalidInput;
+        }
+        *l_nnz = 0;
+        *u_nnz = 0;
+        visit_factors([&](bool lower, Eigen::Index, Eigen::Index, const C &) { ++*(lower ? l_nnz : u_nnz); });
+        return Eigen::Success;
+    }
+
+    // Copies the factors, sized by factor_counts, and the row and column permutations.
+    int factors(index_t *l_rows, index_t *l_cols, C *l_values, index_t *u_rows, index_t *u_cols, C *u_values, size_t *row_perm, size_t *col_perm) const
+    {
+        if (!factorized)
+        {
+            return Eigen::InvalidInput;
+        }
+        size_t l = 0, u = 0;
+        visit_factors([&](bool lower, Eigen::Index row, Eigen::Index col, const C &value)
+                      {
+                          size_t &k = lower ? l : u;
+                          (lower ? l_rows : u_rows)[k] = static_cast<index_t>(row);
+                          (lower ? l_cols : u_cols)[k] = static_cast<index_t>(col);
+                          (lower ? l_values : u_values)[k] = value;
+                          ++k; });
+        for (Eigen::Index i = 0; i < A.cols(); ++i)
+        {
+            row_perm[i] = static_cast<size_t>(solver.rowsPermutation().indices()[i]);
+            col_perm[i] = static_cast<size_t>(solver.colsPermutation().indices()[i]);
+        }
+        return Eigen::Success;
+    }
+
+    // Entries of A, L and U and the operations of the numeric factorization: each
+    // column k of L scales its off-diagonal entries and updates them with row k of U.
+    int factor_stats(size_t *nnz_a, size_t *nnz_l, size_t *nnz_u, double *flops) const
+    {
+        if (!factorized)
+        {
+            return Eigen::InvalidInput;
+        }
+        std::vector<double> below(A.cols(), 0.), right(A.cols(), 0.);
+        *nnz_l = 0;
+        *nnz_u = 0;
+        visit_factors([&](bool lower, Eigen::Index row, Eigen::Index col, const C &)
+                      {
+                          if (lower)
+                          {
+                              ++*nnz_l;
+                              below[col] += row > col ? 1. : 0.;
+                          }
+                          else
+                          {
+                              ++*nnz_u;
+                              right[row] += col > row ? 1. : 0.;
+                          } });
+        *nnz_a = static_cast<size_t>(A.nonZeros());
+        *flops = 0.;
+        for (Eigen::Index k = 0; k < A.cols(); ++k)
+        {
+            *flops += below[k] + 2. * below[k] * right[k];
+        }
+        return Eigen::Success;
+    }
+
+    // Approximate heap usage of the buffers and of the factors.
+    size_t memory_usage() const
+    {
+        typedef typename Eigen::SparseMatrix<C>::StorageIndex Index;
+        size_t bytes = (rows.capacity() + cols.capacity()) * sizeof(index_t) + slots.capacity() * sizeof(size_t);
+        bytes += A.nonZeros() * (sizeof(C) + sizeof(Index)) + (A.outerSize() + 1) * sizeof(Index);
+        bytes += x.size() * sizeof(C);
+        if (factorized)
+        {
+            bytes += factor_nonzeros(solver) * (sizeof(C) + sizeof(Index));
+        }
+        return bytes;
+    }
+
+    int solve(const C *values, const index_t *rows_, const index_t *cols_, const size_t n_value, C *b, const size_t size)
+    {
+        int info = factorize(values, rows_, cols_, n_value, size);
+        if (info != Eigen::Success)
+        {
+            return info;
+        }
+        return solve_factorized(b, size);
+    }
+};
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" void *context_new_cpp()
+{
+    return new (std::nothrow) SolverContext<double>();
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" void *context_new_cpp32()
+{
+    return new (std::nothrow) SolverContext<float>();
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" void context_free_cpp(void *context)
+{
+    delete static_cast<SolverContext<double> *>(context);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" void context_free_cpp32(void *context)
+{
+    delete static_cast<SolverContext<float> *>(context);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_solve_cpp(void *context, const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<double> *>(context)->solve(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, reinterpret_cast<cdouble *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_solve_cpp32(void *context, const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<float> *>(context)->solve(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, reinterpret_cast<cfloat *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_factorize_cpp(void *context, const cmplx64 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<double> *>(context)->factorize(reinterpret_cast<const cdouble *>(values), rows, cols, n_value, size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_factorize_cpp32(void *context, const cmplx32 *values, const index_t *rows, const index_t *cols, const size_t n_value, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<float> *>(context)->factorize(reinterpret_cast<const cfloat *>(values), rows, cols, n_value, size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_solve_factorized_cpp(void *context, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<double> *>(context)->solve_factorized(reinterpret_cast<cdouble *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_solve_factorized_cpp32(void *context, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<SolverContext<float> *>(context)->solve_factorized(reinterpret_cast<cfloat *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_solve_shared_cpp(const void *context, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->solve_shared(reinterpret_cast<cdouble *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_solve_shared_cpp32(const void *context, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->solve_shared(reinterpret_cast<cfloat *>(b), size);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_solve_transposed_cpp(const void *context, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->solve_transposed(reinterpret_cast<cdouble *>(b), size, false);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_solve_transposed_cpp32(const void *context, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->solve_transposed(reinterpret_cast<cfloat *>(b), size, false);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_solve_adjoint_cpp(const void *context, cmplx64 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->solve_transposed(reinterpret_cast<cdouble *>(b), size, true);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_solve_adjoint_cpp32(const void *context, cmplx32 *b, const size_t size)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->solve_transposed(reinterpret_cast<cfloat *>(b), size, true);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_determinant_cpp(const void *context, cmplx64 *det)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->determinant(reinterpret_cast<cdouble *>(det));
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_determinant_cpp32(const void *context, cmplx32 *det)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->determinant(reinterpret_cast<cfloat *>(det));
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_log_determinant_cpp(const void *context, double *log_abs, cmplx64 *phase)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->log_determinant(log_abs, reinterpret_cast<cdouble *>(phase));
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_log_determinant_cpp32(const void *context, float *log_abs, cmplx32 *phase)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->log_determinant(log_abs, reinterpret_cast<cfloat *>(phase));
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" void context_set_pivot_threshold_cpp(void *context, const double threshold)
+{
+    static_cast<SolverContext<double> *>(context)->set_pivot_threshold(threshold);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" void context_set_pivot_threshold_cpp32(void *context, const float threshold)
+{
+    static_cast<SolverContext<float> *>(context)->set_pivot_threshold(threshold);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" void context_set_ordering_cpp(void *context, const int ordering)
+{
+    static_cast<SolverContext<double> *>(context)->set_ordering(ordering);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" void context_set_ordering_cpp32(void *context, const int ordering)
+{
+    static_cast<SolverContext<float> *>(context)->set_ordering(ordering);
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" int context_factor_stats_cpp(const void *context, size_t *nnz_a, size_t *nnz_l, size_t *nnz_u, double *flops)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->factor_stats(nnz_a, nnz_l, nnz_u, flops);
+    });
+}
+
+extern "C" int context_factor_counts_cpp(const void *context, size_t *l_nnz, size_t *u_nnz)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->factor_counts(l_nnz, u_nnz);
+    });
+}
+
+extern "C" int context_factors_cpp(const void *context, index_t *l_rows, index_t *l_cols, cmplx64 *l_values, index_t *u_rows, index_t *u_cols, cmplx64 *u_values, size_t *row_perm, size_t *col_perm)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<double> *>(context)->factors(l_rows, l_cols, reinterpret_cast<cdouble *>(l_values), u_rows, u_cols, reinterpret_cast<cdouble *>(u_values), row_perm, col_perm);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" int context_factor_stats_cpp32(const void *context, size_t *nnz_a, size_t *nnz_l, size_t *nnz_u, double *flops)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->factor_stats(nnz_a, nnz_l, nnz_u, flops);
+    });
+}
+
+extern "C" int context_factor_counts_cpp32(const void *context, size_t *l_nnz, size_t *u_nnz)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->factor_counts(l_nnz, u_nnz);
+    });
+}
+
+extern "C" int context_factors_cpp32(const void *context, index_t *l_rows, index_t *l_cols, cmplx32 *l_values, index_t *u_rows, index_t *u_cols, cmplx32 *u_values, size_t *row_perm, size_t *col_perm)
+{
+    return guarded([&]() -> int {
+        return static_cast<const SolverContext<float> *>(context)->factors(l_rows, l_cols, reinterpret_cast<cfloat *>(l_values), u_rows, u_cols, reinterpret_cast<cfloat *>(u_values), row_perm, col_perm);
+    });
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F64
+extern "C" size_t context_memory_usage_cpp(const void *context)
+{
+    return static_cast<const SolverContext<double> *>(context)->memory_usage();
+}
+#endif
+
+#ifdef SPARSE_COMPLEX_F32
+extern "C" size_t context_memory_usage_cpp32(const void *context)
+{
+    return static_cast<const SolverContext<float> *>(context)->memory_usage();
+}
+#endif
+
+extern "C" void eigen_version_cpp(int *world, int *major, int *minor)
+{
+    *world = EIGEN_WORLD_VERSION;
+    *major = EIGEN_MAJOR_VERSION;
+    *minor = EIGEN_MINOR_VERSION;
+}
+
+// Without OpenMP Eigen always runs on a single thread and ignores the setting.
+extern "C" void eigen_set_num_threads_cpp(int threads)
+{
+    Eigen::setNbThreads(threads);
+}
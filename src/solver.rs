@@ -1,20 +1,338 @@
-use num::complex::{Complex32, Complex64};
+use crate::index::StorageIndex;
+use crate::SolveError;
+#[cfg(feature = "f32")]
+use num::complex::Complex32;
+#[cfg(feature = "f64")]
+use num::complex::Complex64;
+use std::os::raw::c_int;
+#[cfg(any(feature = "f64", feature = "f32"))]
+use std::os::raw::c_void;
 
+/// Values of `Eigen::ComputationInfo` returned by the solver entry points.
+pub const SUCCESS: c_int = 0;
+pub const NUMERICAL_ISSUE: c_int = 1;
+pub const NO_CONVERGENCE: c_int = 2;
+pub const INVALID_INPUT: c_int = 3;
+/// Exceptions caught at the boundary of the C++ entry points.
+pub const OUT_OF_MEMORY: c_int = 4;
+pub const INTERNAL_ERROR: c_int = 5;
+
+extern "C" {
+    pub fn eigen_version_cpp(world: *mut c_int, major: *mut c_int, minor: *mut c_int);
+    #[cfg(feature = "openmp")]
+    pub fn eigen_set_num_threads_cpp(threads: c_int);
+    pub fn analyze_pattern_cpp(
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+        col_perm: *mut usize,
+    ) -> c_int;
+}
+
+#[cfg(feature = "f64")]
 extern "C" {
     pub fn solve_cpp(
         a_matrix: *const Complex64,
-        rows: *const usize,
-        cols: *const usize,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex64,
+        size: usize,
+    ) -> c_int;
+    pub fn pivot_growth_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+        growth: *mut f64,
+    ) -> c_int;
+    pub fn solve_ordered_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex64,
+        size: usize,
+        col_perm: *const usize,
+    ) -> c_int;
+    pub fn solve_strided_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex64,
+        size: usize,
+        nrhs: usize,
+        ld: usize,
+    ) -> c_int;
+    pub fn ilut_new_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+        drop_tolerance: f64,
+        fill_factor: c_int,
+        info: *mut c_int,
+    ) -> *mut c_void;
+    pub fn ilut_free_cpp(context: *mut c_void);
+    pub fn ilut_solve_cpp(context: *const c_void, r: *const Complex64, z: *mut Complex64, size: usize);
+    pub fn solve_hermitian_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
         n_value: usize,
         b: *mut Complex64,
         size: usize,
-    );
+    ) -> c_int;
+    pub fn least_squares_cpp(
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *const Complex64,
+        nrows: usize,
+        ncols: usize,
+        x: *mut Complex64,
+    ) -> c_int;
+    pub fn context_new_cpp() -> *mut c_void;
+    pub fn context_free_cpp(context: *mut c_void);
+    pub fn context_solve_cpp(
+        context: *mut c_void,
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex64,
+        size: usize,
+    ) -> c_int;
+    pub fn context_factorize_cpp(
+        context: *mut c_void,
+        a_matrix: *const Complex64,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+    ) -> c_int;
+    pub fn context_solve_factorized_cpp(context: *mut c_void, b: *mut Complex64, size: usize) -> c_int;
+    pub fn context_solve_shared_cpp(context: *const c_void, b: *mut Complex64, size: usize) -> c_int;
+    pub fn context_solve_transposed_cpp(context: *const c_void, b: *mut Complex64, size: usize) -> c_int;
+    pub fn context_solve_adjoint_cpp(context: *const c_void, b: *mut Complex64, size: usize) -> c_int;
+    pub fn context_determinant_cpp(context: *const c_void, det: *mut Complex64) -> c_int;
+    pub fn context_log_determinant_cpp(context: *const c_void, log_abs: *mut f64, phase: *mut Complex64) -> c_int;
+    pub fn context_set_pivot_threshold_cpp(context: *mut c_void, threshold: f64);
+    pub fn context_set_ordering_cpp(context: *mut c_void, ordering: c_int);
+    pub fn context_memory_usage_cpp(context: *const c_void) -> usize;
+    pub fn context_factor_stats_cpp(
+        context: *const c_void,
+        nnz_a: *mut usize,
+        nnz_l: *mut usize,
+        nnz_u: *mut usize,
+        flops: *mut f64,
+    ) -> c_int;
+    pub fn context_factor_counts_cpp(context: *const c_void, l_nnz: *mut usize, u_nnz: *mut usize) -> c_int;
+    pub fn context_factors_cpp(
+        context: *const c_void,
+        l_rows: *mut StorageIndex,
+        l_cols: *mut StorageIndex,
+        l_values: *mut Complex64,
+        u_rows: *mut StorageIndex,
+        u_cols: *mut StorageIndex,
+        u_values: *mut Complex64,
+        row_perm: *mut usize,
+        col_perm: *mut usize,
+    ) -> c_int;
+}
+
+#[cfg(feature = "f32")]
+extern "C" {
     pub fn solve_cpp32(
         a_matrix: *const Complex32,
-        rows: *const usize,
-        cols: *const usize,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex32,
+        size: usize,
+    ) -> c_int;
+    pub fn pivot_growth_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+        growth: *mut f32,
+    ) -> c_int;
+    pub fn solve_ordered_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex32,
+        size: usize,
+        col_perm: *const usize,
+    ) -> c_int;
+    pub fn solve_strided_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex32,
+        size: usize,
+        nrhs: usize,
+        ld: usize,
+    ) -> c_int;
+    pub fn ilut_new_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+        drop_tolerance: f32,
+        fill_factor: c_int,
+        info: *mut c_int,
+    ) -> *mut c_void;
+    pub fn ilut_free_cpp32(context: *mut c_void);
+    pub fn ilut_solve_cpp32(context: *const c_void, r: *const Complex32, z: *mut Complex32, size: usize);
+    pub fn solve_hermitian_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *mut Complex32,
+        size: usize,
+    ) -> c_int;
+    pub fn least_squares_cpp32(
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        b: *const Complex32,
+        nrows: usize,
+        ncols: usize,
+        x: *mut Complex32,
+    ) -> c_int;
+    pub fn context_new_cpp32() -> *mut c_void;
+    pub fn context_free_cpp32(context: *mut c_void);
+    pub fn context_solve_cpp32(
+        context: *mut c_void,
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
         n_value: usize,
         b: *mut Complex32,
         size: usize,
-    );
+    ) -> c_int;
+    pub fn context_factorize_cpp32(
+        context: *mut c_void,
+        a_matrix: *const Complex32,
+        rows: *const StorageIndex,
+        cols: *const StorageIndex,
+        n_value: usize,
+        size: usize,
+    ) -> c_int;
+    pub fn context_solve_factorized_cpp32(context: *mut c_void, b: *mut Complex32, size: usize) -> c_int;
+    pub fn context_solve_shared_cpp32(context: *const c_void, b: *mut Complex32, size: usize) -> c_int;
+    pub fn context_solve_transposed_cpp32(context: *const c_void, b: *mut Complex32, size: usize) -> c_int;
+    pub fn context_solve_adjoint_cpp32(context: *const c_void, b: *mut Complex32, size: usize) -> c_int;
+    pub fn context_determinant_cpp32(context: *const c_void, det: *mut Complex32) -> c_int;
+    pub fn context_log_determinant_cpp32(context: *const c_void, log_abs: *mut f32, phase: *mut Complex32) -> c_int;
+    pub fn context_set_pivot_threshold_cpp32(context: *mut c_void, threshold: f32);
+    pub fn context_set_ordering_cpp32(context: *mut c_void, ordering: c_int);
+    pub fn context_memory_usage_cpp32(context: *const c_void) -> usize;
+    pub fn context_factor_stats_cpp32(
+        context: *const c_void,
+        nnz_a: *mut usize,
+        nnz_l: *mut usize,
+        nnz_u: *mut usize,
+        flops: *mut f64,
+    ) -> c_int;
+    pub fn context_factor_counts_cpp32(context: *const c_void, l_nnz: *mut usize, u_nnz: *mut usize) -> c_int;
+    pub fn context_factors_cpp32(
+        context: *const c_void,
+        l_rows: *mut StorageIndex,
+        l_cols: *mut StorageIndex,
+        l_values: *mut Complex32,
+        u_rows: *mut StorageIndex,
+        u_cols: *mut StorageIndex,
+        u_values: *mut Complex32,
+        row_perm: *mut usize,
+        col_perm: *mut usize,
+    ) -> c_int;
+}
+
+/// Owning handle of a C++ `SolverContext`, or of another Eigen object with its own
+/// deleter, freed on drop.
+#[cfg(any(feature = "f64", feature = "f32"))]
+pub struct Context {
+    ptr: *mut c_void,
+    free: unsafe extern "C" fn(*mut c_void),
+}
+
+#[cfg(any(feature = "f64", feature = "f32"))]
+impl Context {
+    /// Returns `Err(SolveError::OutOfMemory)` if the context cannot be allocated.
+    #[cfg(feature = "f64")]
+    pub fn f64() -> Result<Self, SolveError> {
+        let ptr = unsafe { context_new_cpp() };
+        if ptr.is_null() {
+            return Err(SolveError::OutOfMemory);
+        }
+        Ok(Context {
+            ptr,
+            free: context_free_cpp,
+        })
+    }
+
+    /// Returns `Err(SolveError::OutOfMemory)` if the context cannot be allocated.
+    #[cfg(feature = "f32")]
+    pub fn f32() -> Result<Self, SolveError> {
+        let ptr = unsafe { context_new_cpp32() };
+        if ptr.is_null() {
+            return Err(SolveError::OutOfMemory);
+        }
+        Ok(Context {
+            ptr,
+            free: context_free_cpp32,
+        })
+    }
+
+    /// Takes ownership of ```ptr```, to be released with ```free```.
+    pub unsafe fn from_raw(ptr: *mut c_void, free: unsafe extern "C" fn(*mut c_void)) -> Self {
+        Context { ptr, free }
+    }
+
+    pub fn as_ptr(&mut self) -> *mut c_void {
+        self.ptr
+    }
+
+    pub fn as_const_ptr(&self) -> *const c_void {
+        self.ptr
+    }
+}
+
+#[cfg(any(feature = "f64", feature = "f32"))]
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe { (self.free)(self.ptr) }
+    }
+}
+
+// The context owns plain Eigen objects with no thread affinity.
+#[cfg(any(feature = "f64", feature = "f32"))]
+unsafe impl Send for Context {}
+
+/// Maps a non-successful `Eigen::ComputationInfo`, or a caught C++ exception, to an
+/// error. The LU solvers only report a numerical issue on a zero pivot.
+pub fn info_to_result(info: c_int) -> Result<(), SolveError> {
+    match info {
+        SUCCESS => Ok(()),
+        NUMERICAL_ISSUE => Err(SolveError::Singular),
+        NO_CONVERGENCE => Err(SolveError::NoConvergence),
+        INVALID_INPUT => Err(SolveError::InvalidInput("invalid input for the solver")),
+        OUT_OF_MEMORY => Err(SolveError::OutOfMemory),
+        INTERNAL_ERROR => Err(SolveError::Internal),
+        _ => Err(SolveError::InvalidInput("unknown solver failure")),
+    }
 }
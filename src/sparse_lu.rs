@@ -0,0 +1,590 @@
+use crate::cancel::Watcher;
+use crate::compressed::compress;
+use crate::index::AsIndex;
+use crate::progress::{Counter, Phase};
+use crate::{ComplexMatrix, Permutation, SolveError};
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use num::complex::Complex;
+use num_traits::float::Float;
+use num_traits::NumCast;
+
+const NONE: usize = usize::MAX;
+
+const MAGIC: &[u8; 4] = b"SCLU";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 4 + 4 + 4 + 4 * 8;
+
+/// The smallest ratio between the diagonal candidate and the largest candidate of
+/// a column for the diagonal to be kept as pivot.
+const DIAGONAL_PREFERENCE: f64 = 0.1;
+
+/// A sparse LU factorization `PAQ = LU` computed in pure Rust, created by
+/// ```ComplexMatrix::factorize_sparse```.
+///
+/// The columns are ordered by minimum degree on the pattern of `A + Aᵀ` and
+/// eliminated left-looking, each column of `L` and `U` coming from a sparse
+/// triangular solve with the previous columns of `L`. The rows are chosen by partial
+/// pivoting, keeping the diagonal when it is within a factor 10 of the largest
+/// candidate. This is the sparse solver of ```solve``` without the `eigen` feature.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseLu<T: Float> {
+    /// The row of `A` chosen as pivot at each step.
+    pivots: Vec<usize>,
+    /// The column of `A` eliminated at each step.
+    columns: Vec<usize>,
+    /// The strictly lower part of `L` by step, with the rows of `A`.
+    l_indptr: Vec<usize>,
+    l_indices: Vec<usize>,
+    l_values: Vec<Complex<T>>,
+    /// The strictly upper part of `U` by step, with the steps of its rows.
+    u_indptr: Vec<usize>,
+    u_indices: Vec<usize>,
+    u_values: Vec<Complex<T>>,
+    diagonal: Vec<Complex<T>>,
+    max_abs: T,
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Compute a sparse LU factorization of the matrix in pure Rust, to solve it
+    /// repeatedly without the Eigen solver.
+    ///
+    /// Returns `Err` if the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let lu = m.factorize_sparse().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(1., 1.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn factorize_sparse(&self) -> Result<SparseLu<T>, SolveError> {
+        let n = self.dimension();
+        let by_col = compress(n, &self.cols, &self.rows, &self.entries);
+        let columns = minimum_degree(n, &self.rows, &self.cols);
+        let zero = Complex::new(T::zero(), T::zero());
+        let threshold = T::from(DIAGONAL_PREFERENCE).unwrap();
+
+        let mut lu = SparseLu {
+            pivots: Vec::with_capacity(n),
+            columns,
+            l_indptr: vec![0],
+            l_indices: Vec::new(),
+            l_values: Vec::new(),
+            u_indptr: vec![0],
+            u_indices: Vec::new(),
+            u_values: Vec::new(),
+            diagonal: Vec::with_capacity(n),
+            max_abs: by_col.values.iter().fold(T::zero(), |max, v| max.max(v.norm())),
+        };
+        let mut step_of = vec![NONE; n];
+        let mut x = vec![zero; n];
+        let mut mark = vec![NONE; n];
+        let mut stack = Vec::new();
+        let mut reach = Vec::new();
+        let watcher = Watcher::new();
+        let counter = Counter::new(Phase::Factorization, n);
+        for k in 0..n {
+            watcher.check()?;
+            let j = lu.columns[k];
+
+            // The rows reached from column j through the columns of L, in postorder.
+            reach.clear();
+            for (i, v) in by_col.row(j) {
+                x[i] = x[i] + *v;
+                if mark[i] == k {
+                    continue;
+                }
+                mark[i] = k;
+                stack.push((i, 0));
+                while let Some(&(i, p)) = stack.last() {
+                    let s = step_of[i];
+                    let end = if s == NONE { 0 } else { lu.l_indptr[s + 1] - lu.l_indptr[s] };
+                    let next = (p..end)
+                        .map(|p| (p, lu.l_indices[lu.l_indptr[s] + p]))
+                        .find(|(_, r)| mark[*r] != k);
+                    let top = stack.len() - 1;
+                    match next {
+                        Some((p, r)) => {
+                            stack[top].1 = p + 1;
+                            mark[r] = k;
+                            stack.push((r, 0));
+                        }
+                        None => {
+                            stack.pop();
+                            reach.push(i);
+                        }
+                    }
+                }
+            }
+
+            // Sparse triangular solve in topological order.
+            for &i in reach.iter().rev() {
+                let s = step_of[i];
+                if s == NONE {
+                    continue;
+                }
+                let xi = x[i];
+                for p in lu.l_indptr[s]..lu.l_indptr[s + 1] {
+                    let r = lu.l_indices[p];
+                    x[r] = x[r] - lu.l_values[p] * xi;
+                }
+            }
+
+            let (mut pivot, mut max) = (NONE, T::zero());
+            for &i in &reach {
+                if step_of[i] == NONE && (pivot == NONE || x[i].norm() > max) {
+                    pivot = i;
+                    max = x[i].norm();
+                }
+            }
+            if step_of[j] == NONE && mark[j] == k && x[j].norm() >= threshold * max {
+                pivot = j;
+            }
+            if pivot == NONE || max == T::zero() || !max.is_finite() {
+                return Err(SolveError::Singular);
+            }
+
+            let d = x[pivot];
+            for &i in &reach {
+                if step_of[i] != NONE {
+                    lu.u_indices.push(step_of[i]);
+                    lu.u_values.push(x[i]);
+                } else if i != pivot {
+                    lu.l_indices.push(i);
+                    lu.l_values.push(x[i] / d);
+                }
+                x[i] = zero;
+            }
+            lu.l_indptr.push(lu.l_indices.len());
+            lu.u_indptr.push(lu.u_indices.len());
+            lu.diagonal.push(d);
+            lu.pivots.push(pivot);
+            step_of[pivot] = k;
+            counter.tick();
+        }
+        Ok(lu)
+    }
+}
+
+impl<T: Float> SparseLu<T> {
+    /// Assembles the factorization from the factors `PAQ = LU` of another solver,
+    /// where ```pivots[k]``` and ```columns[k]``` are the row and column of `A` at step
+    /// ```k``` and ```max_abs``` is the largest entry modulus of `A`. The diagonal of `L`
+    /// is taken to be one.
+    ///
+    /// Returns `Err` if the factors are not triangular or `U` has a zero diagonal.
+    #[cfg(feature = "eigen")]
+    pub(crate) fn from_factors(
+        lower: &ComplexMatrix<T>,
+        upper: &ComplexMatrix<T>,
+        pivots: Vec<usize>,
+        columns: Vec<usize>,
+        max_abs: T,
+    ) -> Result<Self, SolveError> {
+        use core::cmp::Ordering;
+
+        let n = pivots.len();
+        let invalid = SolveError::InvalidInput("the factors are not triangular");
+        if [lower, upper].iter().any(|m| m.dimension() > n) {
+            return Err(invalid);
+        }
+        let lower = compress(n, &lower.cols, &lower.rows, &lower.entries);
+        let upper = compress(n, &upper.cols, &upper.rows, &upper.entries);
+        let zero = Complex::new(T::zero(), T::zero());
+        let mut lu = SparseLu {
+            l_indptr: vec![0],
+            l_indices: Vec::new(),
+            l_values: Vec::new(),
+            u_indptr: vec![0],
+            u_indices: Vec::new(),
+            u_values: Vec::new(),
+            diagonal: vec![zero; n],
+            pivots,
+            columns,
+            max_abs,
+        };
+        for k in 0..n {
+            for (r, v) in lower.row(k) {
+                match r.cmp(&k) {
+                    Ordering::Less => return Err(invalid),
+                    Ordering::Equal => {}
+                    Ordering::Greater => {
+                        lu.l_indices.push(lu.pivots[r]);
+                        lu.l_values.push(*v);
+                    }
+                }
+            }
+            for (s, v) in upper.row(k) {
+                match s.cmp(&k) {
+                    Ordering::Less => {
+                        lu.u_indices.push(s);
+                        lu.u_values.push(*v);
+                    }
+                    Ordering::Equal => lu.diagonal[k] = *v,
+                    Ordering::Greater => return Err(invalid),
+                }
+            }
+            if lu.diagonal[k] == zero {
+                return Err(SolveError::Singular);
+            }
+            lu.l_indptr.push(lu.l_indices.len());
+            lu.u_indptr.push(lu.u_indices.len());
+        }
+        Ok(lu)
+    }
+
+    /// The order of the factorized matrix.
+    pub fn size(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    /// The number of stored entries of `L` and `U`, excluding the unit diagonal of `L`.
+    pub fn nnz(&self) -> usize {
+        self.l_values.len() + self.u_values.len() + self.diagonal.len()
+    }
+
+    /// The row permutation `P`: row ```i``` of `A` is row ```row_permutation().get(i)```
+    /// of `LU`.
+    pub fn row_permutation(&self) -> Permutation {
+        Permutation::from_steps(&self.pivots)
+    }
+
+    /// The column permutation `Q`: column ```j``` of `A` is column
+    /// ```column_permutation().get(j)``` of `LU`.
+    pub fn column_permutation(&self) -> Permutation {
+        Permutation::from_steps(&self.columns)
+    }
+
+    /// Returns the pivot growth factor `max|U| / max|A|`, see
+    /// ```ComplexMatrix::pivot_growth```.
+    pub fn pivot_growth(&self) -> T {
+        if self.max_abs == T::zero() {
+            return T::zero();
+        }
+        let max_u = self
+            .u_values
+            .iter()
+            .chain(self.diagonal.iter())
+            .fold(T::zero(), |max, v| max.max(v.norm()));
+        max_u / self.max_abs
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let n = self.size();
+        if b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let mut y = vec![Complex::new(T::zero(), T::zero()); n];
+        for k in 0..n {
+            let yk = b[self.pivots[k]];
+            y[k] = yk;
+            for p in self.l_indptr[k]..self.l_indptr[k + 1] {
+                let r = self.l_indices[p];
+                b[r] = b[r] - self.l_values[p] * yk;
+            }
+        }
+        for k in (0..n).rev() {
+            let zk = y[k] / self.diagonal[k];
+            y[k] = zk;
+            for p in self.u_indptr[k]..self.u_indptr[k + 1] {
+                let s = self.u_indices[p];
+                y[s] = y[s] - self.u_values[p] * zk;
+            }
+        }
+        for (j, yk) in self.columns.iter().zip(y) {
+            b[*j] = yk;
+        }
+        Ok(())
+    }
+
+    /// Solve the system `Aᴴx=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_adjoint(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        let n = self.size();
+        if b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        // Uᴴ is lower triangular, with the columns of U as its rows.
+        let mut y = self.columns.iter().map(|j| b[*j]).collect::<Vec<_>>();
+        for k in 0..n {
+            let sum = (self.u_indptr[k]..self.u_indptr[k + 1])
+                .fold(y[k], |acc, p| acc - self.u_values[p].conj() * y[self.u_indices[p]]);
+            y[k] = sum / self.diagonal[k].conj();
+        }
+        // The rows of L are those of A, all solved before the step that reads them.
+        for k in (0..n).rev() {
+            let sum = (self.l_indptr[k]..self.l_indptr[k + 1])
+                .fold(y[k], |acc, p| acc - self.l_values[p].conj() * b[self.l_indices[p]]);
+            b[self.pivots[k]] = sum;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Float> SparseLu<T> {
+    /// Serialize the factors and permutations into a portable little-endian byte
+    /// buffer, to reuse an expensive factorization in another run.
+    ///
+    /// The values are stored as `f64`, so the factorization can only be restored with
+    /// the same scalar type. An Eigen ```ComplexLu``` is serialized through
+    /// ```ComplexLu::to_sparse_lu```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SparseLu};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(2., 0.));
+    /// m.add_element(1, 0, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let lu = m.factorize_sparse().unwrap();
+    ///
+    /// let restored = SparseLu::<f64>::from_bytes(&lu.to_bytes()).unwrap();
+    /// assert_eq!(restored, lu);
+    ///```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let n = self.size();
+        let (nnz_l, nnz_u) = (self.l_values.len(), self.u_values.len());
+        let words = 6 * n + 2 + 3 * nnz_l + 3 * nnz_u;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 8 * words);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(core::mem::size_of::<T>() as u32).to_le_bytes());
+        for value in [n, nnz_l, nnz_u] {
+            bytes.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+        let push_float = |bytes: &mut Vec<u8>, v: T| {
+            bytes.extend_from_slice(&v.to_f64().unwrap_or(f64::NAN).to_le_bytes())
+        };
+        push_float(&mut bytes, self.max_abs);
+        for indices in [
+            &self.pivots,
+            &self.columns,
+            &self.l_indptr,
+            &self.l_indices,
+            &self.u_indptr,
+            &self.u_indices,
+        ] {
+            for i in indices {
+                bytes.extend_from_slice(&(*i as u64).to_le_bytes());
+            }
+        }
+        for values in [&self.l_values, &self.u_values, &self.diagonal] {
+            for v in values {
+                push_float(&mut bytes, v.re);
+                push_float(&mut bytes, v.im);
+            }
+        }
+        bytes
+    }
+
+    /// Deserialize a factorization written by ```to_bytes```, possibly in another
+    /// process.
+    ///
+    /// Returns `Err` if the buffer is truncated, was written by an incompatible
+    /// version or for another scalar type, or does not hold consistent factors.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SolveError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(SolveError::InvalidInput("not a serialized sparse LU factorization"));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(SolveError::InvalidInput("unsupported sparse LU format version"));
+        }
+        let width = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if width as usize != core::mem::size_of::<T>() {
+            return Err(SolveError::InvalidInput(
+                "the factorization was written for another scalar type",
+            ));
+        }
+        let words = bytes[12..]
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let truncated = SolveError::InvalidInput("truncated sparse LU factorization");
+        let (n, nnz_l, nnz_u) = (words[0] as usize, words[1] as usize, words[2] as usize);
+        let expected = n
+            .checked_mul(6)
+            .and_then(|w| w.checked_add(nnz_l.checked_mul(3)?))
+            .and_then(|w| w.checked_add(nnz_u.checked_mul(3)?))
+            .and_then(|w| w.checked_add(2))
+            .ok_or(truncated)?;
+        if expected.checked_mul(8) != Some(bytes.len() - HEADER_LEN) {
+            return Err(truncated);
+        }
+
+        let float = |w: u64| <T as NumCast>::from(f64::from_bits(w)).unwrap_or_else(T::nan);
+        let mut rest = &words[4..];
+        let mut take = |len: usize| {
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            head
+        };
+        let mut indices = |len: usize| take(len).iter().map(|i| *i as usize).collect::<Vec<_>>();
+        let pivots = indices(n);
+        let columns = indices(n);
+        let l_indptr = indices(n + 1);
+        let l_indices = indices(nnz_l);
+        let u_indptr = indices(n + 1);
+        let u_indices = indices(nnz_u);
+        let complex = |w: &[u64]| {
+            w.chunks_exact(2)
+                .map(|c| Complex::new(float(c[0]), float(c[1])))
+                .collect::<Vec<_>>()
+        };
+        let l_values = complex(take(2 * nnz_l));
+        let u_values = complex(take(2 * nnz_u));
+        let diagonal = complex(take(2 * n));
+
+        let invalid = SolveError::InvalidInput("the serialized factors are inconsistent");
+        let is_permutation = |p: &[usize]| Permutation::from_vec(p.to_vec()).is_ok();
+        let is_indptr = |indptr: &[usize], nnz: usize| {
+            indptr[0] == 0 && indptr[n] == nnz && indptr.windows(2).all(|w| w[0] <= w[1])
+        };
+        if !is_permutation(&pivots)
+            || !is_permutation(&columns)
+            || !is_indptr(&l_indptr, nnz_l)
+            || !is_indptr(&u_indptr, nnz_u)
+        {
+            return Err(invalid);
+        }
+        // The triangular solves only read `L` below and `U` above each step, so a
+        // step must update the rows pivoted after it and the steps before it.
+        let mut step_of = vec![0; n];
+        for (k, i) in pivots.iter().enumerate() {
+            step_of[*i] = k;
+        }
+        let triangular = (0..n).all(|k| {
+            l_indices[l_indptr[k]..l_indptr[k + 1]]
+                .iter()
+                .all(|r| *r < n && step_of[*r] > k)
+                && u_indices[u_indptr[k]..u_indptr[k + 1]].iter().all(|s| *s < k)
+        });
+        if !triangular {
+            return Err(invalid);
+        }
+
+        Ok(SparseLu {
+            pivots,
+            columns,
+            l_indptr,
+            l_indices,
+            l_values,
+            u_indptr,
+            u_indices,
+            u_values,
+            diagonal,
+            max_abs: float(words[3]),
+        })
+    }
+
+    /// Write the factorization to ```path```, see ```to_bytes```.
+    ///
+    /// The factorization is written to a temporary file that then replaces ```path```,
+    /// so an interrupted write leaves the previous file intact.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SparseLu};
+    /// use num::Complex;
+    ///
+    /// let path = std::env::temp_dir().join("sparse_complex_doc_lu.bin");
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    /// m.factorize_sparse().unwrap().save(&path).unwrap();
+    ///
+    /// let lu = SparseLu::<f64>::load(&path).unwrap();
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(0., 1.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    /// # std::fs::remove_file(&path).unwrap();
+    ///```
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, self.to_bytes())?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Read a factorization written by ```save```.
+    ///
+    /// Returns an `InvalidData` error if the file does not hold a valid factorization.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(&bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl Permutation {
+    /// The permutation moving ```steps[k]``` to ```k```.
+    fn from_steps(steps: &[usize]) -> Self {
+        let mut forward = vec![0; steps.len()];
+        for (k, i) in steps.iter().enumerate() {
+            forward[*i] = k;
+        }
+        Permutation { forward }
+    }
+}
+
+/// Orders `0..n` by minimum degree on the graph of ```A + Aᵀ```, eliminating the
+/// nodes one by one and connecting their neighbours, and returns the nodes in
+/// elimination order.
+///
+/// The elimination graph is stored explicitly, so the work grows with the square
+/// of the degrees; ties are broken by the lowest index.
+fn minimum_degree<I: AsIndex>(n: usize, rows: &[I], cols: &[I]) -> Vec<usize> {
+    let mut adjacency = vec![Vec::new(); n];
+    for (r, c) in rows.iter().zip(cols.iter()) {
+        let (r, c) = (r.index(), c.index());
+        if r != c {
+            adjacency[r].push(c);
+            adjacency[c].push(r);
+        }
+    }
+    for neighbours in adjacency.iter_mut() {
+        neighbours.sort_unstable();
+        neighbours.dedup();
+    }
+
+    let mut heap = (0..n)
+        .map(|i| Reverse((adjacency[i].len(), i)))
+        .collect::<BinaryHeap<_>>();
+    let mut eliminated = vec![false; n];
+    let mut mark = vec![NONE; n];
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse((degree, v))) = heap.pop() {
+        if eliminated[v] || degree != adjacency[v].len() {
+            continue;
+        }
+        eliminated[v] = true;
+        order.push(v);
+        let clique = core::mem::take(&mut adjacency[v]);
+        for &u in &clique {
+            let neighbours = &mut adjacency[u];
+            neighbours.retain(|w| *w != v);
+            neighbours.iter().for_each(|w| mark[*w] = u);
+            for &w in &clique {
+                if w != u && mark[w] != u {
+                    neighbours.push(w);
+                }
+            }
+            heap.push(Reverse((neighbours.len(), u)));
+        }
+    }
+    order
+}
@@ -0,0 +1,134 @@
+use crate::ComplexMatrix;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// How stencil couplings that point outside of the grid are treated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// Couplings to cells outside the grid are dropped (homogeneous Dirichlet).
+    Truncate,
+    /// The grid wraps around on every axis.
+    Periodic,
+}
+
+/// A structured grid of ```D``` dimensions whose cells are numbered in row-major
+/// order, i.e. the last axis varies fastest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grid<const D: usize> {
+    dims: [usize; D],
+}
+
+impl<const D: usize> Grid<D> {
+    /// Create a grid with ```dims[k]``` cells along axis ```k```.
+    ///```rust
+    /// use sparse_complex::Grid;
+    /// let grid = Grid::new([4, 3]);
+    /// assert_eq!(grid.len(), 12);
+    ///```
+    pub fn new(dims: [usize; D]) -> Self {
+        Grid { dims }
+    }
+
+    /// The number of cells along each axis.
+    pub fn dims(&self) -> [usize; D] {
+        self.dims
+    }
+
+    /// The total number of cells, which is the order of the assembled matrix.
+    pub fn len(&self) -> usize {
+        self.dims.iter().product()
+    }
+
+    /// Returns `true` if the grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The matrix index of ```cell```.
+    ///```rust
+    /// use sparse_complex::Grid;
+    /// let grid = Grid::new([4, 3]);
+    /// assert_eq!(grid.linear_index([2, 1]), 7);
+    ///```
+    pub fn linear_index(&self, cell: [usize; D]) -> usize {
+        cell.iter()
+            .zip(self.dims.iter())
+            .fold(0, |index, (c, d)| index * d + c)
+    }
+
+    /// The cell of the matrix index ```index```, inverse of ```linear_index```.
+    ///```rust
+    /// use sparse_complex::Grid;
+    /// let grid = Grid::new([4, 3]);
+    /// assert_eq!(grid.cell(7), [2, 1]);
+    ///```
+    pub fn cell(&self, mut index: usize) -> [usize; D] {
+        let mut cell = [0; D];
+        for k in (0..D).rev() {
+            cell[k] = index % self.dims[k];
+            index /= self.dims[k];
+        }
+        cell
+    }
+
+    /// The neighbor of ```cell``` at ```offset```, or None if it falls outside a truncated grid.
+    fn neighbor(&self, cell: [usize; D], offset: [isize; D], boundary: Boundary) -> Option<[usize; D]> {
+        let mut neighbor = [0; D];
+        for k in 0..D {
+            let dim = self.dims[k] as isize;
+            let position = cell[k] as isize + offset[k];
+            neighbor[k] = match boundary {
+                Boundary::Truncate if position < 0 || position >= dim => return None,
+                Boundary::Truncate => position as usize,
+                Boundary::Periodic => position.rem_euclid(dim) as usize,
+            };
+        }
+        Some(neighbor)
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Assemble a matrix from a stencil over a structured grid.
+    ///
+    /// ```stencil``` is called once per cell and returns the ```(offset, coefficient)```
+    /// couplings of that cell. Each coupling adds the coefficient at
+    /// ```(row, col) = (index of cell, index of cell + offset)```. The closure receives the
+    /// cell, so coefficients may vary in space. Couplings reaching outside the grid
+    /// follow ```boundary```.
+    ///```rust
+    /// use sparse_complex::{Boundary, ComplexMatrix, Grid};
+    /// use num::Complex;
+    ///
+    /// // 2D five-point Laplacian with a complex shift (Helmholtz-like).
+    /// let grid = Grid::new([3, 3]);
+    /// let m = ComplexMatrix::<f64>::from_stencil(&grid, Boundary::Truncate, |_cell| {
+    ///     vec![
+    ///         ([0, 0], Complex::new(4., 0.1)),
+    ///         ([-1, 0], Complex::new(-1., 0.)),
+    ///         ([1, 0], Complex::new(-1., 0.)),
+    ///         ([0, -1], Complex::new(-1., 0.)),
+    ///         ([0, 1], Complex::new(-1., 0.)),
+    ///     ]
+    /// });
+    ///
+    /// let center = grid.linear_index([1, 1]);
+    /// assert_eq!(m.get(center, grid.linear_index([0, 1])), Some(&Complex::new(-1., 0.)));
+    /// assert_eq!(m.get(0, grid.linear_index([2, 2])), None);
+    ///```
+    pub fn from_stencil<const D: usize, F, I>(grid: &Grid<D>, boundary: Boundary, mut stencil: F) -> Self
+    where
+        F: FnMut([usize; D]) -> I,
+        I: IntoIterator<Item = ([isize; D], Complex<T>)>,
+    {
+        let mut m = ComplexMatrix::new();
+        for row in 0..grid.len() {
+            let cell = grid.cell(row);
+            for (offset, value) in stencil(cell) {
+                if let Some(neighbor) = grid.neighbor(cell, offset, boundary) {
+                    m.add_element(row, grid.linear_index(neighbor), value);
+                }
+            }
+        }
+        m
+    }
+}
@@ -0,0 +1,61 @@
+use crate::progress::{self, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, SolvableFloat, SolveError};
+use num::complex::Complex;
+
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve `Ax=b` for each right-hand side of ```rhs``` with a single LU
+    /// factorization, handing each solution to ```f(i, x)``` as soon as it is computed.
+    ///
+    /// Only one solution is kept in memory at a time, so very large sweeps can be
+    /// post-processed without materializing all their solutions. The matrix is
+    /// factorized for the size of the first right-hand side.
+    ///
+    /// Returns the number of solved systems, or `Err` if the factorization fails or a
+    /// right-hand side has another length than the first one.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let rhs = (0..1000).map(|k| [Complex::new(2. * k as f64, 0.), Complex::new(0., 1.)]);
+    /// let mut sum = Complex::new(0., 0.);
+    /// let solved = m.solve_stream(rhs, |_, x| sum += x[0] * x[1]).unwrap();
+    /// assert_eq!(solved, 1000);
+    /// assert_eq!(sum, Complex::new(499500., 0.));
+    ///```
+    pub fn solve_stream<I, B, F>(&self, rhs: I, mut f: F) -> Result<usize, SolveError>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[Complex<T>]>,
+        F: FnMut(usize, &[Complex<T>]),
+    {
+        let mut context = T::new_context()?;
+        let mut x = Vec::new();
+        let mut solved = 0;
+        for b in rhs {
+            let b = b.as_ref();
+            if solved == 0 {
+                self.check_rhs(b.len())?;
+                progress::report(Phase::Factorization, Some(0.));
+                self.debug_validate(b.len());
+                let info = unsafe { T::context_factorize(&mut context, self, b.len()) };
+                solver::info_to_result(info)?;
+                progress::report(Phase::Factorization, Some(1.));
+            } else if b.len() != x.len() {
+                return Err(SolveError::DimensionMismatch);
+            }
+            x.clear();
+            x.extend_from_slice(b);
+            let info = unsafe { T::context_solve_factorized(&mut context, &mut x) };
+            solver::info_to_result(info)?;
+            progress::report(Phase::Solve, None);
+            f(solved, &x);
+            solved += 1;
+        }
+        Ok(solved)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::progress::{self, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, SolvableFloat, SolveError};
+use num::complex::Complex;
+
+/// Checks that ```nrhs``` columns of length ```n``` with leading dimension ```ld``` fit
+/// in a buffer of length ```len```.
+fn check_block(len: usize, n: usize, nrhs: usize, ld: usize) -> Result<(), SolveError> {
+    if ld < n.max(1) {
+        return Err(SolveError::InvalidInput(
+            "the leading dimension is smaller than the number of rows",
+        ));
+    }
+    if nrhs > 0 && len < (nrhs - 1) * ld + n {
+        return Err(SolveError::DimensionMismatch);
+    }
+    Ok(())
+}
+
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve `AX=B` in place for a column-major block of ```nrhs``` right-hand sides of
+    /// length ```n``` with leading dimension ```ld```, as used by LAPACK.
+    ///
+    /// Column ```j``` of `B` is ```b[j * ld..j * ld + n]```; the padding between the
+    /// columns is left untouched, so Fortran-origin buffers are used without repacking.
+    ///
+    /// Returns `Err` if ```ld < n```, if ```b``` is too short or if the factorization
+    /// fails. The solutions are stored in ```b```.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 1.));
+    ///
+    /// let (one, pad) = (Complex::new(1., 0.), Complex::new(9., 9.));
+    /// let mut b = vec![one, one, pad, one * 2., one * 2.];
+    /// m.solve_strided(&mut b, 2, 2, 3).unwrap();
+    /// assert_eq!(b[..2], [Complex::new(0.5, 0.), Complex::new(0., -1.)]);
+    /// assert_eq!(b[2], pad);
+    /// assert_eq!(b[3..], [Complex::new(1., 0.), Complex::new(0., -2.)]);
+    ///```
+    pub fn solve_strided(&self, b: &mut [Complex<T>], n: usize, nrhs: usize, ld: usize) -> Result<(), SolveError> {
+        check_block(b.len(), n, nrhs, ld)?;
+        if nrhs == 0 {
+            return Ok(());
+        }
+        self.check_rhs(n)?;
+        self.debug_validate(n);
+        progress::report(Phase::Factorization, Some(0.));
+        let info = unsafe { T::solve_strided(self, b, n, nrhs, ld) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Solve, Some(1.));
+        Ok(())
+    }
+}
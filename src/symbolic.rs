@@ -0,0 +1,329 @@
+#[cfg(feature = "std")]
+use crate::index::AsIndex;
+#[cfg(feature = "eigen")]
+use crate::solver;
+#[cfg(feature = "std")]
+use crate::ComplexMatrix;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use crate::SolvableFloat;
+use crate::{Permutation, SolveError};
+#[cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+use num::complex::Complex;
+#[cfg(feature = "std")]
+use num_traits::float::Float;
+
+const MAGIC: &[u8; 4] = b"SCSA";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 4 + 4 + 5 * 8;
+
+/// Estimated cost of the numeric factorization of a sparsity pattern.
+///
+/// The counts come from the elimination tree of the symmetrized, column-ordered
+/// pattern and assume diagonal pivots, so the actual factorization may differ when
+/// partial pivoting swaps rows. They are meant to compare orderings and to predict
+/// runtime, not as exact figures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FactorizationEstimate {
+    /// Entries of `L`, including its unit diagonal.
+    pub nnz_l: usize,
+    /// Entries of `U`, including its diagonal.
+    pub nnz_u: usize,
+    /// Entries of `L + U` that are not present in `A`.
+    pub fill_in: usize,
+    /// Floating point operations of the numeric factorization.
+    pub flops: f64,
+}
+
+/// The symbolic analysis of a sparsity pattern: the fill-reducing column ordering
+/// computed by `Eigen::SparseLU::analyzePattern` and the cost it implies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymbolicAnalysis {
+    size: usize,
+    column_permutation: Permutation,
+    estimate: FactorizationEstimate,
+}
+
+impl SymbolicAnalysis {
+    /// The order of the analyzed matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The fill-reducing column ordering: original column `i` becomes column
+    /// ```column_permutation().get(i)``` of the factorized matrix.
+    pub fn column_permutation(&self) -> &Permutation {
+        &self.column_permutation
+    }
+
+    /// The estimated cost of factorizing a matrix with the analyzed pattern.
+    pub fn estimate(&self) -> &FactorizationEstimate {
+        &self.estimate
+    }
+
+    /// Serialize the analysis into a portable little-endian byte buffer.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Permutation, SymbolicAnalysis};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 0, Complex::new(1., 0.));
+    ///
+    /// let analysis = m.analyze_with_ordering(Permutation::identity(2)).unwrap();
+    /// let bytes = analysis.to_bytes();
+    /// assert_eq!(SymbolicAnalysis::from_bytes(&bytes).unwrap(), analysis);
+    ///```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 8 * self.size);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        for value in [
+            self.size,
+            self.estimate.nnz_l,
+            self.estimate.nnz_u,
+            self.estimate.fill_in,
+        ] {
+            bytes.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.estimate.flops.to_le_bytes());
+        for p in self.column_permutation.as_slice() {
+            bytes.extend_from_slice(&(*p as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialize an analysis written by ```to_bytes```, possibly in another process.
+    ///
+    /// Returns `Err` if the buffer is truncated, was written by an incompatible
+    /// version, or does not hold a valid permutation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SolveError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(SolveError::InvalidInput("not a serialized symbolic analysis"));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(SolveError::InvalidInput("unsupported symbolic analysis format version"));
+        }
+        let words = bytes[8..]
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let size = words[0] as usize;
+        let expected = size.checked_mul(8).and_then(|b| b.checked_add(HEADER_LEN));
+        if expected != Some(bytes.len()) {
+            return Err(SolveError::InvalidInput("truncated symbolic analysis"));
+        }
+
+        let column_permutation = Permutation::from_vec(words[5..].iter().map(|p| *p as usize).collect())
+            .map_err(|_| SolveError::InvalidInput("the column ordering is not a permutation"))?;
+
+        Ok(SymbolicAnalysis {
+            size,
+            column_permutation,
+            estimate: FactorizationEstimate {
+                nnz_l: words[1] as usize,
+                nnz_u: words[2] as usize,
+                fill_in: words[3] as usize,
+                flops: f64::from_bits(words[4]),
+            },
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float> ComplexMatrix<T> {
+    /// Run the symbolic analysis of the sparsity pattern.
+    ///
+    /// Only the positions of the entries are used, so the analysis is cheap compared
+    /// to the numeric factorization and can be used to predict its cost.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// for i in 0..4 {
+    ///     m.add_element(i, i, Complex::new(4., 0.));
+    ///     m.add_element(i, 0, Complex::new(-1., 0.));
+    ///     m.add_element(0, i, Complex::new(-1., 0.));
+    /// }
+    ///
+    /// let analysis = m.analyze_pattern().unwrap();
+    /// assert_eq!(analysis.size(), 4);
+    /// assert!(analysis.estimate().nnz_l >= 4);
+    ///```
+    #[cfg(feature = "eigen")]
+    pub fn analyze_pattern(&self) -> Result<SymbolicAnalysis, SolveError> {
+        let size = self.dimension();
+        let mut column_permutation = vec![0; size];
+        let info = unsafe {
+            solver::analyze_pattern_cpp(
+                self.rows.as_ptr(),
+                self.cols.as_ptr(),
+                self.entries.len(),
+                size,
+                column_permutation.as_mut_ptr(),
+            )
+        };
+        solver::info_to_result(info)?;
+
+        let estimate = estimate_factorization(&self.rows, &self.cols, &column_permutation);
+        Ok(SymbolicAnalysis {
+            size,
+            column_permutation: Permutation { forward: column_permutation },
+            estimate,
+        })
+    }
+
+    /// Build the symbolic analysis of the sparsity pattern for a given column ordering,
+    /// e.g. one computed by the application, instead of the one chosen by Eigen.
+    ///
+    /// The analysis is accepted wherever a computed one is, such as
+    /// ```solve_with_analysis```. Returns `Err` if the ordering does not have the order of
+    /// the matrix.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Permutation};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let ordering = Permutation::from_vec(vec![1, 0]).unwrap();
+    /// let analysis = m.analyze_with_ordering(ordering.clone()).unwrap();
+    /// assert_eq!(analysis.column_permutation(), &ordering);
+    ///```
+    pub fn analyze_with_ordering(&self, ordering: Permutation) -> Result<SymbolicAnalysis, SolveError> {
+        let size = self.dimension();
+        if ordering.len() != size {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let estimate = estimate_factorization(&self.rows, &self.cols, ordering.as_slice());
+        Ok(SymbolicAnalysis {
+            size,
+            column_permutation: ordering,
+            estimate,
+        })
+    }
+}
+
+#[cfg(all(feature = "eigen", any(feature = "f64", feature = "f32")))]
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` reusing the column ordering of a previous analysis.
+    ///
+    /// Only the fill-reducing ordering is reused: Eigen still builds the elimination
+    /// tree and the supernodes of the factorization, which it does not expose. Any
+    /// ordering gives a correct solution, but it is only fill-reducing for the pattern
+    /// it was computed on.
+    ///
+    /// Returns `Err` if ```b``` or the analysis do not match the size of the matrix, or
+    /// if the factorization fails. The result is stored in `b`.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., -1.));
+    /// m.add_element(1, 1, Complex::new(-1., 1.));
+    /// let analysis = m.analyze_pattern().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(0., 1.)];
+    /// m.solve_with_analysis(&analysis, &mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(0.5, 0.5), Complex::new(0.5, -0.5)]);
+    ///```
+    pub fn solve_with_analysis(
+        &self,
+        analysis: &SymbolicAnalysis,
+        b: &mut [Complex<T>],
+    ) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        if analysis.size != b.len() {
+            return Err(SolveError::DimensionMismatch);
+        }
+        self.debug_validate(b.len());
+        let info = unsafe {
+            T::solve_ordered(self, &self.entries, b, analysis.column_permutation.as_slice())
+        };
+        solver::info_to_result(info)
+    }
+}
+
+/// Estimates the factorization of the pattern `(rows, cols)` symmetrically permuted
+/// by `perm`, using the column counts of its elimination tree.
+#[cfg(feature = "std")]
+pub(crate) fn estimate_factorization<I: AsIndex>(
+    rows: &[I],
+    cols: &[I],
+    perm: &[usize],
+) -> FactorizationEstimate {
+    let n = perm.len();
+
+    // Strictly lower part of the permuted, symmetrized pattern, by row.
+    let mut lower = vec![Vec::new(); n];
+    let mut pattern = rows
+        .iter()
+        .zip(cols.iter())
+        .map(|(r, c)| (perm[r.index()], perm[c.index()]))
+        .collect::<Vec<_>>();
+    pattern.sort_unstable();
+    pattern.dedup();
+    let nnz_a = pattern.len();
+    for (i, j) in pattern {
+        if i > j {
+            lower[i].push(j);
+        } else if j > i {
+            lower[j].push(i);
+        }
+    }
+
+    // Elimination tree (Liu's algorithm with path compression).
+    let mut parent = vec![usize::MAX; n];
+    let mut ancestor = vec![usize::MAX; n];
+    for (i, row) in lower.iter().enumerate() {
+        for &j in row {
+            let mut k = j;
+            while ancestor[k] != usize::MAX && ancestor[k] != i {
+                let next = ancestor[k];
+                ancestor[k] = i;
+                k = next;
+            }
+            if ancestor[k] == usize::MAX {
+                ancestor[k] = i;
+                parent[k] = i;
+            }
+        }
+    }
+
+    // Column counts: row i of L is the union of the tree paths from its entries up to i.
+    let mut counts = vec![1usize; n];
+    let mut mark = vec![usize::MAX; n];
+    for (i, row) in lower.iter().enumerate() {
+        mark[i] = i;
+        for &j in row {
+            let mut k = j;
+            while mark[k] != i {
+                counts[k] += 1;
+                mark[k] = i;
+                k = parent[k];
+            }
+        }
+    }
+
+    let nnz_l = counts.iter().sum::<usize>();
+    let flops = counts
+        .iter()
+        .map(|&c| {
+            let off = (c - 1) as f64;
+            off + 2. * off * off
+        })
+        .sum();
+
+    FactorizationEstimate {
+        nnz_l,
+        nnz_u: nnz_l,
+        fill_in: (2 * nnz_l - n).saturating_sub(nnz_a),
+        flops,
+    }
+}
@@ -0,0 +1,237 @@
+use crate::index::{to_storage, AsIndex, StorageIndex};
+use crate::ComplexMatrix;
+#[cfg(any(feature = "f64", feature = "f32"))]
+use crate::SolveError;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// The kind of structural symmetry of a ```SymmetricMatrix```.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// `A = A^H`: the mirrored entry is the complex conjugate.
+    Hermitian,
+    /// `A = A^T`: the mirrored entry is the same value.
+    ComplexSymmetric,
+}
+
+/// The triangle kept by a ```SymmetricMatrix```. The diagonal is always stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Triangle {
+    Upper,
+    Lower,
+}
+
+/// A Hermitian or complex-symmetric matrix that only stores one triangle.
+///
+/// Elements added in the other triangle are mirrored into the stored one, so
+/// each off-diagonal coupling only needs to be assembled once.
+#[derive(Clone, PartialEq)]
+pub struct SymmetricMatrix<T: Float> {
+    symmetry: Symmetry,
+    triangle: Triangle,
+    entries: Vec<Complex<T>>,
+    rows: Vec<StorageIndex>,
+    cols: Vec<StorageIndex>,
+}
+
+impl<T: Float> SymmetricMatrix<T> {
+    /// Create a new, initially empty ```SymmetricMatrix``` storing the given triangle.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// let m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Upper);
+    ///```
+    pub fn new(symmetry: Symmetry, triangle: Triangle) -> Self {
+        Self::with_capacity(symmetry, triangle, 0)
+    }
+
+    /// Create a new, initially empty ```SymmetricMatrix``` with a given capacity.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// let m = SymmetricMatrix::<f64>::with_capacity(Symmetry::ComplexSymmetric, Triangle::Lower, 5);
+    ///```
+    pub fn with_capacity(symmetry: Symmetry, triangle: Triangle, capacity: usize) -> Self {
+        SymmetricMatrix {
+            symmetry,
+            triangle,
+            entries: Vec::with_capacity(capacity),
+            rows: Vec::with_capacity(capacity),
+            cols: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the symmetry of the matrix.
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    /// Returns the stored triangle.
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// Add an element at location ```(row, col)```.
+    ///
+    /// Elements outside the stored triangle are mirrored into it (conjugated for
+    /// Hermitian matrices). Only one of ```(row, col)``` and ```(col, row)``` should be added.
+    ///
+    /// The diagonal of a Hermitian matrix is real, so only the real part of a diagonal
+    /// element is stored and its imaginary part is dropped.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Upper);
+    /// m.add_element(1, 0, Complex::new(2., 1.));
+    /// m.add_element(1, 1, Complex::new(3., 1e-17));
+    ///
+    /// assert_eq!(m.get(0, 1), Some(Complex::new(2., -1.)));
+    /// assert_eq!(m.get(1, 0), Some(Complex::new(2., 1.)));
+    /// assert_eq!(m.get(1, 1), Some(Complex::new(3., 0.)));
+    ///```
+    pub fn add_element(&mut self, row: usize, col: usize, value: Complex<T>) {
+        let value = if row == col && self.symmetry == Symmetry::Hermitian {
+            Complex::new(value.re, T::zero())
+        } else {
+            value
+        };
+        let (row, col, value) = if self.is_stored(row, col) {
+            (row, col, value)
+        } else {
+            (col, row, self.mirror(value))
+        };
+        self.entries.push(value);
+        self.rows.push(to_storage(row));
+        self.cols.push(to_storage(col));
+    }
+
+    /// Returns the element at ```(row, col)``` if present, or None if not.
+    ///
+    /// Elements of the implicit triangle are reconstructed from the stored one.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::ComplexSymmetric, Triangle::Lower);
+    /// m.add_element(2, 0, Complex::new(0., 3.));
+    ///
+    /// assert_eq!(m.get(0, 2), Some(Complex::new(0., 3.)));
+    /// assert_eq!(m.get(1, 1), None);
+    ///```
+    pub fn get(&self, row: usize, col: usize) -> Option<Complex<T>> {
+        let stored = self.is_stored(row, col);
+        let (r, c) = if stored { (row, col) } else { (col, row) };
+        self.rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter())
+            .find(|&((i, j), _)| i.index() == r && j.index() == c)
+            .map(|(_, v)| if stored { *v } else { self.mirror(*v) })
+    }
+
+    /// Expand into a full ```ComplexMatrix``` containing both triangles.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Upper);
+    /// m.add_element(0, 0, Complex::new(4., 0.));
+    /// m.add_element(0, 1, Complex::new(1., 1.));
+    ///
+    /// let full = m.to_complex_matrix();
+    /// assert_eq!(full.get(1, 0), Some(&Complex::new(1., -1.)));
+    ///```
+    pub fn to_complex_matrix(&self) -> ComplexMatrix<T> {
+        let mut m = ComplexMatrix::with_capacity(2 * self.entries.len());
+        let elements = self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            let (row, col) = (row.index(), col.index());
+            m.add_element(row, col, *value);
+            if row != col {
+                m.add_element(col, row, self.mirror(*value));
+            }
+        }
+        m
+    }
+
+    fn is_stored(&self, row: usize, col: usize) -> bool {
+        match self.triangle {
+            Triangle::Upper => row <= col,
+            Triangle::Lower => row >= col,
+        }
+    }
+
+    fn mirror(&self, value: Complex<T>) -> Complex<T> {
+        match self.symmetry {
+            Symmetry::Hermitian => value.conj(),
+            Symmetry::ComplexSymmetric => value,
+        }
+    }
+}
+
+#[cfg(feature = "f64")]
+impl SymmetricMatrix<f64> {
+    /// Solve the system `Ax=b`, expanding the stored triangle into the full matrix.
+    ///
+    /// Returns a `Result`. `Ok(())` if the system was solved successfully, `Err(SolveError)` if not.
+    /// The result is stored in `b`.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f64>::new(Symmetry::ComplexSymmetric, Triangle::Upper);
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(0., 1.)];
+    /// m.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(0.5, 0.), Complex::new(0., 0.5)]);
+    ///```
+    pub fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.to_complex_matrix().solve(b)
+    }
+}
+
+#[cfg(feature = "f32")]
+impl SymmetricMatrix<f32> {
+    /// Solve the system `Ax=b`, expanding the stored triangle into the full matrix.
+    ///
+    /// Returns a `Result`. `Ok(())` if the system was solved successfully, `Err(SolveError)` if not.
+    /// The result is stored in `b`.
+    ///```rust
+    /// use sparse_complex::{SymmetricMatrix, Symmetry, Triangle};
+    /// use num::Complex;
+    ///
+    /// let mut m = SymmetricMatrix::<f32>::new(Symmetry::ComplexSymmetric, Triangle::Upper);
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(2., 0.));
+    ///
+    /// let mut b = vec![Complex::new(1., 0.), Complex::new(0., 1.)];
+    /// m.solve(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(0.5, 0.), Complex::new(0., 0.5)]);
+    ///```
+    pub fn solve(&self, b: &mut [Complex<f32>]) -> Result<(), SolveError> {
+        self.to_complex_matrix().solve(b)
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Debug for SymmetricMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut msg = format!("SymmetricMatrix ({:?}, {:?}) {{ \n", self.symmetry, self.triangle);
+        let elements = self
+            .rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            msg = format!("{}  ({},{}) -> {}\n", msg, row, col, value)
+        }
+        write!(f, "{}}}", msg)
+    }
+}
@@ -0,0 +1,130 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static OVERRIDE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Sets the number of threads used by the parallel features of the crate.
+///
+/// 0 (the default) uses all cores, and 1 disables parallelism, e.g. for
+/// deterministic debugging runs. ```with_num_threads``` overrides it for a single call.
+///
+/// With the `openmp` feature it also sets the OpenMP threads of Eigen, which is a
+/// process-wide setting that ```with_num_threads``` does not override.
+///```rust
+/// sparse_complex::set_num_threads(1);
+/// assert_eq!(sparse_complex::num_threads(), 1);
+/// sparse_complex::set_num_threads(0);
+///```
+pub fn set_num_threads(threads: usize) {
+    NUM_THREADS.store(threads, Ordering::Relaxed);
+    #[cfg(feature = "openmp")]
+    unsafe {
+        let threads = std::os::raw::c_int::try_from(threads).unwrap_or(std::os::raw::c_int::MAX);
+        crate::solver::eigen_set_num_threads_cpp(threads)
+    };
+}
+
+/// The number of threads used by the parallel features of the crate, 0 meaning all cores.
+pub fn num_threads() -> usize {
+    OVERRIDE
+        .with(|o| o.get())
+        .unwrap_or_else(|| NUM_THREADS.load(Ordering::Relaxed))
+}
+
+/// Runs ```f``` with the parallel features limited to ```threads``` threads.
+///
+/// With the `rayon` feature, ```f``` runs inside a rayon pool of that size, so the
+/// parallel iterators consumed in ```f``` (e.g. ```ComplexMatrix::par_iter```) are
+/// limited as well. Pools are cached per calling thread.
+///```rust
+/// use sparse_complex::{num_threads, with_num_threads};
+///
+/// let threads = with_num_threads(2, || num_threads());
+/// assert_eq!(threads, 2);
+///```
+pub fn with_num_threads<R, F>(threads: usize, f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    install(threads, move || {
+        let _guard = OverrideGuard::set(threads);
+        f()
+    })
+}
+
+/// Runs ```f``` with the configured number of threads.
+#[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+pub(crate) fn run<R, F>(f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    install(num_threads(), f)
+}
+
+#[cfg(feature = "rayon")]
+fn install<R, F>(threads: usize, f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    thread_local! {
+        static POOL: RefCell<Option<(usize, Rc<rayon::ThreadPool>)>> = const { RefCell::new(None) };
+    }
+
+    if threads == 0 || rayon::current_num_threads() == threads {
+        return f();
+    }
+    let pool = POOL.with(|cached| {
+        let mut cached = cached.borrow_mut();
+        match cached.as_ref() {
+            Some((n, pool)) if *n == threads => Some(Rc::clone(pool)),
+            _ => {
+                let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().ok()?;
+                let pool = Rc::new(pool);
+                *cached = Some((threads, Rc::clone(&pool)));
+                Some(pool)
+            }
+        }
+    });
+    match pool {
+        Some(pool) => {
+            let callback = crate::progress::current();
+            let token = crate::cancel::current();
+            pool.install(move || crate::progress::scoped(callback, || crate::cancel::scoped(token, f)))
+        }
+        None => f(),
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+fn install<R, F>(_threads: usize, f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    f()
+}
+
+/// Restores the previous thread override when dropped, even on panic.
+struct OverrideGuard(Option<usize>);
+
+impl OverrideGuard {
+    fn set(threads: usize) -> Self {
+        OverrideGuard(OVERRIDE.with(|o| o.replace(Some(threads))))
+    }
+}
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        OVERRIDE.with(|o| o.set(self.0));
+    }
+}
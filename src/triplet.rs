@@ -0,0 +1,72 @@
+use crate::index::AsIndex;
+use crate::ComplexMatrix;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// An element of a sparse matrix: the ```value``` at ```(row, col)```.
+///
+/// Every API taking elements accepts anything convertible into a ```Triplet```, in
+/// particular ```(row, col, value)``` tuples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triplet<T: Float> {
+    pub row: usize,
+    pub col: usize,
+    pub value: Complex<T>,
+}
+
+impl<T: Float> Triplet<T> {
+    /// Create the element ```value``` at ```(row, col)```.
+    pub fn new(row: usize, col: usize, value: Complex<T>) -> Self {
+        Triplet { row, col, value }
+    }
+}
+
+impl<T: Float> From<(usize, usize, Complex<T>)> for Triplet<T> {
+    fn from((row, col, value): (usize, usize, Complex<T>)) -> Self {
+        Triplet { row, col, value }
+    }
+}
+
+impl<T: Float> From<Triplet<T>> for (usize, usize, Complex<T>) {
+    fn from(triplet: Triplet<T>) -> Self {
+        (triplet.row, triplet.col, triplet.value)
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Iterate over the stored elements, in insertion order and with duplicates.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, Triplet};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(1, 0, Complex::new(2., 0.));
+    ///
+    /// let triplets = m.triplets().collect::<Vec<_>>();
+    /// assert_eq!(triplets, vec![Triplet::new(1, 0, Complex::new(2., 0.))]);
+    ///```
+    pub fn triplets(&self) -> impl Iterator<Item = Triplet<T>> + '_ {
+        self.rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.entries.iter())
+            .map(|((row, col), value)| Triplet::new(row.index(), col.index(), *value))
+    }
+}
+
+impl<T: Float, E: Into<Triplet<T>>> Extend<E> for ComplexMatrix<T> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, elements: I) {
+        for element in elements {
+            let Triplet { row, col, value } = element.into();
+            self.add_element(row, col, value);
+        }
+    }
+}
+
+impl<T: Float, E: Into<Triplet<T>>> FromIterator<E> for ComplexMatrix<T> {
+    fn from_iter<I: IntoIterator<Item = E>>(elements: I) -> Self {
+        let mut m = ComplexMatrix::new();
+        m.extend(elements);
+        m
+    }
+}
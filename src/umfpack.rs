@@ -0,0 +1,235 @@
+use crate::backend::{Factorization, SolverBackend};
+use crate::compressed::compress;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// The lengths of the `Control` and `Info` arrays.
+const UMFPACK_CONTROL: usize = 20;
+const UMFPACK_INFO: usize = 90;
+
+/// The systems solved by `umfpack_zl_solve`: `Ax=b` and `Aᴴx=b`.
+const UMFPACK_A: c_int = 0;
+const UMFPACK_AT: c_int = 1;
+
+/// Status codes returned by UMFPACK.
+const UMFPACK_OK: c_int = 0;
+const UMFPACK_WARNING_SINGULAR_MATRIX: c_int = 1;
+const UMFPACK_ERROR_OUT_OF_MEMORY: c_int = -1;
+
+#[link(name = "umfpack")]
+extern "C" {
+    fn umfpack_zl_defaults(control: *mut f64);
+    fn umfpack_zl_symbolic(
+        n_row: i64,
+        n_col: i64,
+        ap: *const i64,
+        ai: *const i64,
+        ax: *const f64,
+        az: *const f64,
+        symbolic: *mut *mut c_void,
+        control: *const f64,
+        info: *mut f64,
+    ) -> c_int;
+    fn umfpack_zl_numeric(
+        ap: *const i64,
+        ai: *const i64,
+        ax: *const f64,
+        az: *const f64,
+        symbolic: *mut c_void,
+        numeric: *mut *mut c_void,
+        control: *const f64,
+        info: *mut f64,
+    ) -> c_int;
+    fn umfpack_zl_solve(
+        sys: c_int,
+        ap: *const i64,
+        ai: *const i64,
+        ax: *const f64,
+        az: *const f64,
+        xx: *mut f64,
+        xz: *mut f64,
+        bx: *const f64,
+        bz: *const f64,
+        numeric: *mut c_void,
+        control: *const f64,
+        info: *mut f64,
+    ) -> c_int;
+    fn umfpack_zl_free_symbolic(symbolic: *mut *mut c_void);
+    fn umfpack_zl_free_numeric(numeric: *mut *mut c_void);
+}
+
+/// Maps a failed UMFPACK status to a ```SolveError```.
+fn status_to_error(status: c_int) -> SolveError {
+    match status {
+        UMFPACK_WARNING_SINGULAR_MATRIX => SolveError::Singular,
+        UMFPACK_ERROR_OUT_OF_MEMORY => SolveError::OutOfMemory,
+        _ => SolveError::InvalidInput("UMFPACK rejected the matrix"),
+    }
+}
+
+/// The SuiteSparse UMFPACK engine, factorizing with ```ComplexMatrix::factorize_umfpack```.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UmfpackBackend;
+
+impl SolverBackend<f64> for UmfpackBackend {
+    fn name(&self) -> &'static str {
+        "SuiteSparse UMFPACK"
+    }
+
+    fn factorize(&self, matrix: &ComplexMatrix<f64>) -> Result<Box<dyn Factorization<f64>>, SolveError> {
+        Ok(Box::new(matrix.factorize_umfpack()?))
+    }
+}
+
+/// A sparse LU factorization computed by SuiteSparse UMFPACK, created by
+/// ```ComplexMatrix::factorize_umfpack```.
+///
+/// UMFPACK is a multifrontal method: it factorizes dense frontal matrices with BLAS,
+/// which pays off on large matrices with more fill-in than circuit matrices. The
+/// compressed matrix is kept for the iterative refinement of the solves.
+pub struct UmfpackLu {
+    numeric: *mut c_void,
+    control: [f64; UMFPACK_CONTROL],
+    indptr: Vec<i64>,
+    indices: Vec<i64>,
+    values: Vec<Complex<f64>>,
+}
+
+impl ComplexMatrix<f64> {
+    /// Compute a sparse LU factorization of the matrix with SuiteSparse UMFPACK.
+    ///
+    /// Returns `Err` if the matrix is singular.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(0, 1, Complex::new(0., 1.));
+    /// m.add_element(1, 1, Complex::new(1., 0.));
+    /// let lu = m.factorize_umfpack().unwrap();
+    ///
+    /// let mut b = vec![Complex::new(2., 1.), Complex::new(1., 0.)];
+    /// lu.solve(&mut b).unwrap();
+    /// assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+    ///```
+    pub fn factorize_umfpack(&self) -> Result<UmfpackLu, SolveError> {
+        let n = self.dimension();
+        let by_col = compress(n, &self.cols, &self.rows, &self.entries);
+        let mut lu = UmfpackLu {
+            numeric: ptr::null_mut(),
+            control: [0.; UMFPACK_CONTROL],
+            indptr: by_col.indptr.iter().map(|p| *p as i64).collect(),
+            indices: by_col.indices.iter().map(|i| *i as i64).collect(),
+            values: by_col.values,
+        };
+        let mut info = [0.; UMFPACK_INFO];
+        let mut symbolic = ptr::null_mut();
+        let status = unsafe {
+            umfpack_zl_defaults(lu.control.as_mut_ptr());
+            let status = umfpack_zl_symbolic(
+                n as i64,
+                n as i64,
+                lu.indptr.as_ptr(),
+                lu.indices.as_ptr(),
+                lu.values.as_ptr() as *const f64,
+                ptr::null(),
+                &mut symbolic,
+                lu.control.as_ptr(),
+                info.as_mut_ptr(),
+            );
+            if status != UMFPACK_OK {
+                return Err(status_to_error(status));
+            }
+            let status = umfpack_zl_numeric(
+                lu.indptr.as_ptr(),
+                lu.indices.as_ptr(),
+                lu.values.as_ptr() as *const f64,
+                ptr::null(),
+                symbolic,
+                &mut lu.numeric,
+                lu.control.as_ptr(),
+                info.as_mut_ptr(),
+            );
+            umfpack_zl_free_symbolic(&mut symbolic);
+            status
+        };
+        if status != UMFPACK_OK {
+            return Err(status_to_error(status));
+        }
+        Ok(lu)
+    }
+}
+
+impl UmfpackLu {
+    /// The order of the factorized matrix.
+    pub fn size(&self) -> usize {
+        self.indptr.len() - 1
+    }
+
+    /// Solve the system `Ax=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_system(UMFPACK_A, b)
+    }
+
+    /// Solve the system `Aᴴx=b`. The result is stored in ```b```.
+    ///
+    /// Returns `Err` if ```b``` does not match the matrix size.
+    pub fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        self.solve_system(UMFPACK_AT, b)
+    }
+
+    fn solve_system(&self, sys: c_int, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        if b.len() != self.size() {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let rhs = b.to_vec();
+        let mut info = [0.; UMFPACK_INFO];
+        let status = unsafe {
+            umfpack_zl_solve(
+                sys,
+                self.indptr.as_ptr(),
+                self.indices.as_ptr(),
+                self.values.as_ptr() as *const f64,
+                ptr::null(),
+                b.as_mut_ptr() as *mut f64,
+                ptr::null_mut(),
+                rhs.as_ptr() as *const f64,
+                ptr::null(),
+                self.numeric,
+                self.control.as_ptr(),
+                info.as_mut_ptr(),
+            )
+        };
+        if status != UMFPACK_OK {
+            return Err(status_to_error(status));
+        }
+        Ok(())
+    }
+}
+
+impl Factorization<f64> for UmfpackLu {
+    fn size(&self) -> usize {
+        UmfpackLu::size(self)
+    }
+
+    fn solve(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        UmfpackLu::solve(self, b)
+    }
+
+    fn solve_adjoint(&self, b: &mut [Complex<f64>]) -> Result<(), SolveError> {
+        UmfpackLu::solve_adjoint(self, b)
+    }
+}
+
+impl Drop for UmfpackLu {
+    fn drop(&mut self) {
+        if !self.numeric.is_null() {
+            unsafe { umfpack_zl_free_numeric(&mut self.numeric) };
+        }
+    }
+}
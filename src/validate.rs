@@ -0,0 +1,144 @@
+use crate::compressed::Csr;
+use crate::index::AsIndex;
+#[cfg(any(feature = "f64", feature = "f32"))]
+use crate::SolvableFloat;
+use crate::{ComplexMatrix, SolveError};
+use num::complex::Complex;
+use num_traits::float::Float;
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Check that every stored element is finite, to locate the NaN or infinite
+    /// values of a faulty assembly.
+    ///
+    /// Returns `Err(SolveError::NonFiniteElement)` with the position of the first
+    /// element that is not, in insertion order.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SolveError};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(1., 0.));
+    /// m.add_element(2, 1, Complex::new(0., f64::INFINITY));
+    /// assert_eq!(m.validate(), Err(SolveError::NonFiniteElement { row: 2, col: 1 }));
+    ///```
+    pub fn validate(&self) -> Result<(), SolveError> {
+        let elements = self.rows.iter().zip(self.cols.iter()).zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            if !is_finite(value) {
+                return Err(SolveError::NonFiniteElement {
+                    row: row.index(),
+                    col: col.index(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "f64", feature = "f32"))]
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` like ```solve```, after checking that the matrix and
+    /// ```b``` only hold finite values.
+    ///
+    /// Returns `Err(SolveError::NonFiniteElement)` or `Err(SolveError::NonFiniteRhs)`
+    /// with the position of the first value that is not finite, leaving ```b```
+    /// untouched, and otherwise `Err` in the same cases as ```solve```.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SolveError};
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(2., 0.));
+    /// m.add_element(1, 1, Complex::new(4., 0.));
+    ///
+    /// let mut b = vec![Complex::new(2., 0.), Complex::new(f64::NAN, 0.)];
+    /// assert_eq!(m.solve_checked(&mut b), Err(SolveError::NonFiniteRhs { index: 1 }));
+    ///
+    /// b[1] = Complex::new(4., 0.);
+    /// m.solve_checked(&mut b).unwrap();
+    /// assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+    ///```
+    pub fn solve_checked(&self, b: &mut [Complex<T>]) -> Result<(), SolveError> {
+        self.validate()?;
+        if let Some(index) = b.iter().position(|v| !is_finite(v)) {
+            return Err(SolveError::NonFiniteRhs { index });
+        }
+        self.solve(b)
+    }
+}
+
+fn is_finite<T: Float>(value: &Complex<T>) -> bool {
+    value.re.is_finite() && value.im.is_finite()
+}
+
+// The checks below compile to nothing unless the `debug-validate` feature is enabled.
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Asserts that the triplets describe an ```n × n``` matrix with finite values,
+    /// before they are handed to the sparse solver. The entry points check ```n```
+    /// against the dimension first, so a mismatch is reported as an error.
+    #[cfg(any(feature = "f64", feature = "f32"))]
+    pub(crate) fn debug_validate(&self, n: usize) {
+        if !cfg!(feature = "debug-validate") {
+            return;
+        }
+        assert!(
+            self.rows.len() == self.entries.len() && self.cols.len() == self.entries.len(),
+            "debug-validate: the triplet arrays have different lengths"
+        );
+        let elements = self.rows.iter().zip(self.cols.iter()).zip(self.entries.iter());
+        for ((row, col), value) in elements {
+            assert!(
+                row.index() < n && col.index() < n,
+                "debug-validate: the element ({}, {}) is out of a {} × {} matrix",
+                row,
+                col,
+                n,
+                n
+            );
+            assert!(
+                value.re.is_finite() && value.im.is_finite(),
+                "debug-validate: the element ({}, {}) is not finite",
+                row,
+                col
+            );
+        }
+    }
+}
+
+impl<T: Float> Csr<T> {
+    /// Asserts that the row pointers are consistent and that every row has sorted,
+    /// in-bounds column indices without duplicates.
+    pub(crate) fn debug_validate(&self) {
+        if !cfg!(feature = "debug-validate") {
+            return;
+        }
+        assert!(
+            self.indptr.len() == self.n + 1 && self.indptr[0] == 0,
+            "debug-validate: invalid row pointers"
+        );
+        assert_eq!(
+            self.indptr[self.n],
+            self.indices.len(),
+            "debug-validate: the row pointers do not match the number of elements"
+        );
+        assert_eq!(
+            self.indices.len(),
+            self.values.len(),
+            "debug-validate: the index and value arrays have different lengths"
+        );
+        for row in 0..self.n {
+            let indices = &self.indices[self.indptr[row]..self.indptr[row + 1]];
+            assert!(
+                indices.windows(2).all(|pair| pair[0] < pair[1]),
+                "debug-validate: row {} is not sorted or has duplicates",
+                row
+            );
+            assert!(
+                indices.iter().all(|col| *col < self.n),
+                "debug-validate: row {} has a column out of bounds",
+                row
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::dense::DenseLu;
+use crate::{ComplexMatrix, SolveError};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A disc of the complex plane that is guaranteed to contain a solution component.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Enclosure<T: Float> {
+    pub center: Complex<T>,
+    pub radius: T,
+}
+
+impl<T: Float> Enclosure<T> {
+    /// Returns `true` if ```z``` lies in the disc.
+    pub fn contains(&self, z: Complex<T>) -> bool {
+        (z - self.center).norm() <= self.radius
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` and return rigorous enclosures of the solution.
+    ///
+    /// The approximate solution `x̃` of a dense LU factorization is verified with its
+    /// approximate inverse `R`: if `‖I - RA‖ < 1`, the error `x - x̃` is bounded from the
+    /// residual `b - Ax̃`. Rounding errors of the verification itself are bounded with
+    /// the usual `γ_k = ku / (1 - ku)` terms, so each returned disc contains the exact
+    /// solution component of the stored system.
+    ///
+    /// The work is dense and cubic in the matrix size, so this is meant for small and
+    /// medium systems. Returns `Err` if `b` does not match the matrix size, the matrix
+    /// is singular or too ill-conditioned to verify the solution.
+    ///```rust
+    /// use sparse_complex::ComplexMatrix;
+    /// use num::Complex;
+    ///
+    /// let mut m = ComplexMatrix::<f64>::new();
+    /// m.add_element(0, 0, Complex::new(3., 1.));
+    /// m.add_element(0, 1, Complex::new(1., 0.));
+    /// m.add_element(1, 1, Complex::new(0., 2.));
+    ///
+    /// // The exact solution is x = (1, i).
+    /// let b = [Complex::new(3., 2.), Complex::new(-2., 0.)];
+    /// let x = m.solve_verified(&b).unwrap();
+    /// assert!(x[0].contains(Complex::new(1., 0.)));
+    /// assert!(x[1].contains(Complex::new(0., 1.)));
+    /// assert!(x[0].radius < 1e-12);
+    ///```
+    pub fn solve_verified(&self, b: &[Complex<T>]) -> Result<Vec<Enclosure<T>>, SolveError> {
+        let n = self.dimension();
+        if b.len() != n {
+            return Err(SolveError::DimensionMismatch);
+        }
+        let a = self.to_dense(n);
+        let lu = DenseLu::new(n, a.clone())?;
+        let mut x = b.to_vec();
+        lu.solve(&mut x);
+        let r = lu.inverse();
+
+        // Complex products and sums of up to n terms, with a safety factor for the
+        // complex multiplication.
+        let g = gamma::<T>(2 * n + 4) * T::from(2).unwrap();
+        let zero = Complex::new(T::zero(), T::zero());
+
+        // Residual b - Ax̃ and a bound of its rounding error.
+        let mut residual = vec![zero; n];
+        let mut residual_error = vec![T::zero(); n];
+        for i in 0..n {
+            let row = &a[i * n..(i + 1) * n];
+            let (sum, abs) = row.iter().zip(x.iter()).fold(
+                (b[i], b[i].norm()),
+                |(sum, abs), (aij, xj)| (sum - *aij * *xj, abs + aij.norm() * xj.norm()),
+            );
+            residual[i] = sum;
+            residual_error[i] = g * abs;
+        }
+
+        // |A| e, used to bound the rounding error of RA.
+        let abs_a_ones = (0..n)
+            .map(|i| a[i * n..(i + 1) * n].iter().fold(T::zero(), |acc, v| acc + v.norm()))
+            .collect::<Vec<_>>();
+
+        let mut correction = vec![T::zero(); n];
+        let mut contraction = vec![T::zero(); n];
+        for i in 0..n {
+            let r_row = &r[i * n..(i + 1) * n];
+
+            // Bound of |R r| including the error of r and of the product.
+            let (rr, abs_rr, abs_r_err) = r_row.iter().enumerate().fold(
+                (zero, T::zero(), T::zero()),
+                |(sum, abs, err), (k, rik)| {
+                    (
+                        sum + *rik * residual[k],
+                        abs + rik.norm() * residual[k].norm(),
+                        err + rik.norm() * residual_error[k],
+                    )
+                },
+            );
+            correction[i] = rr.norm() + g * abs_rr + abs_r_err;
+
+            // Row sum of |I - RA| plus the rounding error of RA.
+            let mut row_sum = T::zero();
+            for j in 0..n {
+                let ra = r_row
+                    .iter()
+                    .enumerate()
+                    .fold(zero, |acc, (k, rik)| acc + *rik * a[k * n + j]);
+                let identity = if i == j { T::one() } else { T::zero() };
+                row_sum = row_sum + (Complex::new(identity, T::zero()) - ra).norm();
+            }
+            let abs_r_abs_a = r_row
+                .iter()
+                .zip(abs_a_ones.iter())
+                .fold(T::zero(), |acc, (rik, ak)| acc + rik.norm() * *ak);
+            contraction[i] = (row_sum + g * abs_r_abs_a) * (T::one() + g);
+        }
+
+        let alpha = contraction.iter().fold(T::zero(), |acc, c| acc.max(*c));
+        if alpha.is_nan() || alpha >= T::one() {
+            return Err(SolveError::NumericalIssue);
+        }
+        let max_correction = correction.iter().fold(T::zero(), |acc, c| acc.max(*c));
+        let delta = max_correction / (T::one() - alpha) * (T::one() + g);
+
+        let inflate = (T::one() + g) * (T::one() + g);
+        Ok(x.into_iter()
+            .zip(correction.iter().zip(contraction.iter()))
+            .map(|(center, (c, k))| Enclosure {
+                center,
+                radius: (*c + *k * delta) * inflate,
+            })
+            .collect())
+    }
+}
+
+/// The rounding error bound `ku / (1 - ku)`, infinite when `ku >= 1`.
+fn gamma<T: Float>(k: usize) -> T {
+    let ku = T::from(k).unwrap() * T::epsilon() / T::from(2).unwrap();
+    if ku < T::one() {
+        ku / (T::one() - ku)
+    } else {
+        T::infinity()
+    }
+}
@@ -0,0 +1,83 @@
+use crate::cancel;
+use crate::progress::{self, Phase};
+use crate::solver;
+use crate::{ComplexMatrix, SolvableFloat, SolveError};
+use num::complex::Complex;
+use num_traits::float::Float;
+use std::marker::PhantomData;
+
+/// Scratch state reused by ```solve_in_place``` across many solves.
+///
+/// The workspace owns the compressed matrix, the triplet-to-slot map and the Eigen
+/// solver. While the sparsity pattern of the solved matrices does not change, a solve
+/// only scatters the new values and refactorizes: the buffers and the symbolic
+/// analysis of the first solve are kept. A new pattern rebuilds them once. The
+/// numeric factorization still allocates its factors inside Eigen, so a solve is not
+/// free of heap allocations.
+pub struct SolveWorkspace<T: Float> {
+    context: solver::Context,
+    marker: PhantomData<T>,
+}
+
+impl<T: SolvableFloat> SolveWorkspace<T> {
+    /// Create an empty workspace. Buffers are allocated by the first solve.
+    ///
+    /// Returns `Err(SolveError::OutOfMemory)` if the Eigen solver cannot be allocated.
+    ///```rust
+    /// use sparse_complex::SolveWorkspace;
+    /// let workspace = SolveWorkspace::<f64>::new().unwrap();
+    ///```
+    pub fn new() -> Result<Self, SolveError> {
+        Ok(SolveWorkspace {
+            context: T::new_context()?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Approximate heap memory held by the workspace, including the LU factors of
+    /// the last successful factorization.
+    pub fn memory_usage_bytes(&self) -> usize {
+        unsafe { T::context_memory_usage(&self.context) }
+    }
+}
+
+impl<T: SolvableFloat> ComplexMatrix<T> {
+    /// Solve the system `Ax=b` using the buffers of ```workspace```.
+    ///
+    /// Repeated solves of matrices with the same pattern reuse the compressed matrix,
+    /// the triplet-to-slot map and the symbolic analysis instead of rebuilding them on
+    /// every call. This reduces the allocations per call but does not remove them:
+    /// Eigen still allocates the LU factors of each numeric factorization.
+    ///
+    /// Returns `Err` if the factorization fails. The result is stored in `b`. A
+    /// cancellation token is checked before the factorization, not while Eigen runs it.
+    ///```rust
+    /// use sparse_complex::{ComplexMatrix, SolveWorkspace};
+    /// use num::Complex;
+    ///
+    /// let mut workspace = SolveWorkspace::<f64>::new().unwrap();
+    /// for k in 1..4 {
+    ///     let mut m = ComplexMatrix::<f64>::new();
+    ///     m.add_element(0, 0, Complex::new(k as f64, 0.));
+    ///     m.add_element(1, 1, Complex::new(0., 2.));
+    ///
+    ///     let mut b = vec![Complex::new(k as f64, 0.), Complex::new(0., 2.)];
+    ///     m.solve_in_place(&mut workspace, &mut b).unwrap();
+    ///     assert_eq!(b, vec![Complex::new(1., 0.), Complex::new(1., 0.)]);
+    /// }
+    ///```
+    pub fn solve_in_place(
+        &self,
+        workspace: &mut SolveWorkspace<T>,
+        b: &mut [Complex<T>],
+    ) -> Result<(), SolveError> {
+        self.check_rhs(b.len())?;
+        cancel::check()?;
+        progress::report(Phase::Factorization, Some(0.));
+        self.debug_validate(b.len());
+        let info = unsafe { T::context_factorize_solve(&mut workspace.context, self, b) };
+        solver::info_to_result(info)?;
+        progress::report(Phase::Solve, Some(1.));
+        Ok(())
+    }
+}
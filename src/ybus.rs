@@ -0,0 +1,130 @@
+use crate::ComplexMatrix;
+use alloc::vec;
+use num::complex::Complex;
+use num_traits::float::Float;
+
+/// A branch of a power network in the π model: a line, or a transformer with an
+/// ideal off-nominal tap and phase shifter on its ```from``` side.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Branch<T: Float> {
+    pub from: usize,
+    pub to: usize,
+    /// The series impedance `r + jx`.
+    pub impedance: Complex<T>,
+    /// The total line charging susceptance `b`, split half at each end.
+    pub charging: T,
+    /// The off-nominal turns ratio, `1` for a line.
+    pub tap: T,
+    /// The phase shift angle in radians, `0` for a line.
+    pub phase_shift: T,
+}
+
+impl<T: Float> Branch<T> {
+    /// A line between ```from``` and ```to```.
+    pub fn line(from: usize, to: usize, impedance: Complex<T>, charging: T) -> Self {
+        Branch {
+            from,
+            to,
+            impedance,
+            charging,
+            tap: T::one(),
+            phase_shift: T::zero(),
+        }
+    }
+
+    /// A transformer with its tap on the ```from``` side.
+    pub fn transformer(
+        from: usize,
+        to: usize,
+        impedance: Complex<T>,
+        tap: T,
+        phase_shift: T,
+    ) -> Self {
+        Branch {
+            from,
+            to,
+            impedance,
+            charging: T::zero(),
+            tap,
+            phase_shift,
+        }
+    }
+}
+
+/// A shunt admittance `g + jb` from a bus to the ground.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shunt<T: Float> {
+    pub bus: usize,
+    pub admittance: Complex<T>,
+}
+
+impl<T: Float> Shunt<T> {
+    /// A shunt ```admittance``` at ```bus```.
+    pub fn new(bus: usize, admittance: Complex<T>) -> Self {
+        Shunt { bus, admittance }
+    }
+}
+
+impl<T: Float> ComplexMatrix<T> {
+    /// Assemble the bus admittance matrix `Ybus` of a network of ```n``` buses.
+    ///
+    /// With `ys = 1 / z` and the complex tap `t = tap·e^{jθ}`, each branch stamps
+    ///
+    /// ```text
+    /// Yff = (ys + jb/2) / |t|²    Yft = -ys / conj(t)
+    /// Ytf = -ys / t               Ytt = ys + jb/2
+    /// ```
+    ///
+    /// and each shunt adds its admittance to the diagonal. Parallel branches are
+    /// summed. Every bus gets a diagonal entry, so the matrix has order ```n``` even if
+    /// the last buses are isolated.
+    ///
+    /// Panics if a branch or a shunt has a bus out of ```0..n```, or if a branch
+    /// connects a bus to itself.
+    ///```rust
+    /// use sparse_complex::{Branch, ComplexMatrix, Shunt};
+    /// use num::Complex;
+    ///
+    /// let branches = [
+    ///     Branch::line(0, 1, Complex::new(0., 0.5), 0.),
+    ///     Branch::transformer(1, 2, Complex::new(0., 0.25), 1., 0.),
+    /// ];
+    /// let shunts = [Shunt::new(2, Complex::new(1., 0.))];
+    ///
+    /// let y = ComplexMatrix::<f64>::admittance_from_branches(3, &branches, &shunts);
+    /// assert_eq!(y.get(0, 1), Some(&Complex::new(0., 2.)));
+    /// assert_eq!(y.get(1, 1), Some(&Complex::new(0., -6.)));
+    /// assert_eq!(y.get(2, 2), Some(&Complex::new(1., -4.)));
+    ///```
+    pub fn admittance_from_branches(n: usize, branches: &[Branch<T>], shunts: &[Shunt<T>]) -> Self {
+        let zero = Complex::new(T::zero(), T::zero());
+        let two = T::one() + T::one();
+        let mut diagonal = vec![zero; n];
+        let mut m = ComplexMatrix::with_capacity(2 * branches.len() + n);
+        for branch in branches {
+            let (f, t) = (branch.from, branch.to);
+            assert!(
+                f < n && t < n,
+                "the branch ({}, {}) has a bus out of bounds",
+                f,
+                t
+            );
+            assert_ne!(f, t, "the branch ({}, {}) connects a bus to itself", f, t);
+            let ys = branch.impedance.inv();
+            let tap = Complex::from_polar(branch.tap, branch.phase_shift);
+            let ytt = ys + Complex::new(T::zero(), branch.charging / two);
+            diagonal[f] = diagonal[f] + ytt / tap.norm_sqr();
+            diagonal[t] = diagonal[t] + ytt;
+            m.add_element(f, t, -ys / tap.conj());
+            m.add_element(t, f, -ys / tap);
+        }
+        for shunt in shunts {
+            assert!(shunt.bus < n, "the shunt at {} is out of bounds", shunt.bus);
+            diagonal[shunt.bus] = diagonal[shunt.bus] + shunt.admittance;
+        }
+        for (i, d) in diagonal.into_iter().enumerate() {
+            m.add_element(i, i, d);
+        }
+        m
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg(feature = "f64")]
 #[cfg(test)]
 mod tests {
     use super::data;
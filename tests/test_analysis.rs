@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_gershgorin_sums_duplicates() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 0, Complex::new(1., 1.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(-1., 1.));
+        m.add_element(1, 1, Complex::new(3., 0.));
+
+        let discs = m.gershgorin_discs();
+        assert_eq!(discs.len(), 2);
+        assert_eq!(discs[0].0, Complex::new(2., 1.));
+        assert_abs_diff_eq!(discs[0].1, 1., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_gershgorin_missing_diagonal() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 1, Complex::new(0., 2.));
+        m.add_element(1, 0, Complex::new(0., -2.));
+
+        let discs = m.gershgorin_discs();
+        assert_eq!(discs[0], (Complex::new(0., 0.), 2.));
+        assert_eq!(discs[1], (Complex::new(0., 0.), 2.));
+    }
+
+    #[test]
+    fn test_spectrum_bounds_empty() {
+        let m = ComplexMatrix::<f64>::new();
+        assert_eq!(m.spectrum_bounds(), None);
+    }
+
+    #[test]
+    fn test_spectral_radius_dominant_negative() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(-5., 0.));
+        m.add_element(1, 1, Complex::new(2., 0.));
+        m.add_element(2, 2, Complex::new(0., 1.));
+        m.add_element(0, 2, Complex::new(1., 0.));
+
+        let rho = m.spectral_radius_estimate(1e-12, 500).unwrap();
+        assert_abs_diff_eq!(rho, 5., epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_spectral_radius_nilpotent() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 0.));
+
+        let rho = m.spectral_radius_estimate(1e-12, 100).unwrap();
+        assert_abs_diff_eq!(rho, 0., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_spectral_radius_not_converged() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1.1, 0.));
+        assert!(m.spectral_radius_estimate(1e-15, 2).is_err());
+    }
+
+    #[test]
+    fn test_dominance_detects_sign_error() {
+        // Assembling the (1, 0) coupling twice breaks the dominance of row 1.
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(2., 0.));
+        m.add_element(0, 1, Complex::new(-1., 0.));
+        m.add_element(1, 0, Complex::new(-1., 0.));
+        m.add_element(1, 0, Complex::new(-1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let margins = m.row_dominance_margins();
+        assert_abs_diff_eq!(margins[0], 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(margins[1], -1., epsilon = 1e-12);
+        assert!(!m.is_diagonally_dominant(false));
+    }
+}
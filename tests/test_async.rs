@@ -0,0 +1,77 @@
+#![cfg(feature = "async")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+    use std::thread::{self, Thread};
+
+    /// Wakes the thread polling the future.
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A minimal executor, parking the test thread until the future is woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Arc::new(Unpark(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match Pin::as_mut(&mut future).poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    fn ladder(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.5));
+                m.add_element(i + 1, i, Complex::new(-1., -0.5));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_solve_async() {
+        let m = ladder(40);
+        let b = (0..40).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+
+        let x = block_on(m.solve_async(b.clone())).unwrap();
+        let mut expected = b;
+        m.solve(&mut expected).unwrap();
+        assert!(x.iter().zip(&expected).all(|(a, e)| (a - e).norm() < 1e-12));
+
+        let pending = (1..5)
+            .map(|k| ladder(10 * k).solve_async(vec![Complex::new(1., 0.); 10 * k]))
+            .collect::<Vec<_>>();
+        for (k, future) in (1..5).zip(pending) {
+            assert_eq!(block_on(future).unwrap().len(), 10 * k);
+        }
+    }
+
+    #[test]
+    fn test_solve_async_errors() {
+        let m = ladder(3);
+        let result = block_on(m.solve_async(vec![Complex::new(1., 0.); 2]));
+        assert_eq!(result, Err(SolveError::DimensionMismatch));
+
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        singular.add_element(1, 1, Complex::new(0., 0.));
+        let result = block_on(singular.solve_async(vec![Complex::new(1., 0.); 2]));
+        assert!(result.is_err());
+    }
+}
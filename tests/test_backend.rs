@@ -0,0 +1,79 @@
+#![cfg(feature = "f64")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// An unsymmetric complex tridiagonal matrix with an empty first diagonal entry.
+    fn matrix(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            if i > 0 {
+                m.add_element(i, i, Complex::new(3., i as f64));
+                m.add_element(i, i - 1, Complex::new(-1., 0.5));
+            }
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(2., -1.));
+            }
+        }
+        m
+    }
+
+    fn residual(m: &ComplexMatrix<f64>, x: &[Complex<f64>], b: &[Complex<f64>]) -> f64 {
+        m.residual_norm(x, b).unwrap().norm_inf
+    }
+
+    fn backends() -> Vec<Box<dyn SolverBackend<f64>>> {
+        #[allow(unused_mut)]
+        let mut backends: Vec<Box<dyn SolverBackend<f64>>> = vec![Box::new(RustBackend)];
+        #[cfg(feature = "eigen")]
+        backends.push(Box::new(EigenBackend));
+        backends
+    }
+
+    #[test]
+    fn test_backend_solve() {
+        let m = matrix(12);
+        let b = (0..12).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+        for backend in backends() {
+            let lu = backend.factorize(&m).unwrap();
+            assert_eq!(lu.size(), 12);
+            let mut x = b.clone();
+            lu.solve(&mut x).unwrap();
+            assert!(residual(&m, &x, &b) < 1e-12, "{}", backend.name());
+
+            let mut y = b.clone();
+            m.solve_with_backend(backend.as_ref(), &mut y).unwrap();
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn test_backend_solve_adjoint() {
+        let m = matrix(12);
+        let mut adjoint = m.clone();
+        adjoint.transpose_in_place();
+        adjoint.conj_in_place();
+        let b = (0..12).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+        for backend in backends() {
+            let mut x = b.clone();
+            backend.factorize(&m).unwrap().solve_adjoint(&mut x).unwrap();
+            assert!(residual(&adjoint, &x, &b) < 1e-12, "{}", backend.name());
+        }
+    }
+
+    #[test]
+    fn test_backend_errors() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 0.));
+        for backend in backends() {
+            assert!(backend.factorize(&m).is_err());
+            let lu = backend.factorize(&matrix(3)).unwrap();
+            let mut short = vec![Complex::new(1., 0.); 2];
+            assert_eq!(lu.solve(&mut short), Err(SolveError::DimensionMismatch));
+            assert_eq!(lu.solve_adjoint(&mut short), Err(SolveError::DimensionMismatch));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn pentadiagonal(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            // A small diagonal forces row interchanges.
+            m.add_element(i, i, Complex::new(0.1, 0.05 * i as f64));
+            for (offset, value) in [(1, Complex::new(2., 1.)), (2, Complex::new(-1., 0.5))] {
+                if i + offset < n {
+                    m.add_element(i, i + offset, value);
+                    m.add_element(i + offset, i, value.conj() * 0.5);
+                }
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_banded_solve_with_pivoting() {
+        let n = 30;
+        let m = pentadiagonal(n);
+        assert_eq!(m.bandwidths(), (2, 2));
+        let banded = BandedMatrix::from_matrix(&m);
+        assert_eq!(banded.bandwidths(), (2, 2));
+        assert_eq!(banded.get(3, 5), Complex::new(-1., 0.5));
+        assert_eq!(banded.get(0, 5), Complex::new(0., 0.));
+
+        let x = (0..n)
+            .map(|i| Complex::new(i as f64, 1.))
+            .collect::<Vec<_>>();
+        let mut b = m.pow_apply(1, &x);
+        banded.solve(&mut b).unwrap();
+        for (bi, xi) in b.iter().zip(x.iter()) {
+            assert!((bi - xi).norm() < 1e-9, "{} != {}", bi, xi);
+        }
+    }
+
+    #[test]
+    fn test_singular_band() {
+        let mut m = BandedMatrix::<f64>::new(3, 1, 0);
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(2, 1, Complex::new(1., 0.));
+        let mut b = vec![Complex::new(1., 0.); 3];
+        assert!(m.solve(&mut b).is_err());
+        assert!(m.solve(&mut [Complex::new(1., 0.)]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_element_out_of_band() {
+        let mut m = BandedMatrix::<f64>::new(4, 1, 1);
+        m.add_element(0, 2, Complex::new(1., 0.));
+    }
+}
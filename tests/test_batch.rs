@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn system(k: usize) -> (ComplexMatrix<f64>, Vec<Complex<f64>>) {
+        let n = 4;
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            // Zero diagonal in the first row forces a row exchange.
+            if i > 0 {
+                m.add_element(i, i, Complex::new(3. + k as f64, 1.));
+            }
+            m.add_element(i, (i + 1) % n, Complex::new(1., -(k as f64)));
+            m.add_element((i + 2) % n, i, Complex::new(0.5, 0.));
+        }
+        let x = (0..n).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|i| (0..n).filter_map(|j| m.get(i, j).map(|v| v * x[j])).sum())
+            .collect();
+        (m, b)
+    }
+
+    #[test]
+    fn test_batch_recovers_solutions() {
+        let mut systems = (0..50)
+            .map(|k| {
+                let (m, b) = system(k);
+                SmallSystem::from_matrix(&m, b)
+            })
+            .collect::<Vec<_>>();
+        let status = solve_batch_small(&mut systems);
+        assert!(status.iter().all(|s| s.is_ok()));
+        for s in &systems {
+            for (i, xi) in s.rhs().iter().enumerate() {
+                assert!((xi - Complex::new(i as f64, 1.)).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_reports_singular_systems() {
+        let zero = Complex::new(0., 0.);
+        let one = Complex::new(1., 0.);
+        let mut systems = vec![
+            SmallSystem::<f32>::new(2, vec![one, zero, zero, one], vec![one, one]),
+            SmallSystem::<f32>::new(2, vec![one, one, one, one], vec![one, one]),
+        ];
+        let status = solve_batch_small(&mut systems);
+        assert!(status[0].is_ok());
+        assert!(status[1].is_err());
+        assert_eq!(systems[0].rhs(), &[one, one]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_batch_par_solves_sparse_systems() {
+        let mut systems = (0..50).map(system).collect::<Vec<_>>();
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        systems.push((singular, vec![Complex::new(1., 0.); 2]));
+
+        let solutions = with_num_threads(2, || solve_batch_par(&systems));
+        assert_eq!(solutions.len(), 51);
+        assert_eq!(solutions[50], Err(SolveError::Singular));
+        for x in &solutions[..50] {
+            let x = x.as_ref().unwrap();
+            for (i, xi) in x.iter().enumerate() {
+                assert!((xi - Complex::new(i as f64, 1.)).norm() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn block(values: [f64; 4]) -> Vec<Complex<f64>> {
+        values.iter().map(|v| Complex::new(*v, -*v)).collect()
+    }
+
+    #[test]
+    fn test_block_to_scalar_indices() {
+        let mut m = BlockMatrix::<f64>::new(2);
+        m.add_block(0, 0, &block([1., 2., 3., 4.]));
+        m.add_block(1, 0, &block([5., 6., 7., 8.]));
+
+        let scalar = m.to_complex_matrix();
+        assert_eq!(scalar.get(0, 1), Some(&Complex::new(2., -2.)));
+        assert_eq!(scalar.get(1, 0), Some(&Complex::new(3., -3.)));
+        assert_eq!(scalar.get(2, 0), Some(&Complex::new(5., -5.)));
+        assert_eq!(scalar.get(3, 1), Some(&Complex::new(8., -8.)));
+        assert_eq!(scalar.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_block_scalar_get_matches_block() {
+        let mut m = BlockMatrix::<f64>::new(2);
+        m.add_block(2, 1, &block([1., 2., 3., 4.]));
+
+        assert_eq!(m.num_blocks(), 1);
+        assert_eq!(m.get(5, 2), Some(&Complex::new(3., -3.)));
+        assert_eq!(m.get(4, 3), Some(&Complex::new(2., -2.)));
+        assert_eq!(m.get(2, 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_block_wrong_length() {
+        let mut m = BlockMatrix::<f64>::new(3);
+        m.add_block(0, 0, &block([1., 2., 3., 4.]));
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn block(s: usize, seed: f64, diagonal: f64) -> Vec<Complex<f64>> {
+        (0..s * s)
+            .map(|k| {
+                let d = if k / s == k % s { diagonal } else { 0. };
+                Complex::new(
+                    d + (seed + k as f64).sin() * 0.3,
+                    (seed * 1.7 + k as f64).cos() * 0.3,
+                )
+            })
+            .collect()
+    }
+
+    /// Builds a random block-diagonally dominant matrix and its scalar copy.
+    fn build(n: usize, s: usize, periodic: bool) -> (BlockTridiagonal<f64>, ComplexMatrix<f64>) {
+        let mut m = if periodic {
+            BlockTridiagonal::periodic(n, s)
+        } else {
+            BlockTridiagonal::new(n, s)
+        };
+        let mut full = ComplexMatrix::new();
+        let add =
+            |full: &mut ComplexMatrix<f64>, bi: usize, bj: usize, values: &[Complex<f64>]| {
+                for (k, v) in values.iter().enumerate() {
+                    full.add_element(bi * s + k / s, bj * s + k % s, *v);
+                }
+            };
+        for i in 0..n {
+            let d = block(s, i as f64, 4.);
+            m.set_diagonal(i, &d);
+            add(&mut full, i, i, &d);
+            if i > 0 || periodic {
+                let l = block(s, 10. + i as f64, 0.);
+                m.set_lower(i, &l);
+                add(&mut full, i, (i + n - 1) % n, &l);
+            }
+            if i + 1 < n || periodic {
+                let u = block(s, 20. + i as f64, 0.);
+                m.set_upper(i, &u);
+                add(&mut full, i, (i + 1) % n, &u);
+            }
+        }
+        (m, full)
+    }
+
+    fn check(n: usize, s: usize, periodic: bool) {
+        let (m, full) = build(n, s, periodic);
+        let x = (0..n * s)
+            .map(|i| Complex::new(i as f64 * 0.1, 1. - i as f64 * 0.05))
+            .collect::<Vec<_>>();
+        let mut b = full.pow_apply(1, &x);
+        m.solve(&mut b).unwrap();
+        for (bi, xi) in b.iter().zip(x.iter()) {
+            assert!(
+                (bi - xi).norm() < 1e-10,
+                "n = {}, periodic = {}: {} != {}",
+                n,
+                periodic,
+                bi,
+                xi
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        for n in [1, 2, 3, 4, 9] {
+            check(n, 3, false);
+        }
+    }
+
+    #[test]
+    fn test_solve_periodic() {
+        for n in [1, 2, 3, 4, 9] {
+            check(n, 2, true);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_corner_of_non_periodic_matrix() {
+        let mut m = BlockTridiagonal::<f64>::new(3, 1);
+        m.set_lower(0, &[Complex::new(1., 0.)]);
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_parallel_assembly_matches_serial() {
+        let n = 400;
+        let threads = 4;
+        let builder = Arc::new(ConcurrentMatrixBuilder::<f64>::new());
+        let handles = (0..threads)
+            .map(|t| {
+                let builder = Arc::clone(&builder);
+                thread::spawn(move || {
+                    let mut shard = builder.shard(2 * n / threads);
+                    for i in (t..n).step_by(threads) {
+                        shard.add_element(i, i, Complex::new(2., i as f64));
+                        shard.add_element(i, (i + 1) % n, Complex::new(-1., 0.));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let m = Arc::try_unwrap(builder).ok().unwrap().build();
+        for i in 0..n {
+            assert_eq!(m.get(i, i), Some(&Complex::new(2., i as f64)));
+            assert_eq!(m.get(i, (i + 1) % n), Some(&Complex::new(-1., 0.)));
+        }
+        assert_eq!(m.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_empty_builder() {
+        let builder = ConcurrentMatrixBuilder::<f32>::new();
+        drop(builder.shard(10));
+        let m = builder.build();
+        assert_eq!(m, ComplexMatrix::new());
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "f64")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn ladder(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.5));
+                m.add_element(i + 1, i, Complex::new(-1., -0.5));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_cancelled_solve() {
+        let m = ladder(200);
+        let b = vec![Complex::new(1., 0.); 200];
+        let token = CancellationToken::new();
+
+        let mut x = b.clone();
+        assert_eq!(with_cancellation(&token, || m.solve(&mut x)), Ok(()));
+
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        let mut y = b.clone();
+        assert_eq!(
+            with_cancellation(&token, || m.solve(&mut y)),
+            Err(SolveError::Cancelled)
+        );
+        assert_eq!(y, b);
+        let lu = with_cancellation(&token, || m.factorize_sparse());
+        assert_eq!(lu.err(), Some(SolveError::Cancelled));
+
+        // The token only applies inside with_cancellation.
+        assert_eq!(m.solve(&mut y), Ok(()));
+        assert_eq!(y, x);
+        assert_eq!(SolveError::Cancelled.to_string(), "the operation was cancelled");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_cancelled_batch() {
+        let systems = (1..20)
+            .map(|k| (ladder(10 * k), vec![Complex::new(1., 0.); 10 * k]))
+            .collect::<Vec<_>>();
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+
+        // Cancel once the first system is solved.
+        let solutions = with_progress(
+            move |_, fraction| {
+                if fraction > Some(0.) {
+                    canceller.cancel();
+                }
+            },
+            || with_cancellation(&token, || with_num_threads(1, || solve_batch_par(&systems))),
+        );
+        assert!(solutions[0].is_ok());
+        assert!(solutions[1..].iter().all(|s| *s == Err(SolveError::Cancelled)));
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_validate_locates_non_finite_elements() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        assert_eq!(m.validate(), Ok(()));
+
+        m.add_element(1, 0, Complex::new(f64::NAN, 0.));
+        m.add_element(0, 1, Complex::new(0., f64::NEG_INFINITY));
+        assert_eq!(
+            m.validate(),
+            Err(SolveError::NonFiniteElement { row: 1, col: 0 })
+        );
+        assert_eq!(
+            m.validate().unwrap_err().to_string(),
+            "the element (1, 0) is not finite"
+        );
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_solve_checked() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(2., 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let mut b = vec![Complex::new(3., 0.), Complex::new(f32::INFINITY, 0.)];
+        assert_eq!(
+            m.solve_checked(&mut b),
+            Err(SolveError::NonFiniteRhs { index: 1 })
+        );
+        assert_eq!(b[0], Complex::new(3., 0.));
+
+        b[1] = Complex::new(1., 0.);
+        assert_eq!(m.solve_checked(&mut b), Ok(()));
+        assert_eq!(b, vec![Complex::new(1., 0.); 2]);
+
+        m.add_element(1, 0, Complex::new(f32::NAN, f32::NAN));
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert_eq!(
+            m.solve_checked(&mut b),
+            Err(SolveError::NonFiniteElement { row: 1, col: 0 })
+        );
+        assert_eq!(
+            SolveError::NonFiniteRhs { index: 1 }.to_string(),
+            "the element 1 of the right-hand side is not finite"
+        );
+    }
+}
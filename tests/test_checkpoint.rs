@@ -0,0 +1,39 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_checkpoint_resume_assembly() {
+        let path = std::env::temp_dir().join("sparse_complex_test_checkpoint.bin");
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(2, 0, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(0., 1.));
+        m.add_element(2, 0, Complex::new(1., 0.));
+        m.compact();
+        m.save_checkpoint(&path).unwrap();
+
+        let mut resumed = ComplexMatrix::<f32>::load_checkpoint(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(resumed, m);
+        assert_eq!(resumed.get(2, 0), Some(&Complex::new(2., 0.)));
+
+        resumed.add_element(1, 1, Complex::new(3., 0.));
+        resumed.add_element(2, 0, Complex::new(-2., 0.));
+        resumed.compact();
+        assert_eq!(resumed.get(2, 0), Some(&Complex::new(0., 0.)));
+        assert_eq!(resumed.get(1, 1), Some(&Complex::new(3., 0.)));
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_invalid_buffers() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        let bytes = m.to_checkpoint();
+
+        assert!(ComplexMatrix::<f32>::from_checkpoint(&bytes).is_err());
+        assert!(ComplexMatrix::<f64>::from_checkpoint(&bytes[..bytes.len() - 1]).is_err());
+        assert!(ComplexMatrix::<f64>::from_checkpoint(b"not a checkpoint at all").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "cuda")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// The nodal admittance matrix of a ladder of ```n``` nodes with a voltage source
+    /// branch current as the last unknown, as in modified nodal analysis.
+    fn mna(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        let y = Complex::new(1., -2.);
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(0.1, 0.3));
+            if i + 1 < n {
+                m.add_element(i, i, y);
+                m.add_element(i + 1, i + 1, y);
+                m.add_element(i, i + 1, -y);
+                m.add_element(i + 1, i, -y);
+            }
+        }
+        m.add_element(0, n, Complex::new(1., 0.));
+        m.add_element(n, 0, Complex::new(1., 0.));
+        m
+    }
+
+    #[test]
+    fn test_cuda_solve() {
+        let m = mna(30);
+        let qr = m.upload_cuda().unwrap();
+        assert_eq!(qr.size(), 31);
+        let b = (0..31).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+
+        let mut x = b.clone();
+        qr.solve(&mut x).unwrap();
+        assert!(m.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+
+        let mut adjoint = m.clone();
+        adjoint.transpose_in_place();
+        adjoint.conj_in_place();
+        let mut x = b.clone();
+        qr.solve_adjoint(&mut x).unwrap();
+        assert!(adjoint.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+
+        let mut y = b.clone();
+        m.solve_with_backend(&CudaBackend, &mut y).unwrap();
+        let mut expected = b;
+        m.solve(&mut expected).unwrap();
+        assert!(y.iter().zip(&expected).all(|(a, e)| (a - e).norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_cuda_errors() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 0.));
+        // The matrix is only factorized by the solves.
+        let qr = m.upload_cuda().unwrap();
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert_eq!(qr.solve(&mut b), Err(SolveError::Singular));
+
+        let qr = mna(3).upload_cuda().unwrap();
+        let mut short = vec![Complex::new(1., 0.); 3];
+        assert_eq!(qr.solve(&mut short), Err(SolveError::DimensionMismatch));
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_error_variants() {
+        let mut m = BandedMatrix::<f64>::new(2, 1, 1);
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert_eq!(m.solve(&mut b), Err(SolveError::Singular));
+        assert_eq!(m.solve(&mut b[..1]), Err(SolveError::DimensionMismatch));
+        assert!(matches!(
+            Permutation::from_vec(vec![1, 1]),
+            Err(SolveError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_error_trait() {
+        let error: Box<dyn std::error::Error> = Box::new(SolveError::Singular);
+        assert_eq!(error.to_string(), "the matrix is singular");
+        assert_eq!(
+            SolveError::InvalidInput("bad ordering").to_string(),
+            "invalid input: bad ordering"
+        );
+        assert_eq!(SolveError::OutOfMemory.to_string(), "out of memory");
+        assert_eq!(SolveError::Internal.to_string(), "internal solver error");
+    }
+
+    #[cfg(all(
+        feature = "eigen",
+        feature = "f64",
+        target_pointer_width = "64",
+        not(feature = "index32")
+    ))]
+    #[test]
+    fn test_out_of_memory() {
+        // The compressed matrix of order 2^50 needs petabytes for its column offsets:
+        // Eigen throws std::bad_alloc, which comes back as an error instead of aborting.
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(1 << 50, 1 << 50, Complex::new(1., 0.));
+        assert_eq!(m.factorize().err(), Some(SolveError::OutOfMemory));
+    }
+}
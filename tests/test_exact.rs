@@ -0,0 +1,70 @@
+#![cfg(feature = "exact")]
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use num::{BigRational, Complex, ToPrimitive};
+    use sparse_complex::*;
+
+    fn rational(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_exact_full_matrix() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(5., 3.));
+        m.add_element(1, 1, Complex::new(1., -9.));
+        m.add_element(0, 1, Complex::new(-33., 0.));
+        m.add_element(1, 0, Complex::new(0., -47.));
+        let b = [Complex::new(13.4, 7.), Complex::new(3.2, -7.)];
+
+        let x = m.solve_exact(&b).unwrap();
+        let expected = [
+            Complex::new(0.21852826260018543, 0.10986007256547237),
+            Complex::new(-0.3829375425665308, -0.1756095408900631),
+        ];
+        for (e, v) in expected.iter().zip(x.iter()) {
+            assert_abs_diff_eq!(e.re, v.re.to_f64().unwrap(), epsilon = 1e-14);
+            assert_abs_diff_eq!(e.im, v.im.to_f64().unwrap(), epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_exact_needs_pivoting() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 1, Complex::new(2., 0.));
+        m.add_element(1, 0, Complex::new(0., 4.));
+        let x = m
+            .solve_exact(&[Complex::new(1., 0.), Complex::new(1., 0.)])
+            .unwrap();
+        assert_eq!(x[0], Complex::new(rational(0, 1), rational(-1, 4)));
+        assert_eq!(x[1], Complex::new(rational(1, 2), rational(0, 1)));
+    }
+
+    #[test]
+    fn test_exact_sums_duplicates() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 0, Complex::new(2., 0.));
+        let x = m.solve_exact(&[Complex::new(1., 0.)]).unwrap();
+        assert_eq!(x[0].re, rational(1, 3));
+    }
+
+    #[test]
+    fn test_exact_singular() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 1.));
+        m.add_element(0, 1, Complex::new(2., 2.));
+        m.add_element(1, 0, Complex::new(1., 1.));
+        m.add_element(1, 1, Complex::new(2., 2.));
+        let b = [Complex::new(1., 0.), Complex::new(1., 0.)];
+        assert!(m.solve_exact(&b).is_err());
+    }
+
+    #[test]
+    fn test_exact_rejects_nan() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(f64::NAN, 0.));
+        assert!(m.solve_exact(&[Complex::new(1., 0.)]).is_err());
+    }
+}
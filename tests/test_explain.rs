@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_explain_records_pivots_and_growth() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1e-3, 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        m.add_element(2, 2, Complex::new(0., 2.));
+
+        let explanation = m.explain_factorization();
+        let steps = explanation.steps();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].pivot_row, 1);
+        assert_eq!(steps[0].diagonal, 1e-3);
+        assert_eq!(steps[0].pivot, Complex::new(1., 0.));
+        assert!(steps.iter().all(|s| s.growth <= 1. && s.pivot_ratio <= 1.));
+        assert_eq!(explanation.permutation().as_slice(), &[1, 0, 2]);
+        assert_eq!(explanation.singular_at(), None);
+
+        let text = explanation.to_string();
+        assert!(text.starts_with("LU factorization of a 3 × 3 matrix"));
+        assert!(text.ends_with("row permutation: [1, 0, 2]"));
+    }
+
+    #[test]
+    fn test_explain_singular() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(2., 0.));
+        m.add_element(0, 1, Complex::new(2., 0.));
+        m.add_element(1, 1, Complex::new(4., 0.));
+
+        let explanation = m.explain_factorization();
+        assert_eq!(explanation.singular_at(), Some(1));
+        assert!(explanation.permutation().is_empty());
+        assert!(explanation.to_string().ends_with("no nonzero pivot at step 1"));
+    }
+}
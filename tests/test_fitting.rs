@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_vector_fit_recovers_poles_and_residues() {
+        let poles = [
+            Complex::new(-2., 10.),
+            Complex::new(-2., -10.),
+            Complex::new(-5., 60.),
+            Complex::new(-5., -60.),
+        ];
+        let residues = [
+            Complex::new(1., 2.),
+            Complex::new(1., -2.),
+            Complex::new(30., 0.),
+            Complex::new(30., 0.),
+        ];
+        let s = (1..300)
+            .map(|k| Complex::new(0., k as f64 * 0.4))
+            .collect::<Vec<_>>();
+        let response = s
+            .iter()
+            .map(|s| {
+                poles
+                    .iter()
+                    .zip(residues.iter())
+                    .fold(Complex::new(0.2, 0.) + s * 1e-3, |acc, (p, r)| {
+                        acc + r / (s - p)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let model = vector_fit(&s, &response, 4, 10).unwrap();
+        assert!(model.rms_error(&s, &response) < 1e-8);
+        for (p, r) in poles.iter().zip(residues.iter()) {
+            let n = (0..4)
+                .min_by(|i, j| {
+                    (model.poles()[*i] - p)
+                        .norm()
+                        .partial_cmp(&(model.poles()[*j] - p).norm())
+                        .unwrap()
+                })
+                .unwrap();
+            assert!((model.poles()[n] - p).norm() < 1e-6);
+            assert!((model.residues()[n] - r).norm() < 1e-5);
+        }
+        assert!((model.constant() - Complex::new(0.2, 0.)).norm() < 1e-6);
+        assert!((model.proportional() - Complex::new(1e-3, 0.)).norm() < 1e-8);
+        assert!(model.poles().iter().all(|p| p.re <= 0.));
+    }
+
+    #[test]
+    fn test_vector_fit_rejects_too_few_samples() {
+        let s = vec![Complex::new(0., 1.), Complex::new(0., 2.)];
+        let response = vec![Complex::new(1., 0.); 2];
+        assert!(vector_fit(&s, &response, 2, 3).is_err());
+        assert!(vector_fit(&s, &response[..1], 0, 3).is_err());
+    }
+}
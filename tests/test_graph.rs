@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_laplacian_rows_sum_to_zero() {
+        let edges = vec![
+            (0, 1, Complex::new(1., -1.)),
+            (1, 2, Complex::new(2., 0.)),
+            (2, 0, Complex::new(0., 3.)),
+            (1, 2, Complex::new(1., 0.)),
+            (2, 2, Complex::new(5., 5.)),
+        ];
+        let l = ComplexMatrix::<f64>::laplacian_from_edges(4, edges).to_compressed();
+        assert_eq!(l.size(), 4);
+        for row in 0..4 {
+            let sum = l.row(row).fold(Complex::new(0., 0.), |acc, (_, v)| acc + v);
+            assert_eq!(sum, Complex::new(0., 0.));
+        }
+        assert_eq!(l.get(1, 2), Some(&Complex::new(-3., 0.)));
+        assert_eq!(l.get(3, 3), Some(&Complex::new(0., 0.)));
+    }
+
+    #[test]
+    fn test_adjacency_is_symmetric() {
+        let edges = vec![(0, 2, Complex::new(1., 1.)), (1, 1, Complex::new(4., 0.))];
+        let a = ComplexMatrix::<f64>::adjacency_from_edges(3, edges);
+        assert_eq!(a.get(0, 2), a.get(2, 0));
+        assert_eq!(a.get(1, 1), Some(&Complex::new(4., 0.)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_edge_out_of_bounds() {
+        ComplexMatrix::<f64>::laplacian_from_edges(2, vec![(0, 2, Complex::new(1., 0.))]);
+    }
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "f16")]
+
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_half_matrix_halves_value_storage() {
+        let mut m = ComplexMatrix::<f32>::with_capacity(4);
+        let mut h = HalfMatrix::new();
+        for i in 0..4 {
+            m.add_element(i, i, Complex::new(1.5, i as f32));
+            h.add_element(i, i, Complex::new(1.5, i as f32));
+        }
+        assert_eq!(HalfMatrix::from_matrix(&m).unwrap(), h);
+        assert_eq!(h.to_f32(), m);
+        let index = if cfg!(feature = "index32") { 4 } else { std::mem::size_of::<usize>() };
+        let indices = 2 * 4 * index;
+        assert_eq!(
+            2 * (h.memory_usage_bytes() - indices),
+            m.memory_usage_bytes() - indices
+        );
+    }
+
+    #[test]
+    fn test_half_matrix_rejects_overflow() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(0., 7e4));
+        assert!(HalfMatrix::from_matrix(&m).is_err());
+        let result = std::panic::catch_unwind(|| {
+            HalfMatrix::new().add_element(0, 0, Complex::new(f32::NAN, 0.))
+        });
+        assert!(result.is_err());
+    }
+}
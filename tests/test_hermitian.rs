@@ -0,0 +1,55 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// The lower triangle of a Hermitian positive definite Helmholtz-like matrix.
+    fn helmholtz_lower(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 0.));
+            if i > 0 {
+                m.add_element(i, i - 1, Complex::new(-1., 0.5));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_solve_hermitian_matches_lu() {
+        let n = 30;
+        let lower = helmholtz_lower(n);
+        let mut full = lower.clone();
+        for i in 1..n {
+            full.add_element(i - 1, i, Complex::new(-1., -0.5));
+        }
+
+        let b = (0..n)
+            .map(|i| Complex::new(1., i as f64))
+            .collect::<Vec<_>>();
+        let (mut x, mut expected) = (b.clone(), b);
+        lower.solve_hermitian(&mut x).unwrap();
+        full.solve(&mut expected).unwrap();
+        for (xi, ei) in x.iter().zip(expected.iter()) {
+            assert!((xi - ei).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_solve_hermitian_errors() {
+        let m = helmholtz_lower(4);
+        assert_eq!(
+            m.solve_hermitian(&mut [Complex::new(1., 0.); 3]),
+            Err(SolveError::DimensionMismatch)
+        );
+
+        let mut indefinite = ComplexMatrix::<f64>::new();
+        indefinite.add_element(0, 0, Complex::new(1., 0.));
+        indefinite.add_element(1, 1, Complex::new(-1., 0.));
+        assert!(matches!(
+            indefinite.solve_hermitian(&mut [Complex::new(1., 0.); 2]),
+            Err(SolveError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_typed_indices_match_untyped_api() {
+        let mut typed = ComplexMatrix::<f64>::new();
+        typed.add_at(Row(2), Col(0), Complex::new(1., 2.));
+        typed.add_elements(&[(Row(0), Col(1), Complex::new(3., 0.))]);
+        let from_entries = ComplexMatrix::from_entries(vec![
+            (Row(2), Col(0), Complex::new(1., 2.)),
+            (Row(0), Col(1), Complex::new(3., 0.)),
+        ]);
+
+        let mut untyped = ComplexMatrix::<f64>::new();
+        untyped.add_element(2, 0, Complex::new(1., 2.));
+        untyped.add_element(0, 1, Complex::new(3., 0.));
+
+        assert_eq!(typed, untyped);
+        assert_eq!(from_entries, untyped);
+        assert_eq!(typed.get_at(Row(0), Col(1)), untyped.get(0, 1));
+        assert_eq!(typed.get_at(Row(1), Col(0)), None);
+        assert_eq!(usize::from(Row(4)), 4);
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "index32")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn ladder(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.5));
+                m.add_element(i + 1, i, Complex::new(-1., -0.5));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_index32_storage() {
+        let m = ComplexMatrix::<f64>::with_capacity(10);
+        assert_eq!(m.memory_usage_bytes(), 10 * (16 + 2 * 4));
+        assert!(solver_info().features.contains(&"index32"));
+
+        let m = ladder(50);
+        assert_eq!(m.get(49, 48), Some(&Complex::new(-1., -0.5)));
+        let triplets = m.triplets().collect::<Vec<_>>();
+        assert_eq!(triplets[1], Triplet::new(0, 1, Complex::new(-1., 0.5)));
+    }
+
+    #[test]
+    fn test_index32_solve() {
+        let m = ladder(50);
+        let b = (0..50).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+
+        let mut x = b.clone();
+        m.factorize_sparse().unwrap().solve(&mut x).unwrap();
+        assert!(m.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[should_panic(expected = "32-bit storage")]
+    fn test_index32_overflow() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(1 << 32, 0, Complex::new(1., 0.));
+    }
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use sparse_complex::*;
+
+    #[test]
+    fn test_solver_info() {
+        let info = solver_info();
+        let bits = if cfg!(feature = "index32") { 32 } else { usize::BITS };
+        assert_eq!(info.index_bits, bits);
+        if cfg!(feature = "eigen") {
+            assert_eq!(info.eigen_version, Some((3, 4, 0)));
+        } else {
+            assert_eq!(info.eigen_version, None);
+        }
+        assert!(info.features.contains(&"std"));
+        assert_eq!(info.features.contains(&"rayon"), cfg!(feature = "rayon"));
+        assert_eq!(info.features.contains(&"f16"), cfg!(feature = "f16"));
+        assert!(info.to_string().starts_with("sparse_complex "));
+    }
+}
@@ -0,0 +1,296 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// A nonsymmetric convection-diffusion-like matrix with a complex shift.
+    fn convection(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 0.5));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1.5, 0.));
+                m.add_element(i + 1, i, Complex::new(-0.5, 0.2));
+            }
+            if i + 10 < n {
+                m.add_element(i, i + 10, Complex::new(-0.5, 0.));
+                m.add_element(i + 10, i, Complex::new(-0.5, 0.));
+            }
+        }
+        m
+    }
+
+    fn rhs(n: usize) -> Vec<Complex<f64>> {
+        (0..n).map(|i| Complex::new((i as f64).sin(), 1.)).collect()
+    }
+
+    fn residual(m: &ComplexMatrix<f64>, x: &[Complex<f64>], b: &[Complex<f64>]) -> f64 {
+        let mut r = b.to_vec();
+        for t in m.triplets() {
+            r[t.row] -= t.value * x[t.col];
+        }
+        let r_norm = r.iter().map(|ri| ri.norm_sqr()).sum::<f64>();
+        let b_norm = b.iter().map(|bi| bi.norm_sqr()).sum::<f64>();
+        (r_norm / b_norm).sqrt()
+    }
+
+    #[test]
+    fn test_bicgstab_converges() {
+        let n = 200;
+        let m = convection(n);
+        let b = rhs(n);
+        let mut options = IterOptions::new();
+        options.set_tolerance(1e-10);
+
+        let mut x = b.clone();
+        let plain = m
+            .solve_bicgstab(&IdentityPreconditioner, &mut x, &options)
+            .unwrap();
+        assert!(plain.residual < 1e-10);
+        assert!((residual(&m, &x, &b) - plain.residual).abs() < 1e-12);
+
+        let mut x = b.clone();
+        let jacobi = m
+            .solve_bicgstab(&Jacobi::new(&m).unwrap(), &mut x, &options)
+            .unwrap();
+        assert!(residual(&m, &x, &b) < 1e-10);
+        assert!(jacobi.iterations <= plain.iterations);
+    }
+
+    #[test]
+    fn test_bicgstab_limits() {
+        let m = convection(100);
+        let mut options = IterOptions::new();
+        options.set_max_iterations(2);
+        let mut b = rhs(100);
+        assert_eq!(
+            m.solve_bicgstab(&IdentityPreconditioner, &mut b, &options),
+            Err(SolveError::NoConvergence)
+        );
+        assert_eq!(
+            m.solve_bicgstab(&IdentityPreconditioner, &mut b[..50], &options),
+            Err(SolveError::DimensionMismatch)
+        );
+        assert_eq!(
+            m.solve_bicgstab(&IdentityPreconditioner, &mut rhs(101), &options),
+            Err(SolveError::DimensionMismatch)
+        );
+
+        let mut zero = vec![Complex::new(0., 0.); 100];
+        let report = m
+            .solve_bicgstab(&IdentityPreconditioner, &mut zero, &options)
+            .unwrap();
+        assert_eq!(report.iterations, 0);
+        assert_eq!(zero, vec![Complex::new(0., 0.); 100]);
+    }
+
+    #[test]
+    fn test_gmres_converges() {
+        let n = 200;
+        let m = convection(n);
+        let b = rhs(n);
+        let mut options = IterOptions::new();
+        options.set_tolerance(1e-10);
+
+        let mut iterations = Vec::new();
+        for restart in [5, 20, 200] {
+            options.set_restart(restart);
+            let mut x = b.clone();
+            let report = m
+                .solve_gmres(&IdentityPreconditioner, &mut x, &options)
+                .unwrap();
+            assert!(report.residual < 1e-10);
+            assert!((residual(&m, &x, &b) - report.residual).abs() < 1e-12);
+            iterations.push(report.iterations);
+        }
+        assert!(iterations[2] <= iterations[0]);
+
+        let mut x = b.clone();
+        let ilu = m
+            .solve_gmres(&Ilu0::new(&m).unwrap(), &mut x, &options)
+            .unwrap();
+        assert!(residual(&m, &x, &b) < 1e-10);
+        assert!(ilu.iterations < iterations[2]);
+    }
+
+    #[test]
+    fn test_gmres_limits() {
+        let m = convection(100);
+        let mut options = IterOptions::new();
+        options.set_restart(3);
+        options.set_max_iterations(4);
+        let mut b = rhs(100);
+        assert_eq!(
+            m.solve_gmres(&IdentityPreconditioner, &mut b, &options),
+            Err(SolveError::NoConvergence)
+        );
+        assert_eq!(
+            m.solve_gmres(&IdentityPreconditioner, &mut b[..50], &options),
+            Err(SolveError::DimensionMismatch)
+        );
+        assert_eq!(
+            m.solve_gmres(&IdentityPreconditioner, &mut rhs(101), &options),
+            Err(SolveError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_restart() {
+        IterOptions::<f64>::new().set_restart(0);
+    }
+
+    /// A lossy Helmholtz-like Hermitian positive definite matrix on a 2D grid.
+    fn helmholtz(side: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        let index = |i: usize, j: usize| i * side + j;
+        for i in 0..side {
+            for j in 0..side {
+                let k = index(i, j);
+                m.add_element(k, k, Complex::new(4.1 + 0.01 * (i + j) as f64, 0.));
+                if j + 1 < side {
+                    m.add_element(k, index(i, j + 1), Complex::new(-1., 0.1));
+                    m.add_element(index(i, j + 1), k, Complex::new(-1., -0.1));
+                }
+                if i + 1 < side {
+                    m.add_element(k, index(i + 1, j), Complex::new(-1., 0.));
+                    m.add_element(index(i + 1, j), k, Complex::new(-1., 0.));
+                }
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_cg_converges() {
+        let m = helmholtz(15);
+        let n = 225;
+        let b = rhs(n);
+        let mut options = IterOptions::new();
+        options.set_tolerance(1e-10);
+
+        let mut x = b.clone();
+        let report = m.solve_cg(&mut x, &options).unwrap();
+        assert!(report.residual < 1e-10);
+        assert!((residual(&m, &x, &b) - report.residual).abs() < 1e-12);
+        assert!(report.iterations < n);
+
+        let mut y = b.clone();
+        m.solve_pcg(&IdentityPreconditioner, &mut y, &options)
+            .unwrap();
+        for (xi, yi) in x.iter().zip(y.iter()) {
+            assert!((xi - yi).norm() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_cg_errors() {
+        let mut options = IterOptions::new();
+        options.set_max_iterations(3);
+        let m = helmholtz(10);
+        let mut b = rhs(100);
+        assert_eq!(m.solve_cg(&mut b, &options), Err(SolveError::NoConvergence));
+        assert_eq!(
+            m.solve_cg(&mut b[..10], &options),
+            Err(SolveError::DimensionMismatch)
+        );
+        assert_eq!(
+            m.solve_cg(&mut rhs(101), &options),
+            Err(SolveError::DimensionMismatch)
+        );
+
+        let mut indefinite = ComplexMatrix::<f64>::new();
+        indefinite.add_element(0, 0, Complex::new(1., 0.));
+        indefinite.add_element(1, 1, Complex::new(-1., 0.));
+        assert!(matches!(
+            indefinite.solve_pcg(
+                &IdentityPreconditioner,
+                &mut [Complex::new(0., 0.), Complex::new(1., 0.)],
+                &options
+            ),
+            Err(SolveError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_iterative_matches_solve() {
+        let n = 300;
+        let m = convection(n);
+        let b = rhs(n);
+        let (mut x, mut expected) = (b.clone(), b);
+        let mut options = IterOptions::new();
+        options.set_tolerance(1e-12);
+        options.set_drop_tolerance(1e-3);
+        m.solve_iterative(&mut x, &options).unwrap();
+        m.solve(&mut expected).unwrap();
+        for (xi, ei) in x.iter().zip(expected.iter()) {
+            assert!((xi - ei).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_convergence_history() {
+        let m = convection(150);
+        let b = rhs(150);
+        let mut options = IterOptions::new();
+        options.set_tolerance(1e-10);
+        options.set_restart(15);
+
+        let mut x = b.clone();
+        let gmres = m
+            .solve_gmres(&IdentityPreconditioner, &mut x, &options)
+            .unwrap();
+        assert_eq!(gmres.history.len(), gmres.iterations + 1);
+        assert_eq!(gmres.history[0], 1.);
+        // GMRES minimizes the residual, so its norm never increases.
+        assert!(gmres
+            .history
+            .windows(2)
+            .all(|w| w[1] <= w[0] * (1. + 1e-12)));
+        assert!(*gmres.history.last().unwrap() <= 1e-10);
+
+        let mut x = b.clone();
+        let bicgstab = m
+            .solve_bicgstab(&IdentityPreconditioner, &mut x, &options)
+            .unwrap();
+        assert_eq!(bicgstab.history.len(), bicgstab.iterations + 1);
+        assert!((bicgstab.history.last().unwrap() - bicgstab.residual).abs() < 1e-12);
+
+        let mut zero = vec![Complex::new(0., 0.); 150];
+        let report = m.solve_cg(&mut zero, &options).unwrap();
+        assert_eq!(report.history, vec![0.]);
+    }
+
+    #[test]
+    fn test_solve_auto() {
+        let m = convection(200);
+        let b = rhs(200);
+
+        let mut x = b.clone();
+        let report = m.solve_auto(&mut x, &IterOptions::new()).unwrap();
+        assert!(report.unwrap().residual < 1e-8);
+        assert!(residual(&m, &x, &b) < 1e-8);
+
+        // Without iterations the direct solver takes over.
+        let mut options = IterOptions::new();
+        options.set_max_iterations(0);
+        let mut x = b.clone();
+        assert_eq!(m.solve_auto(&mut x, &options), Ok(None));
+        assert!(residual(&m, &x, &b) < 1e-10);
+
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        let mut x = vec![Complex::new(1., 0.); 2];
+        assert_eq!(
+            singular.solve_auto(&mut x, &options),
+            Err(SolveError::Singular)
+        );
+        let mut x = vec![Complex::new(1., 0.); 199];
+        assert_eq!(
+            m.solve_auto(&mut x, &options),
+            Err(SolveError::DimensionMismatch)
+        );
+    }
+}
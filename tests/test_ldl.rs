@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// The Hermitian admittance matrix of a ring of n nodes with shunts to the ground.
+    fn ring(n: usize, shunt: f64) -> SymmetricMatrix<f64> {
+        let mut m = SymmetricMatrix::new(Symmetry::Hermitian, Triangle::Lower);
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let y = Complex::new(1. + 0.1 * i as f64, 0.5);
+            m.add_element(i, i, Complex::new(2. * y.norm() + shunt, 0.));
+            m.add_element(
+                j,
+                i,
+                -Complex::new(y.norm(), 0.) * Complex::from_polar(1., 0.2 * i as f64),
+            );
+        }
+        m
+    }
+
+    fn rhs(n: usize) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|i| Complex::new(1. + i as f64, (i as f64).sin()))
+            .collect()
+    }
+
+    fn assert_solves(ldl: &HermitianLdl<f64>, m: &SymmetricMatrix<f64>) {
+        let n = ldl.size();
+        let mut x = rhs(n);
+        ldl.solve(&mut x).unwrap();
+        let full = m.to_complex_matrix();
+        let ax = full.pow_apply(1, &x);
+        for (axi, bi) in ax.iter().zip(rhs(n).iter()) {
+            assert!((axi - bi).norm() < 1e-10, "{} != {}", axi, bi);
+        }
+    }
+
+    #[test]
+    fn test_factorize_with_ordering() {
+        let m = ring(12, 0.3);
+        assert_solves(&m.factorize_ldl().unwrap(), &m);
+
+        let ordering = Permutation::from_vec((0..12).map(|i| (5 * i) % 12).collect()).unwrap();
+        let ldl = m.factorize_ldl_with_ordering(ordering.clone()).unwrap();
+        assert_eq!(ldl.ordering(), &ordering);
+        assert_solves(&ldl, &m);
+    }
+
+    #[test]
+    fn test_update_and_downdate() {
+        let n = 10;
+        let mut m = ring(n, 0.3);
+        let mut ldl = m.factorize_ldl().unwrap();
+
+        // Add a shunt at node 4, then a branch between nodes 4 and 5.
+        let mut v = vec![Complex::new(0., 0.); n];
+        v[4] = Complex::new(0.8, 0.);
+        ldl.update(&v).unwrap();
+        m.add_element(4, 4, Complex::new(0.64, 0.));
+        assert_solves(&ldl, &m);
+
+        let mut v = vec![Complex::new(0., 0.); n];
+        v[4] = Complex::new(0.5, 0.5);
+        v[5] = Complex::new(-0.5, -0.5);
+        ldl.update(&v).unwrap();
+        m.add_element(4, 4, Complex::new(0.5, 0.));
+        m.add_element(5, 5, Complex::new(0.5, 0.));
+        m.add_element(5, 4, Complex::new(-0.5, 0.));
+        assert_solves(&ldl, &m);
+
+        // Removing the branch again.
+        ldl.downdate(&v).unwrap();
+        m.add_element(4, 4, Complex::new(-0.5, 0.));
+        m.add_element(5, 5, Complex::new(-0.5, 0.));
+        m.add_element(5, 4, Complex::new(0.5, 0.));
+        assert_solves(&ldl, &m);
+    }
+
+    #[test]
+    fn test_update_outside_the_pattern_is_rejected() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Lower);
+        for i in 0..3 {
+            m.add_element(i, i, Complex::new(4., 0.));
+        }
+        let mut ldl = m.factorize_ldl().unwrap();
+        let before = ldl.clone();
+        let v = vec![
+            Complex::new(1., 0.),
+            Complex::new(0., 0.),
+            Complex::new(1., 0.),
+        ];
+        assert!(ldl.update(&v).is_err());
+        assert_eq!(ldl, before);
+
+        // Downdating a diagonal entry to zero makes the matrix singular.
+        let v = vec![
+            Complex::new(2., 0.),
+            Complex::new(0., 0.),
+            Complex::new(0., 0.),
+        ];
+        assert!(ldl.downdate(&v).is_err());
+        assert_eq!(ldl, before);
+    }
+
+    #[test]
+    fn test_complex_symmetric_is_rejected() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::ComplexSymmetric, Triangle::Lower);
+        m.add_element(0, 0, Complex::new(1., 0.));
+        assert!(m.factorize_ldl().is_err());
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn observations() -> (ComplexMatrix<f64>, Vec<Complex<f64>>) {
+        let mut a = ComplexMatrix::<f64>::new();
+        let mut b = Vec::new();
+        for i in 0..12 {
+            let t = i as f64 * 0.3;
+            a.add_element(i, 0, Complex::new(1., 0.));
+            a.add_element(i, 1, Complex::new(t.cos(), t.sin()));
+            a.add_element(i, 2, Complex::new(0., t));
+            b.push(Complex::new(
+                2. + t.cos() - 0.5 * t,
+                t.sin() + 0.01 * (i % 3) as f64,
+            ));
+        }
+        (a, b)
+    }
+
+    #[test]
+    fn test_least_squares_satisfies_normal_equations() {
+        let (a, b) = observations();
+        let ls = UpdatingLeastSquares::from_matrix(&a, 3, &b);
+        let x = ls.solution().unwrap();
+
+        let residual = b
+            .iter()
+            .enumerate()
+            .map(|(i, bi)| {
+                bi - (0..3)
+                    .filter_map(|j| a.get(i, j).map(|v| v * x[j]))
+                    .sum::<Complex<f64>>()
+            })
+            .collect::<Vec<_>>();
+        for j in 0..3 {
+            let aht_r = (0..b.len())
+                .filter_map(|i| a.get(i, j).map(|v| v.conj() * residual[i]))
+                .sum::<Complex<f64>>();
+            assert!(aht_r.norm() < 1e-10);
+        }
+        let norm = residual.iter().map(|r| r.norm_sqr()).sum::<f64>().sqrt();
+        assert!((ls.residual_norm() - norm).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_appending_rows_matches_batch_factorization() {
+        let (a, b) = observations();
+        let batch = UpdatingLeastSquares::from_matrix(&a, 3, &b)
+            .solution()
+            .unwrap();
+
+        let mut ls = UpdatingLeastSquares::<f64>::new(3);
+        for (i, rhs) in b.iter().enumerate() {
+            let row = (0..3)
+                .filter_map(|j| a.get(i, j).map(|v| (j, *v)))
+                .collect::<Vec<_>>();
+            ls.append_row(&row, *rhs);
+            if i < 2 {
+                assert!(ls.solution().is_err());
+            }
+        }
+        assert_eq!(ls.observations(), 12);
+        for (x, y) in ls.solution().unwrap().iter().zip(batch.iter()) {
+            assert!((x - y).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_sparse_qr_matches_updating_least_squares() {
+        let (a, b) = observations();
+        let expected = UpdatingLeastSquares::from_matrix(&a, 3, &b)
+            .solution()
+            .unwrap();
+        let x = a.solve_least_squares(&b).unwrap();
+        for (xi, ei) in x.iter().zip(expected.iter()) {
+            assert!((xi - ei).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sparse_qr_errors() {
+        let (a, b) = observations();
+        assert_eq!(
+            a.solve_least_squares(&b[..5]),
+            Err(SolveError::DimensionMismatch)
+        );
+
+        let mut wide = ComplexMatrix::<f64>::new();
+        wide.add_element(0, 0, Complex::new(1., 0.));
+        wide.add_element(0, 1, Complex::new(1., 0.));
+        assert!(wide.solve_least_squares(&[Complex::new(1., 0.)]).is_err());
+
+        let mut deficient = ComplexMatrix::<f64>::new();
+        for i in 0..3 {
+            deficient.add_element(i, 0, Complex::new(1., 0.));
+            deficient.add_element(i, 1, Complex::new(2., 0.));
+        }
+        let b = vec![Complex::new(1., 0.); 3];
+        assert_eq!(
+            deficient.solve_least_squares(&b),
+            Err(SolveError::Singular)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// A Hermitian tridiagonal matrix with eigenvalues 2 - 2cos(kπ / (n + 1)).
+    fn laplacian(n: usize) -> ComplexMatrix<f64> {
+        let mut a = ComplexMatrix::new();
+        for i in 0..n {
+            a.add_element(i, i, Complex::new(2., 0.));
+            if i + 1 < n {
+                let phase = Complex::from_polar(1., 0.3 * i as f64);
+                a.add_element(i, i + 1, -phase);
+                a.add_element(i + 1, i, -phase.conj());
+            }
+        }
+        a
+    }
+
+    fn exact(n: usize, k: usize) -> f64 {
+        2. - 2. * (k as f64 * std::f64::consts::PI / (n as f64 + 1.)).cos()
+    }
+
+    #[test]
+    fn test_smallest_and_largest() {
+        let n = 80;
+        let a = laplacian(n);
+        let jacobi = Jacobi::new(&a).unwrap();
+        let mut solver = Lobpcg::new();
+        solver.set_tolerance(1e-10);
+
+        let smallest = solver.smallest(&a, None, &jacobi, 3).unwrap();
+        for (k, value) in smallest.values().iter().enumerate() {
+            assert!((value - exact(n, k + 1)).abs() < 1e-8, "{} {}", k, value);
+        }
+        let largest = solver
+            .largest(&a, None, &IdentityPreconditioner, 2)
+            .unwrap();
+        assert!((largest.values()[0] - exact(n, n)).abs() < 1e-8);
+        assert!((largest.values()[1] - exact(n, n - 1)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_generalized_pencil() {
+        // B = 2I halves the eigenvalues of A.
+        let n = 40;
+        let a = laplacian(n);
+        let mut b = ComplexMatrix::new();
+        for i in 0..n {
+            b.add_element(i, i, Complex::new(2., 0.));
+        }
+        let pairs = Lobpcg::new()
+            .smallest(&a, Some(&b), &IdentityPreconditioner, 2)
+            .unwrap();
+        assert!((pairs.values()[0] - exact(n, 1) / 2.).abs() < 1e-6);
+
+        // The eigenvectors are B-orthonormal.
+        let x = &pairs.vectors()[0];
+        let bx = b.pow_apply(1, x);
+        let xbx: Complex<f64> = x.iter().zip(bx.iter()).map(|(xi, yi)| xi.conj() * yi).sum();
+        assert!((xbx - Complex::new(1., 0.)).norm() < 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_count() {
+        let a = laplacian(5);
+        assert!(Lobpcg::new()
+            .smallest(&a, None, &IdentityPreconditioner, 0)
+            .is_err());
+        assert!(Lobpcg::new()
+            .smallest(&a, None, &IdentityPreconditioner, 6)
+            .is_err());
+    }
+}
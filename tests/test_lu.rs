@@ -0,0 +1,399 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn tridiagonal(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(-1., 0.));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_lu_matches_solve() {
+        let m = tridiagonal(50);
+        let lu = m.factorize().unwrap();
+        assert_eq!(lu.size(), 50);
+        for k in 0..3 {
+            let b = (0..50)
+                .map(|i| Complex::new(i as f64, k as f64))
+                .collect::<Vec<_>>();
+            let (mut x, mut expected) = (b.clone(), b);
+            lu.solve(&mut x).unwrap();
+            m.solve(&mut expected).unwrap();
+            for (xi, ei) in x.iter().zip(expected.iter()) {
+                assert!((xi - ei).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lu_errors() {
+        let lu = tridiagonal(5).factorize().unwrap();
+        assert_eq!(
+            lu.solve(&mut [Complex::new(1., 0.); 4]),
+            Err(SolveError::DimensionMismatch)
+        );
+
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        assert!(singular.factorize().is_err());
+    }
+
+    #[test]
+    fn test_lu_refactorize() {
+        let mut lu = tridiagonal(20).factorize().unwrap();
+        let mut scaled = tridiagonal(20);
+        scaled.scale_in_place(Complex::new(0., 2.));
+        lu.refactorize(&scaled).unwrap();
+
+        let b = (0..20).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+        let (mut x, mut expected) = (b.clone(), b);
+        lu.solve(&mut x).unwrap();
+        scaled.solve(&mut expected).unwrap();
+        for (xi, ei) in x.iter().zip(expected.iter()) {
+            assert!((xi - ei).norm() < 1e-12);
+        }
+
+        let mut singular = tridiagonal(20);
+        singular.scale_in_place(Complex::new(0., 0.));
+        assert!(lu.refactorize(&singular).is_err());
+        assert!(lu.solve(&mut vec![Complex::new(1., 0.); 20]).is_err());
+    }
+
+    #[test]
+    fn test_solve_multi() {
+        let m = tridiagonal(30);
+        let mut rhs = (0..4)
+            .map(|k| (0..30).map(|i| Complex::new(i as f64, k as f64)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let expected = rhs
+            .iter()
+            .map(|b| {
+                let mut x = b.clone();
+                m.solve(&mut x).unwrap();
+                x
+            })
+            .collect::<Vec<_>>();
+        m.solve_multi(&mut rhs).unwrap();
+        for (x, e) in rhs.iter().zip(expected.iter()) {
+            assert!(x.iter().zip(e.iter()).all(|(xi, ei)| (xi - ei).norm() < 1e-12));
+        }
+
+        let mut bad = vec![vec![Complex::new(1., 0.); 30], vec![Complex::new(1., 0.); 29]];
+        assert_eq!(m.solve_multi(&mut bad), Err(SolveError::DimensionMismatch));
+        assert_eq!(bad[0], vec![Complex::new(1., 0.); 30]);
+    }
+
+    fn unsymmetric(n: usize) -> ComplexMatrix<f64> {
+        let mut m = tridiagonal(n);
+        for i in 0..n - 3 {
+            m.add_element(i, i + 3, Complex::new(0.5, -1.));
+        }
+        m
+    }
+
+    #[test]
+    fn test_solve_transposed() {
+        let m = unsymmetric(25);
+        let mut transposed = m.clone();
+        transposed.transpose_in_place();
+
+        let b = (0..25).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+        let (mut x, mut expected) = (b.clone(), b.clone());
+        m.factorize().unwrap().solve_transposed(&mut x).unwrap();
+        transposed.solve(&mut expected).unwrap();
+        assert!(x.iter().zip(expected.iter()).all(|(xi, ei)| (xi - ei).norm() < 1e-12));
+
+        let mut y = b;
+        m.solve_transposed(&mut y).unwrap();
+        assert!(y.iter().zip(expected.iter()).all(|(yi, ei)| (yi - ei).norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_solve_adjoint() {
+        let m = unsymmetric(25);
+        let mut adjoint = m.clone();
+        adjoint.transpose_in_place();
+        adjoint.conj_in_place();
+
+        let b = (0..25).map(|i| Complex::new(i as f64, -1.)).collect::<Vec<_>>();
+        let (mut x, mut expected) = (b.clone(), b.clone());
+        m.factorize().unwrap().solve_adjoint(&mut x).unwrap();
+        adjoint.solve(&mut expected).unwrap();
+        assert!(x.iter().zip(expected.iter()).all(|(xi, ei)| (xi - ei).norm() < 1e-12));
+
+        let mut y = b;
+        m.solve_adjoint(&mut y).unwrap();
+        assert!(y.iter().zip(expected.iter()).all(|(yi, ei)| (yi - ei).norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_determinant() {
+        // The determinant of the tridiagonal matrix follows the three-term recurrence
+        // d_k = a d_{k-1} - d_{k-2}.
+        let a = Complex::new(4., 1.);
+        let (mut previous, mut det) = (Complex::new(1., 0.), a);
+        for _ in 1..20 {
+            let next = a * det - previous;
+            previous = det;
+            det = next;
+        }
+        let m = tridiagonal(20);
+        assert!((m.determinant().unwrap() - det).norm() < 1e-10 * det.norm());
+        assert!((m.factorize().unwrap().determinant().unwrap() - det).norm() < 1e-10 * det.norm());
+
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        assert_eq!(singular.determinant().unwrap(), Complex::new(0., 0.));
+    }
+
+    #[test]
+    fn test_log_determinant() {
+        let m = tridiagonal(20);
+        let det = m.determinant().unwrap();
+        let lu = m.factorize().unwrap();
+        assert!((lu.log_abs_determinant().unwrap() - det.norm().ln()).abs() < 1e-10);
+        assert!((lu.determinant_phase().unwrap() - det / det.norm()).norm() < 1e-10);
+
+        // A permutation with one transposition flips the sign.
+        let mut swap = ComplexMatrix::<f64>::new();
+        swap.add_element(0, 1, Complex::new(1e-200, 0.));
+        swap.add_element(1, 0, Complex::new(1e-200, 0.));
+        swap.add_element(2, 2, Complex::new(0., 1e-200));
+        assert!((swap.log_abs_determinant().unwrap() + 600. * 10f64.ln()).abs() < 1e-9);
+        assert!((swap.determinant_phase().unwrap() - Complex::new(0., -1.)).norm() < 1e-12);
+
+        let mut singular = ComplexMatrix::<f64>::new();
+        singular.add_element(0, 0, Complex::new(1., 0.));
+        singular.add_element(1, 0, Complex::new(1., 0.));
+        assert_eq!(singular.log_abs_determinant().unwrap(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_pivot_threshold() {
+        let m = unsymmetric(30);
+        let mut options = LuOptions::new();
+        assert_eq!(options.pivot_threshold(), 1.);
+        options.set_pivot_threshold(0.01);
+        let lu = m.factorize_with_options(&options).unwrap();
+
+        let b = (0..30).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+        let (mut x, mut expected) = (b.clone(), b);
+        lu.solve(&mut x).unwrap();
+        m.solve(&mut expected).unwrap();
+        assert!(x.iter().zip(expected.iter()).all(|(xi, ei)| (xi - ei).norm() < 1e-10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pivot_threshold_out_of_range() {
+        LuOptions::new().set_pivot_threshold(2.);
+    }
+
+    #[test]
+    fn test_orderings() {
+        let m = unsymmetric(40);
+        let b = (0..40).map(|i| Complex::new(1., -(i as f64))).collect::<Vec<_>>();
+        let mut expected = b.clone();
+        m.solve(&mut expected).unwrap();
+        for ordering in [ColumnOrdering::Colamd, ColumnOrdering::Amd, ColumnOrdering::Natural] {
+            let mut options = LuOptions::new();
+            options.set_ordering(ordering);
+            assert_eq!(options.ordering(), ordering);
+            let mut lu = m.factorize_with_options(&options).unwrap();
+            let mut x = b.clone();
+            lu.solve(&mut x).unwrap();
+            assert!(x.iter().zip(expected.iter()).all(|(xi, ei)| (xi - ei).norm() < 1e-10));
+
+            // Refactorizing keeps the ordering of the options.
+            lu.refactorize(&m).unwrap();
+            let mut y = b.clone();
+            lu.solve(&mut y).unwrap();
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn test_factors() {
+        let n = 25;
+        let m = unsymmetric(n);
+        let factors = m.factorize().unwrap().factors().unwrap();
+        let (p, q) = (factors.row_permutation(), factors.column_permutation());
+        let zero = Complex::new(0., 0.);
+        let entry = |m: &ComplexMatrix<f64>, i: usize, j: usize| *m.get(i, j).unwrap_or(&zero);
+        for i in 0..n {
+            assert_eq!(entry(factors.lower(), i, i), Complex::new(1., 0.));
+            for j in 0..n {
+                if j > i {
+                    assert_eq!(entry(factors.lower(), i, j), zero);
+                }
+                if j < i {
+                    assert_eq!(entry(factors.upper(), i, j), zero);
+                }
+                let lu = (0..n).fold(zero, |acc, k| {
+                    acc + entry(factors.lower(), p.get(i), k) * entry(factors.upper(), k, q.get(j))
+                });
+                assert!((lu - entry(&m, i, j)).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_sparse_lu() {
+        let n = 25;
+        let b = (0..n).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+        let close = |x: &[Complex<f64>], y: &[Complex<f64>]| {
+            x.iter().zip(y.iter()).all(|(xi, yi)| (xi - yi).norm() < 1e-10 * yi.norm().max(1.))
+        };
+
+        let m = unsymmetric(n);
+        let lu = m.factorize().unwrap();
+        let copy = lu.to_sparse_lu().unwrap();
+        assert_eq!(copy.size(), n);
+        assert!(copy.pivot_growth() > 0.);
+        let (mut x, mut expected) = (b.clone(), b.clone());
+        copy.solve(&mut x).unwrap();
+        lu.solve(&mut expected).unwrap();
+        assert!(close(&x, &expected));
+        let (mut x, mut expected) = (b.clone(), b.clone());
+        copy.solve_adjoint(&mut x).unwrap();
+        lu.solve_adjoint(&mut expected).unwrap();
+        assert!(close(&x, &expected));
+
+        let path = std::env::temp_dir().join("sparse_complex_test_complex_lu.bin");
+        lu.save(&path).unwrap();
+        assert_eq!(SparseLu::<f64>::load(&path).unwrap(), copy);
+        std::fs::remove_file(&path).unwrap();
+
+        // The copy of an equilibrated factorization solves the unscaled matrix.
+        let mut scaled = ComplexMatrix::new();
+        for t in m.triplets() {
+            scaled.add_element(t.row, t.col, t.value * 2f64.powi(t.row as i32 % 5 * 8 - 16));
+        }
+        let mut options = LuOptions::new();
+        options.set_equilibrate(true);
+        let lu = scaled.factorize_with_options(&options).unwrap();
+        let (mut x, mut expected) = (b.clone(), b);
+        lu.to_sparse_lu().unwrap().solve(&mut x).unwrap();
+        lu.solve(&mut expected).unwrap();
+        assert!(close(&x, &expected));
+    }
+
+    #[test]
+    fn test_factorization_stats() {
+        let n = 40;
+        let m = unsymmetric(n);
+        let mut lu = m.factorize().unwrap();
+        let stats = *lu.stats();
+        let factors = lu.factors().unwrap();
+        assert_eq!(stats.nnz_l, factors.lower().triplets().count());
+        assert_eq!(stats.nnz_u, factors.upper().triplets().count());
+        assert!(stats.fill_ratio >= (2 * n) as f64 / m.triplets().count() as f64);
+        assert!(stats.flops > 0.);
+        assert!(stats.memory_bytes > 0);
+
+        lu.refactorize(&m).unwrap();
+        assert_eq!(lu.stats().nnz_l, stats.nnz_l);
+
+        let mut b = vec![Complex::new(1., 0.); n];
+        assert_eq!(m.solve_with_stats(&mut b).unwrap().nnz_u, stats.nnz_u);
+    }
+
+    #[test]
+    fn test_equilibrate() {
+        // Rows and columns spanning twelve orders of magnitude.
+        let n = 30;
+        let scale = |i: usize| 10f64.powi(i as i32 % 7 * 2 - 6);
+        let mut m = ComplexMatrix::new();
+        for t in tridiagonal(n).triplets() {
+            m.add_element(t.row, t.col, t.value * scale(t.row) / scale(t.col + 3));
+        }
+        let b = (0..n).map(|i| Complex::new(scale(i), 1.)).collect::<Vec<_>>();
+        let residual = |x: &[Complex<f64>], b: &[Complex<f64>], transpose: bool| {
+            let mut r = b.to_vec();
+            for t in m.triplets() {
+                let (i, j) = if transpose { (t.col, t.row) } else { (t.row, t.col) };
+                r[i] -= t.value * x[j];
+            }
+            r.iter().zip(b.iter()).map(|(ri, bi)| ri.norm() / bi.norm()).fold(0., f64::max)
+        };
+
+        let mut options = LuOptions::new();
+        options.set_equilibrate(true);
+        assert!(options.equilibrate());
+        let mut lu = m.factorize_with_options(&options).unwrap();
+        assert!(lu.is_equilibrated());
+        assert!(!m.factorize().unwrap().is_equilibrated());
+
+        let mut x = b.clone();
+        lu.solve(&mut x).unwrap();
+        assert!(residual(&x, &b, false) < 1e-12);
+        let mut x = b.clone();
+        lu.solve_transposed(&mut x).unwrap();
+        assert!(residual(&x, &b, true) < 1e-12);
+
+        let det = m.factorize().unwrap().log_abs_determinant().unwrap();
+        assert!((lu.log_abs_determinant().unwrap() - det).abs() < 1e-9 * det.abs().max(1.));
+
+        // Refactorizing scales the new values.
+        let mut m2 = ComplexMatrix::new();
+        for t in m.triplets() {
+            m2.add_element(t.row, t.col, t.value * 2.);
+        }
+        lu.refactorize(&m2).unwrap();
+        let mut x = b.clone();
+        lu.solve(&mut x).unwrap();
+        x.iter_mut().for_each(|xi| *xi *= 2.);
+        assert!(residual(&x, &b, false) < 1e-12);
+
+        // A failed solve leaves the right-hand side as it was.
+        let mut singular = m.clone();
+        singular.scale_in_place(Complex::new(0., 0.));
+        assert!(lu.refactorize(&singular).is_err());
+        let mut x = b.clone();
+        assert!(lu.solve(&mut x).is_err());
+        assert!(lu.solve_transposed(&mut x).is_err());
+        assert!(lu.solve_adjoint(&mut x).is_err());
+        assert_eq!(x, b);
+    }
+
+    #[test]
+    fn test_concurrent_solves() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let m = tridiagonal(40);
+        let lu = std::sync::Arc::new(m.factorize().unwrap());
+        assert_send_sync(&lu);
+        let handles = (0..8)
+            .map(|k| {
+                let lu = std::sync::Arc::clone(&lu);
+                std::thread::spawn(move || {
+                    let b = (0..40).map(|i| Complex::new(i as f64, k as f64)).collect::<Vec<_>>();
+                    let (mut x, mut y) = (b.clone(), b);
+                    lu.solve(&mut x).unwrap();
+                    lu.solve_adjoint(&mut y).unwrap();
+                    (x, y)
+                })
+            })
+            .collect::<Vec<_>>();
+        for (k, handle) in handles.into_iter().enumerate() {
+            let (x, y) = handle.join().unwrap();
+            let b = (0..40).map(|i| Complex::new(i as f64, k as f64)).collect::<Vec<_>>();
+            let (mut expected_x, mut expected_y) = (b.clone(), b);
+            lu.solve(&mut expected_x).unwrap();
+            lu.solve_adjoint(&mut expected_y).unwrap();
+            assert_eq!(x, expected_x);
+            assert_eq!(y, expected_y);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn assert_close(a: &PortMatrix<f64>, b: &PortMatrix<f64>) {
+        for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+            assert!((x - y).norm() < 1e-10, "{} != {}", x, y);
+        }
+    }
+
+    fn two_port() -> PortMatrix<f64> {
+        // A T network: 10 Ω and j20 Ω series arms with a 100 - j5 Ω shunt arm.
+        let (a, b, c) = (
+            Complex::new(10., 0.),
+            Complex::new(0., 20.),
+            Complex::new(100., -5.),
+        );
+        PortMatrix::new(2, vec![a + c, c, c, b + c])
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let z = two_port();
+        let reference = [50., 75.];
+
+        let y = z.z_to_y().unwrap();
+        assert_close(&y.y_to_z().unwrap(), &z);
+
+        let s = z.z_to_s(&reference).unwrap();
+        assert_close(&s.s_to_z(&reference).unwrap(), &z);
+        assert_close(&y.y_to_s(&reference).unwrap(), &s);
+        assert_close(&s.s_to_y(&reference).unwrap(), &y);
+    }
+
+    #[test]
+    fn test_matched_line() {
+        // A through connection between 50 Ω ports transmits everything.
+        let y = Complex::new(1e6, 0.);
+        let zero = Complex::new(0., 0.);
+        let through = PortMatrix::new(2, vec![y, zero - y, zero - y, y]);
+
+        let s = through.y_to_s(&[50., 50.]).unwrap();
+        assert!(s.get(0, 0).norm() < 1e-4);
+        assert!((s.get(0, 1) - Complex::new(1., 0.)).norm() < 1e-4);
+        assert!(through.y_to_z().is_err());
+    }
+
+    #[test]
+    fn test_invalid_reference() {
+        let z = two_port();
+        assert!(z.z_to_s(&[50.]).is_err());
+        assert!(z.z_to_s(&[50., 0.]).is_err());
+    }
+}
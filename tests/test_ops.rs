@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn sample() -> ComplexMatrix<f64> {
+        ComplexMatrix::from_entries(vec![
+            (0, 0, Complex::new(2., 1.)),
+            (0, 2, Complex::new(-1., 3.)),
+            (1, 1, Complex::new(0., -4.)),
+            (2, 0, Complex::new(5., 0.)),
+        ])
+    }
+
+    #[test]
+    fn test_adjoint_in_place() {
+        let original = sample();
+        let mut m = original.clone();
+        m.transpose_in_place();
+        m.conj_in_place();
+        for (i, j) in [(0, 0), (0, 2), (1, 1), (2, 0)] {
+            assert_eq!(m.get(j, i).copied(), original.get(i, j).map(|v| v.conj()));
+        }
+        m.conj_in_place();
+        m.transpose_in_place();
+        assert_eq!(m, original);
+    }
+
+    #[test]
+    fn test_scale_in_place() {
+        let mut m = sample();
+        m.scale_in_place(Complex::new(2., 0.));
+        assert_eq!(m.get(0, 2), Some(&Complex::new(-2., 6.)));
+        assert_eq!(m.get(1, 1), Some(&Complex::new(0., -8.)));
+    }
+
+    #[test]
+    fn test_axpy_appends_scaled_elements() {
+        let mut b = sample();
+        let a = ComplexMatrix::from_entries(vec![(1, 2, Complex::new(1., 1.))]);
+        b.axpy(Complex::new(0., 1.), &a, Complex::new(2., 0.));
+        assert_eq!(b.get(0, 0), Some(&Complex::new(4., 2.)));
+        assert_eq!(b.get(1, 2), Some(&Complex::new(-1., 1.)));
+    }
+
+    #[test]
+    fn test_axpy_in_pattern_keeps_pattern() {
+        let mut b = sample();
+        let a = ComplexMatrix::from_entries(vec![
+            (2, 0, Complex::new(1., 0.)),
+            (0, 0, Complex::new(0., 1.)),
+            (2, 0, Complex::new(1., 0.)),
+        ]);
+        b.axpy_in_pattern(Complex::new(2., 0.), &a, Complex::new(-1., 0.)).unwrap();
+        assert_eq!(b.get(0, 0), Some(&Complex::new(-2., 1.)));
+        assert_eq!(b.get(2, 0), Some(&Complex::new(-1., 0.)));
+        assert_eq!(b.get(1, 1), Some(&Complex::new(0., 4.)));
+
+        let outside = ComplexMatrix::from_entries(vec![(1, 0, Complex::new(1., 0.))]);
+        let before = b.clone();
+        assert!(b.axpy_in_pattern(Complex::new(1., 0.), &outside, Complex::new(3., 0.)).is_err());
+        assert_eq!(b, before);
+    }
+
+    #[test]
+    fn test_axpy_with_slots() {
+        let mut b = sample();
+        let a = ComplexMatrix::from_entries(vec![
+            (2, 0, Complex::new(1., 0.)),
+            (0, 0, Complex::new(0., 1.)),
+        ]);
+        let slots = b.pattern_slots(&a).unwrap();
+        let mut expected = b.clone();
+        for _ in 0..3 {
+            b.axpy_with_slots(Complex::new(2., 0.), &a, Complex::new(0.5, 0.), &slots)
+                .unwrap();
+            expected
+                .axpy_in_pattern(Complex::new(2., 0.), &a, Complex::new(0.5, 0.))
+                .unwrap();
+        }
+        assert_eq!(b, expected);
+
+        let outside = ComplexMatrix::from_entries(vec![(1, 0, Complex::new(1., 0.))]);
+        assert!(b.pattern_slots(&outside).is_err());
+        let before = b.clone();
+        assert_eq!(
+            b.axpy_with_slots(Complex::new(1., 0.), &outside, Complex::new(1., 0.), &slots),
+            Err(SolveError::DimensionMismatch)
+        );
+        assert_eq!(b, before);
+    }
+
+    #[test]
+    fn test_hadamard_intersection() {
+        let a = sample();
+        let b = ComplexMatrix::from_entries(vec![
+            (0, 2, Complex::new(1., 0.)),
+            (0, 2, Complex::new(0., 1.)),
+            (1, 0, Complex::new(7., 0.)),
+            (2, 0, Complex::new(0., 2.)),
+            (3, 3, Complex::new(1., 0.)),
+        ]);
+        let product = a.hadamard(&b);
+        assert_eq!(product.get(0, 2), Some(&(Complex::new(-1., 3.) * Complex::new(1., 1.))));
+        assert_eq!(product.get(2, 0), Some(&Complex::new(0., 10.)));
+        assert_eq!(product.get(0, 0), None);
+        assert_eq!(product.get(1, 0), None);
+        assert_eq!(product.get(3, 3), None);
+        assert_eq!(product, b.hadamard(&a));
+    }
+
+    #[test]
+    fn test_recip_policies() {
+        let mut m = sample();
+        m.add_element(1, 1, Complex::new(0., 4.));
+
+        let skipped = m.recip(ZeroPolicy::Skip).unwrap();
+        assert_eq!(skipped.get(0, 0), Some(&Complex::new(2., 1.).inv()));
+        assert_eq!(skipped.get(1, 1), None);
+
+        let substituted = m.recip(ZeroPolicy::Substitute(Complex::new(1., 0.))).unwrap();
+        assert_eq!(substituted.get(1, 1), Some(&Complex::new(1., 0.)));
+
+        assert!(m.recip(ZeroPolicy::Error).is_err());
+        assert!(sample().recip(ZeroPolicy::Error).is_ok());
+    }
+
+    #[test]
+    fn test_component_div_over_numerator_pattern() {
+        let a = sample();
+        let d = ComplexMatrix::from_entries(vec![
+            (0, 0, Complex::new(2., 1.)),
+            (1, 1, Complex::new(0., 2.)),
+            (1, 2, Complex::new(5., 0.)),
+        ]);
+
+        let q = a.component_div(&d, ZeroPolicy::Skip).unwrap();
+        assert_eq!(q.get(0, 0), Some(&Complex::new(1., 0.)));
+        assert_eq!(q.get(1, 1), Some(&Complex::new(-2., 0.)));
+        assert_eq!(q.get(1, 2), None);
+        assert_eq!(q.get(2, 0), None);
+
+        assert!(a.component_div(&d, ZeroPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn test_real_and_imag_parts() {
+        let mut m = sample();
+        m.add_element(0, 0, Complex::new(1., 1.));
+
+        let real = m.real_part();
+        let imag = m.imag_part();
+        let sum = |part: &RealMatrix<f64>, row, col| {
+            part.iter()
+                .filter(|&(r, c, _)| r == row && c == col)
+                .map(|(_, _, v)| *v)
+                .sum::<f64>()
+        };
+        assert_eq!(sum(&real, 0, 0), 3.);
+        assert_eq!(sum(&imag, 0, 0), 2.);
+        assert_eq!(imag.get(2, 0), Some(&0.));
+
+        let magnitude = m.magnitude_matrix();
+        assert_eq!(magnitude.get(0, 0), Some(&Complex::new(3., 2.).norm()));
+        assert_eq!(magnitude.get(1, 1), Some(&4.));
+        assert_eq!(real.to_complex_matrix().get(2, 0), Some(&Complex::new(5., 0.)));
+    }
+
+    #[test]
+    fn test_pow_apply_matches_repeated_products() {
+        let m = sample();
+        let x = vec![Complex::new(1., 0.), Complex::new(0., 1.), Complex::new(-1., 2.)];
+        let ax = m.pow_apply(1, &x);
+        let expected = vec![
+            Complex::new(2., 1.) * x[0] + Complex::new(-1., 3.) * x[2],
+            Complex::new(0., -4.) * x[1],
+            Complex::new(5., 0.) * x[0],
+        ];
+        assert_eq!(ax, expected);
+        assert_eq!(m.pow_apply(3, &x), m.pow_apply(1, &m.pow_apply(2, &x)));
+    }
+
+    #[test]
+    fn test_compressed_row_and_col_views() {
+        let mut m = sample();
+        m.add_element(2, 0, Complex::new(1., 1.));
+        let c = m.to_compressed();
+        assert_eq!(c.size(), 3);
+        assert_eq!(c.nnz(), 4);
+
+        let row = c.row(0).map(|(j, v)| (j, *v)).collect::<Vec<_>>();
+        assert_eq!(row, vec![(0, Complex::new(2., 1.)), (2, Complex::new(-1., 3.))]);
+        let col = c.col(0).map(|(i, v)| (i, *v)).collect::<Vec<_>>();
+        assert_eq!(col, vec![(0, Complex::new(2., 1.)), (2, Complex::new(6., 1.))]);
+        assert_eq!(c.col(1).count(), 1);
+        assert_eq!(c.get(1, 2), None);
+        assert_eq!(c.get(5, 0), None);
+    }
+
+    #[test]
+    fn test_density_and_memory_usage() {
+        let m = sample();
+        assert_eq!(m.density(), 4. / 9.);
+        assert_eq!(ComplexMatrix::<f64>::new().density(), 0.);
+        let index = if cfg!(feature = "index32") { 4 } else { std::mem::size_of::<usize>() };
+        assert!(m.memory_usage_bytes() >= 4 * (16 + 2 * index));
+    }
+
+    #[test]
+    fn test_diff_reports_values_and_structure() {
+        let a = sample();
+        assert!(a.diff(&a.clone(), 0.).is_empty());
+
+        let mut b = ComplexMatrix::from_entries(vec![
+            (0, 0, Complex::new(1., 1.)),
+            (0, 0, Complex::new(1., 0.)),
+            (0, 2, Complex::new(-1., 3.5)),
+            (1, 1, Complex::new(0., -4.)),
+            (3, 3, Complex::new(1., 0.)),
+        ]);
+        b.add_element(2, 1, Complex::new(0., 0.));
+
+        let zero = Complex::new(0., 0.);
+        let differences = a.diff(&b, 0.1);
+        assert_eq!(
+            differences,
+            vec![
+                (0, 2, Complex::new(-1., 3.), Complex::new(-1., 3.5)),
+                (2, 0, Complex::new(5., 0.), zero),
+                (2, 1, zero, zero),
+                (3, 3, zero, Complex::new(1., 0.)),
+            ]
+        );
+        assert_eq!(a.diff(&b, 1.).len(), 3);
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "rayon")]
+
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use rayon::prelude::*;
+    use sparse_complex::*;
+
+    fn matrix(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(i as f64, 1.));
+            m.add_element(i, (i + 1) % n, Complex::new(-1., i as f64 * 1e-3));
+        }
+        m
+    }
+
+    #[test]
+    fn test_par_iter_matches_triplets() {
+        let m = matrix(1000);
+        let collected = m.par_iter().map(|(r, c, v)| (r, c, *v)).collect::<Vec<_>>();
+        assert_eq!(collected.len(), 2000);
+        for (r, c, v) in collected {
+            assert_eq!(m.get(r, c), Some(&v));
+        }
+    }
+
+    #[test]
+    fn test_par_iter_mut_scales_values() {
+        let mut m = matrix(1000);
+        let original = m.clone();
+        m.par_iter_mut().for_each(|(_, _, v)| *v *= Complex::new(0., 2.));
+        let scaled = m.par_iter().map(|(_, _, v)| *v).collect::<Vec<_>>();
+        let expected = original
+            .par_iter()
+            .map(|(_, _, v)| *v * Complex::new(0., 2.))
+            .collect::<Vec<_>>();
+        assert_eq!(scaled, expected);
+    }
+}
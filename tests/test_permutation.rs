@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_permutation_composition_and_inverse() {
+        let p = Permutation::from_vec(vec![3, 0, 2, 1]).unwrap();
+        let q = Permutation::from_vec(vec![1, 2, 3, 0]).unwrap();
+        let x = vec![10, 20, 30, 40];
+
+        assert_eq!(p.then(&q).apply(&x), q.apply(&p.apply(&x)));
+        assert_eq!(p.inverse().apply(&x), p.apply_inverse(&x));
+        assert_eq!(p.inverse().inverse(), p);
+        assert_eq!(p.then(&p.inverse()), Permutation::identity(4));
+        assert_eq!(p.apply(&x)[p.get(2)], x[2]);
+    }
+
+    #[test]
+    fn test_permute_matrix_moves_elements() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 2, Complex::new(1., 0.));
+        m.add_element(2, 1, Complex::new(0., 1.));
+        let rows = Permutation::from_vec(vec![1, 2, 0]).unwrap();
+        let cols = Permutation::from_vec(vec![2, 0, 1]).unwrap();
+
+        let permuted = m.permute(&rows, &cols);
+        assert_eq!(permuted.get(1, 1), Some(&Complex::new(1., 0.)));
+        assert_eq!(permuted.get(0, 0), Some(&Complex::new(0., 1.)));
+        assert_eq!(permuted.permute(&rows.inverse(), &cols.inverse()), m);
+    }
+
+    #[test]
+    fn test_permutation_rejects_invalid_indices() {
+        assert!(Permutation::from_vec(vec![1, 1]).is_err());
+        assert!(Permutation::from_vec(vec![2, 0]).is_err());
+        assert!(Permutation::from_vec(Vec::new()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_checkout_reuses_returned_workspace() {
+        let pool = SolverPool::<f64>::new();
+        assert_eq!(pool.idle(), 0);
+        {
+            let _first = pool.checkout().unwrap();
+            let _second = pool.checkout().unwrap();
+        }
+        assert_eq!(pool.idle(), 2);
+        {
+            let _workspace = pool.checkout().unwrap();
+            assert_eq!(pool.idle(), 1);
+        }
+        assert_eq!(pool.idle(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_solves() {
+        let pool = Arc::new(SolverPool::<f64>::new());
+        let handles = (0..4)
+            .map(|t| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    for k in 1..10 {
+                        let mut m = ComplexMatrix::<f64>::new();
+                        m.add_element(0, 0, Complex::new(k as f64, t as f64));
+                        m.add_element(1, 1, Complex::new(2., 0.));
+                        m.add_element(0, 1, Complex::new(1., 0.));
+
+                        let mut b = vec![Complex::new(k as f64 + 1., t as f64), Complex::new(2., 0.)];
+                        m.solve_in_place(&mut pool.checkout().unwrap(), &mut b).unwrap();
+                        assert!((b[0] - Complex::new(1., 0.)).norm() < 1e-12);
+                        assert!((b[1] - Complex::new(1., 0.)).norm() < 1e-12);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(pool.idle() >= 1 && pool.idle() <= 4);
+    }
+}
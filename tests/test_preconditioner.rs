@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn tridiagonal(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i > 0 {
+                m.add_element(i, i - 1, Complex::new(-1., 0.5));
+            }
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., -0.25));
+            }
+        }
+        m
+    }
+
+    fn mul(m: &ComplexMatrix<f64>, x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        (0..x.len())
+            .map(|i| {
+                x.iter()
+                    .enumerate()
+                    .filter_map(|(j, xj)| m.get(i, j).map(|v| v * xj))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn dot(a: &[Complex<f64>], b: &[Complex<f64>]) -> Complex<f64> {
+        a.iter().zip(b.iter()).map(|(x, y)| x.conj() * y).sum()
+    }
+
+    fn vector(n: usize, phase: f64) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|i| Complex::new((i as f64 + phase).cos(), (i as f64 * phase).sin()))
+            .collect()
+    }
+
+    #[test]
+    fn ilu0_is_exact_for_tridiagonal() {
+        let m = tridiagonal(6);
+        let x = vector(6, 0.3);
+        let b = mul(&m, &x);
+
+        let ilu = Ilu0::new(&m).unwrap();
+        let mut z = vec![Complex::new(0., 0.); 6];
+        ilu.apply(&b, &mut z);
+        for (zi, xi) in z.iter().zip(x.iter()) {
+            assert!((zi - xi).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn adjoint_application_is_consistent() {
+        let mut m = tridiagonal(5);
+        m.add_element(0, 4, Complex::new(0.5, 0.5));
+        m.add_element(4, 1, Complex::new(0., -0.7));
+        let r = vector(5, 0.7);
+        let s = vector(5, 1.9);
+
+        let preconditioners: Vec<Box<dyn Preconditioner<f64>>> = vec![
+            Box::new(IdentityPreconditioner),
+            Box::new(Jacobi::new(&m).unwrap()),
+            Box::new(Ilu0::new(&m).unwrap()),
+        ];
+        for p in preconditioners {
+            let mut z = vec![Complex::new(0., 0.); 5];
+            let mut w = vec![Complex::new(0., 0.); 5];
+            p.apply(&r, &mut z);
+            p.apply_adjoint(&s, &mut w).unwrap();
+            assert!((dot(&s, &z) - dot(&w, &r)).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn zero_diagonal_is_rejected() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        assert!(Jacobi::new(&m).is_err());
+        assert!(Ilu0::new(&m).is_err());
+    }
+
+    #[test]
+    fn user_preconditioner_uses_default_adjoint() {
+        struct Scale(f64);
+        impl Preconditioner<f64> for Scale {
+            fn apply(&self, r: &[Complex<f64>], z: &mut [Complex<f64>]) {
+                for (zi, ri) in z.iter_mut().zip(r.iter()) {
+                    *zi = ri * self.0;
+                }
+            }
+        }
+
+        let p = Scale(2.);
+        let mut z = vec![Complex::new(0., 0.); 2];
+        p.apply(&[Complex::new(1., 1.), Complex::new(0., 2.)], &mut z);
+        assert_eq!(z, vec![Complex::new(2., 2.), Complex::new(0., 4.)]);
+        assert!(p.apply_adjoint(&[Complex::new(1., 0.); 2], &mut z).is_err());
+    }
+
+    #[test]
+    fn unordered_duplicated_entries_are_compressed() {
+        let ordered = tridiagonal(6);
+        let mut shuffled = ComplexMatrix::<f64>::new();
+        for i in (0..6).rev() {
+            if i + 1 < 6 {
+                shuffled.add_element(i, i + 1, Complex::new(-1., -0.25));
+            }
+            shuffled.add_element(i, i, Complex::new(3., 1.));
+            if i > 0 {
+                shuffled.add_element(i, i - 1, Complex::new(-1., 0.5));
+            }
+            shuffled.add_element(i, i, Complex::new(1., 0.));
+        }
+
+        let r = vector(6, 1.1);
+        let mut expected = vec![Complex::new(0., 0.); 6];
+        let mut z = vec![Complex::new(0., 0.); 6];
+        Ilu0::new(&ordered).unwrap().apply(&r, &mut expected);
+        Ilu0::new(&shuffled).unwrap().apply(&r, &mut z);
+        for (zi, ei) in z.iter().zip(expected.iter()) {
+            assert!((zi - ei).norm() < 1e-12);
+        }
+    }
+
+    fn spai_error(m: &ComplexMatrix<f64>, spai: &Spai<f64>) -> f64 {
+        let r = vector(20, 0.7);
+        let mut z = vec![Complex::new(0., 0.); r.len()];
+        spai.apply(&r, &mut z);
+        let amz = mul(m, &z);
+        amz.iter().zip(r.iter()).map(|(a, b)| (a - b).norm_sqr()).sum::<f64>().sqrt()
+    }
+
+    #[test]
+    fn spai_approximates_the_inverse() {
+        let m = tridiagonal(20);
+        let spai = Spai::new(&m).unwrap();
+        assert_eq!(spai.nnz(), 58);
+        let norm = vector(20, 0.7).iter().map(|v| v.norm_sqr()).sum::<f64>().sqrt();
+        assert!(spai_error(&m, &spai) < 0.2 * norm);
+
+        // The pattern of A² allows a closer fit.
+        let mut square = ComplexMatrix::<f64>::new();
+        for i in 0..20usize {
+            for j in i.saturating_sub(2)..(i + 3).min(20) {
+                square.add_element(i, j, Complex::new(1., 0.));
+            }
+        }
+        let wider = Spai::with_pattern(&m, &square).unwrap();
+        assert!(spai_error(&m, &wider) < spai_error(&m, &spai));
+    }
+
+    #[test]
+    fn spai_adjoint_is_consistent() {
+        let m = tridiagonal(8);
+        let spai = Spai::new(&m).unwrap();
+        let (x, y) = (vector(8, 0.3), vector(8, 1.9));
+        let mut mx = vec![Complex::new(0., 0.); 8];
+        let mut mhy = vec![Complex::new(0., 0.); 8];
+        spai.apply(&x, &mut mx);
+        spai.apply_adjoint(&y, &mut mhy).unwrap();
+        assert!((dot(&y, &mx) - dot(&mhy, &x)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn spai_rejects_empty_column() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(0., 0.));
+        assert!(Spai::new(&m).is_err());
+    }
+}
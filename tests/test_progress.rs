@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_progress_batch_reaches_completion() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&reports);
+        let mut systems = (1..500)
+            .map(|k| {
+                SmallSystem::new(
+                    1,
+                    vec![Complex::new(k as f64, 0.)],
+                    vec![Complex::new(k as f64, 0.)],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let status = with_progress(
+            move |phase, fraction| log.lock().unwrap().push((phase, fraction)),
+            || solve_batch_small(&mut systems),
+        );
+        assert!(status.iter().all(|s| s.is_ok()));
+
+        let reports = reports.lock().unwrap();
+        assert!(reports.iter().all(|(phase, _)| *phase == Phase::Batch));
+        assert_eq!(reports.first(), Some(&(Phase::Batch, Some(0.))));
+        assert!(reports.len() <= 101);
+        let last = reports.iter().filter_map(|(_, f)| *f).fold(0., f64::max);
+        assert_eq!(last, 1.);
+    }
+
+    #[test]
+    fn test_progress_is_scoped() {
+        let reports = Arc::new(Mutex::new(0));
+        let log = Arc::clone(&reports);
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+
+        with_progress(move |_, _| *log.lock().unwrap() += 1, || m.to_compressed());
+        let inside = *reports.lock().unwrap();
+        m.to_compressed();
+        assert!(inside > 0);
+        assert_eq!(*reports.lock().unwrap(), inside);
+    }
+
+    #[test]
+    fn test_progress_sparse_factorization() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&reports);
+        let n = 1000;
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(-1., 0.));
+            }
+        }
+
+        let lu = with_progress(
+            move |phase, fraction| log.lock().unwrap().push((phase, fraction)),
+            || m.factorize_sparse(),
+        );
+        assert!(lu.is_ok());
+
+        let reports = reports.lock().unwrap();
+        let fractions = reports
+            .iter()
+            .filter(|(phase, _)| *phase == Phase::Factorization)
+            .filter_map(|(_, f)| *f)
+            .collect::<Vec<_>>();
+        assert_eq!(fractions.len(), 101);
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(fractions.last(), Some(&1.));
+    }
+}
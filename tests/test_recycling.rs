@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn shifted_laplacian(n: usize, shift: f64) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(2. - shift, shift));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(-1., 0.));
+            }
+        }
+        m
+    }
+
+    fn relative_residual(m: &ComplexMatrix<f64>, b: &[Complex<f64>], x: &[Complex<f64>]) -> f64 {
+        let ax = m.pow_apply(1, x);
+        let r = b
+            .iter()
+            .zip(ax.iter())
+            .map(|(bi, axi)| (bi - axi).norm_sqr())
+            .sum::<f64>();
+        (r / b.iter().map(|bi| bi.norm_sqr()).sum::<f64>()).sqrt()
+    }
+
+    #[test]
+    fn test_recycling_reduces_iterations_over_a_sweep() {
+        let n = 200;
+        let b = (0..n)
+            .map(|i| Complex::new(1., (i % 7) as f64))
+            .collect::<Vec<_>>();
+        let mut recycling = RecyclingSolver::<f64>::new(30, 10);
+        recycling.set_tolerance(1e-10);
+        recycling.set_max_iterations(5000);
+
+        for k in 0..5 {
+            let m = shifted_laplacian(n, 1e-4 * k as f64);
+            let mut x = vec![Complex::new(0., 0.); n];
+            let recycled = recycling
+                .solve(&m, &IdentityPreconditioner, &b, &mut x)
+                .unwrap();
+            assert!(relative_residual(&m, &b, &x) < 1e-9);
+            assert_eq!(recycling.recycled_dimension(), 10);
+
+            let mut cold = RecyclingSolver::<f64>::new(30, 10);
+            cold.set_tolerance(1e-10);
+            cold.set_max_iterations(5000);
+            let mut x = vec![Complex::new(0., 0.); n];
+            let cold_started = cold.solve(&m, &IdentityPreconditioner, &b, &mut x).unwrap();
+            if k > 0 {
+                assert!(
+                    3 * recycled < 2 * cold_started,
+                    "{} vs {}",
+                    recycled,
+                    cold_started
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_recycling_with_preconditioner() {
+        let n = 50;
+        let m = shifted_laplacian(n, 0.1);
+        let jacobi = Jacobi::new(&m).unwrap();
+        let b = vec![Complex::new(0., 1.); n];
+        let mut solver = RecyclingSolver::<f64>::new(10, 3);
+        let mut x = vec![Complex::new(0., 0.); n];
+        solver.solve(&m, &jacobi, &b, &mut x).unwrap();
+        assert!(relative_residual(&m, &b, &x) < 1e-7);
+
+        solver.clear();
+        assert_eq!(solver.recycled_dimension(), 0);
+        assert!(solver.solve(&m, &jacobi, &b, &mut x[1..]).is_err());
+    }
+
+    #[test]
+    fn test_recycling_reports_non_convergence() {
+        let m = shifted_laplacian(100, 0.);
+        let b = vec![Complex::new(1., 0.); 100];
+        let mut solver = RecyclingSolver::<f64>::new(5, 2);
+        solver.set_max_iterations(10);
+        let mut x = vec![Complex::new(0., 0.); 100];
+        assert!(solver
+            .solve(&m, &IdentityPreconditioner, &b, &mut x)
+            .is_err());
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_residual_norm() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 1.));
+        m.add_element(0, 0, Complex::new(1., -1.));
+        m.add_element(0, 1, Complex::new(0., 1.));
+        m.add_element(1, 0, Complex::new(3., 0.));
+
+        // Ax = [2 + i, 3], so Ax - b = [1, 3 - 4i].
+        let x = vec![Complex::new(1., 0.), Complex::new(1., 0.)];
+        let b = vec![Complex::new(1., 1.), Complex::new(0., 4.)];
+        let r = m.residual_norm(&x, &b).unwrap();
+        assert_abs_diff_eq!(r.norm2, 26f64.sqrt(), epsilon = 1e-12);
+        assert_abs_diff_eq!(r.norm_inf, 5., epsilon = 1e-12);
+
+        let exact = vec![Complex::new(2., 1.), Complex::new(3., 0.)];
+        let r = m.residual_norm(&x, &exact).unwrap();
+        assert_eq!(r.norm2, 0.);
+        assert_eq!(r.norm_inf, 0.);
+    }
+
+    #[test]
+    fn test_residual_norm_dimensions() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let two = vec![Complex::new(1., 0.); 2];
+        let three = vec![Complex::new(1., 0.); 3];
+        assert_eq!(
+            m.residual_norm(&three, &two),
+            Err(SolveError::DimensionMismatch)
+        );
+        assert_eq!(
+            m.residual_norm(&two, &three),
+            Err(SolveError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,104 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn tridiagonal(n: usize, shift: f64) -> Vec<(usize, usize, Complex<f64>)> {
+        let mut elements = Vec::new();
+        for i in 0..n {
+            elements.push((i, i, Complex::new(4., shift)));
+            if i + 1 < n {
+                elements.push((i, i + 1, Complex::new(-1., 0.)));
+                elements.push((i + 1, i, Complex::new(-1., 0.)));
+            }
+        }
+        elements
+    }
+
+    #[test]
+    fn test_solve_pattern_batch_matches_solve() {
+        let n = 12;
+        let pattern = ComplexMatrix::from_entries(tridiagonal(n, 0.));
+        let analysis = pattern.analyze_pattern().unwrap();
+        let shifts = [0.5, 1.5, -1., 2.];
+        let values = shifts
+            .iter()
+            .map(|s| tridiagonal(n, *s).into_iter().map(|(_, _, v)| v).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let b = (0..n).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+        let mut rhs = vec![b.clone(); shifts.len()];
+
+        let status = pattern.solve_pattern_batch(&analysis, &values, &mut rhs);
+        assert!(status.iter().all(|s| s.is_ok()));
+        for (shift, x) in shifts.iter().zip(rhs.iter()) {
+            let mut expected = b.clone();
+            ComplexMatrix::from_entries(tridiagonal(n, *shift)).solve(&mut expected).unwrap();
+            for (x, y) in x.iter().zip(expected.iter()) {
+                assert!((x - y).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_pattern_batch_reports_each_system() {
+        let mut pattern = ComplexMatrix::<f64>::new();
+        pattern.add_element(0, 0, Complex::new(1., 0.));
+        pattern.add_element(1, 1, Complex::new(1., 0.));
+        let analysis = pattern.analyze_pattern().unwrap();
+
+        let values = vec![
+            vec![Complex::new(1., 0.)],
+            vec![Complex::new(2., 0.); 2],
+            vec![Complex::new(1., 0.); 2],
+        ];
+        let mut rhs = vec![
+            vec![Complex::new(1., 0.); 2],
+            vec![Complex::new(1., 0.); 3],
+            vec![Complex::new(1., 0.); 2],
+        ];
+        let status = pattern.solve_pattern_batch(&analysis, &values, &mut rhs);
+        assert!(status[0].is_err());
+        assert!(status[1].is_err());
+        assert_eq!(status[2], Ok(()));
+
+        // An analysis of a smaller pattern is rejected for every system.
+        let mut larger = pattern.clone();
+        larger.add_element(4, 4, Complex::new(1., 0.));
+        let values = vec![vec![Complex::new(1., 0.); 3]; 2];
+        let mut rhs = vec![vec![Complex::new(1., 0.); 2]; 2];
+        let status = larger.solve_pattern_batch(&analysis, &values, &mut rhs);
+        assert!(status.iter().all(|s| *s == Err(SolveError::DimensionMismatch)));
+    }
+
+    #[test]
+    fn test_solve_sweep_matches_solve() {
+        let n = 10;
+        let pattern = ComplexMatrix::from_entries(tridiagonal(n, 0.));
+        let frequencies = [0.1, 1., 10., 100.];
+        let values = |f: f64| {
+            tridiagonal(n, f)
+                .into_iter()
+                .map(|(_, _, v)| v)
+                .collect::<Vec<_>>()
+        };
+        let b = (0..n).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+
+        let solutions = pattern.solve_sweep(&frequencies, values, &b).unwrap();
+        assert_eq!(solutions.len(), frequencies.len());
+        for (f, x) in frequencies.iter().zip(solutions) {
+            let mut expected = b.clone();
+            ComplexMatrix::from_entries(tridiagonal(n, *f)).solve(&mut expected).unwrap();
+            for (x, y) in x.unwrap().iter().zip(expected.iter()) {
+                assert!((x - y).norm() < 1e-12);
+            }
+        }
+
+        let short = pattern.solve_sweep(&frequencies, |_| vec![Complex::new(1., 0.)], &b).unwrap();
+        assert!(short.iter().all(|s| *s == Err(SolveError::DimensionMismatch)));
+        assert_eq!(
+            pattern.solve_sweep(&frequencies, values, &b[1..]),
+            Err(SolveError::DimensionMismatch)
+        );
+    }
+}
@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "f64")]
     use approx::assert_abs_diff_eq;
     use sparse_complex::*;
     use num::Complex;
+    #[cfg(feature = "f64")]
     #[test]
     //#[ignore = "Solve not implemented"]
     fn test_simple_matrix() {
@@ -19,6 +21,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     // #[ignore = "Solve not implemented"]
     fn test_full_simple_matrix() {
@@ -42,6 +45,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     fn test_only_imag_simple_matrix() {
         let mut m = ComplexMatrix::<f64>::new();
@@ -61,6 +65,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     fn test_only_real_simple_matrix() {
         let mut m = ComplexMatrix::<f64>::new();
@@ -80,6 +85,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     fn test_part_real_part_imag_simple_matrix() {
         let mut m = ComplexMatrix::<f64>::new();
@@ -99,6 +105,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     fn test_b_imag_is_zero() {
         let mut m = ComplexMatrix::<f64>::new();
@@ -117,6 +124,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f64")]
     #[test]
     fn test_b_real_is_zero() {
         let mut m = ComplexMatrix::<f64>::new();
@@ -135,4 +143,209 @@ mod tests {
             assert_abs_diff_eq!(e.im, &expected[i].im, epsilon = 1e-6);
         }
     }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_pivot_growth() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1e-3, 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let growth = m.pivot_growth().unwrap();
+        assert!(growth >= 1.);
+        assert!(growth < 10.);
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_pivot_growth_singular() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        assert!(m.pivot_growth().is_err());
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_from_sorted_entries_solve() {
+        let entries = vec![
+            (0, 0, Complex::new(5., 3.)),
+            (1, 0, Complex::new(0., -47.)),
+            (0, 1, Complex::new(-33., 0.)),
+            (1, 1, Complex::new(1., -9.)),
+        ];
+        let m = ComplexMatrix::<f64>::from_sorted_entries(entries).unwrap();
+
+        let mut b = vec![Complex::new(13.4, 7.), Complex::new(3.2, -7.)];
+        let expected = [
+            Complex::new(0.21852826260018543, 0.10986007256547237),
+            Complex::new(-0.3829375425665308, -0.1756095408900631),
+        ];
+        m.solve(&mut b).unwrap();
+
+        for (i, e) in b.iter().enumerate() {
+            assert_abs_diff_eq!(e.re, &expected[i].re, epsilon = 1e-6);
+            assert_abs_diff_eq!(e.im, &expected[i].im, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_from_sorted_entries_rejects_duplicates() {
+        let entries = vec![(0, 0, Complex::new(1., 0.)), (0, 0, Complex::new(1., 0.))];
+        assert!(ComplexMatrix::<f64>::from_sorted_entries(entries).is_err());
+    }
+
+    #[test]
+    fn test_add_elements_matches_add_element() {
+        let elements = [
+            (0, 0, Complex::new(5., 3.)),
+            (1, 1, Complex::new(1., -9.)),
+            (0, 1, Complex::new(-33., 0.)),
+        ];
+        let mut single = ComplexMatrix::<f64>::new();
+        for (row, col, value) in elements {
+            single.add_element(row, col, value);
+        }
+
+        let mut bulk = ComplexMatrix::<f64>::new();
+        bulk.add_elements(&elements);
+        assert_eq!(bulk, single);
+
+        let mut parts = ComplexMatrix::<f64>::new();
+        parts.add_elements_from_parts(
+            &[0, 1, 0],
+            &[0, 1, 1],
+            &[Complex::new(5., 3.), Complex::new(1., -9.), Complex::new(-33., 0.)],
+        );
+        assert_eq!(parts, single);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_elements_from_parts_length_mismatch() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_elements_from_parts(&[0, 1], &[0], &[Complex::new(1., 0.)]);
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_dense_path_matches_sparse_solver() {
+        let n = 10;
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., i as f64));
+            m.add_element(i, (i + 3) % n, Complex::new(-1., 0.5));
+            m.add_element((i + 1) % n, i, Complex::new(0.5, -1.));
+        }
+        let b = (0..n).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+
+        // The sparse LU is used directly, as the dense threshold is shared by the tests.
+        assert!(n <= dense_solve_threshold());
+        let mut sparse = b.clone();
+        m.factorize_sparse().unwrap().solve(&mut sparse).unwrap();
+        let mut dense = b;
+        m.solve(&mut dense).unwrap();
+
+        for (d, s) in dense.iter().zip(sparse.iter()) {
+            assert_abs_diff_eq!(d.re, s.re, epsilon = 1e-10);
+            assert_abs_diff_eq!(d.im, s.im, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_from_fn_matches_from_row_fn() {
+        let value = |i: usize, j: usize| Complex::new(i as f64, j as f64);
+        let a = ComplexMatrix::<f64>::from_fn(5, 5, |i, j| if (i + j) % 3 == 0 { Some(value(i, j)) } else { None });
+        let b = ComplexMatrix::<f64>::from_row_fn(5, |i| {
+            (0..5).filter(move |j| (i + j) % 3 == 0).map(move |j| (j, value(i, j)))
+        });
+        assert_eq!(a, b);
+        assert_eq!(a.get(4, 2), Some(&Complex::new(4., 2.)));
+        assert_eq!(a.get(4, 3), None);
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_singular() {
+        // Large and wide enough to reach the sparse solver; column 39 is empty.
+        let n = 40;
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n - 1 {
+            m.add_element(i, i, Complex::new(4., 1.));
+        }
+        m.add_element(n - 1, 0, Complex::new(1., 0.));
+        m.add_element(0, n / 2, Complex::new(1., 0.));
+
+        let mut b = vec![Complex::new(1., 0.); n];
+        assert!(m.solve(&mut b).is_err());
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_solve_singular_f32() {
+        let n = 40;
+        let mut m = ComplexMatrix::<f32>::new();
+        for i in 0..n - 1 {
+            m.add_element(i, i, Complex::new(4., 1.));
+        }
+        m.add_element(n - 1, 0, Complex::new(1., 0.));
+        m.add_element(0, n / 2, Complex::new(1., 0.));
+
+        let mut b = vec![Complex::new(1., 0.); n];
+        assert!(m.solve(&mut b).is_err());
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_dimension_mismatch() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let mut short = vec![Complex::new(1., 0.)];
+        assert_eq!(m.solve(&mut short), Err(SolveError::DimensionMismatch));
+        let mut long = vec![Complex::new(1., 0.); 3];
+        assert_eq!(m.solve(&mut long), Err(SolveError::DimensionMismatch));
+        assert_eq!(long, vec![Complex::new(1., 0.); 3]);
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_into() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(5., 3.));
+        m.add_element(1, 0, Complex::new(0., -47.));
+        m.add_element(0, 1, Complex::new(-33., 0.));
+        m.add_element(1, 1, Complex::new(1., -9.));
+
+        let b = vec![Complex::new(13.4, 7.), Complex::new(3.2, -7.)];
+        let mut expected = b.clone();
+        m.solve(&mut expected).unwrap();
+        assert_eq!(m.solve_into(&b).unwrap(), expected);
+        assert!(m.solve_into(&b[..1]).is_err());
+    }
+
+    #[cfg(any(feature = "f64", feature = "f32"))]
+    fn solve_generic<T: SolvableFloat>(scale: T) -> Vec<Complex<T>> {
+        let mut m = ComplexMatrix::<T>::new();
+        m.add_element(0, 0, Complex::new(scale, T::zero()));
+        m.add_element(1, 1, Complex::new(T::zero(), scale));
+        let mut b = vec![Complex::new(scale, T::zero()); 2];
+        m.solve(&mut b).unwrap();
+        b
+    }
+
+    #[cfg(any(feature = "f64", feature = "f32"))]
+    #[test]
+    fn test_solve_generic() {
+        #[cfg(feature = "f64")]
+        assert_eq!(solve_generic(2f64), vec![Complex::new(1., 0.), Complex::new(0., -1.)]);
+        #[cfg(feature = "f32")]
+        assert_eq!(solve_generic(2f32), vec![Complex::new(1., 0.), Complex::new(0., -1.)]);
+    }
 }
@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// An unsymmetric 2D convection-diffusion stencil on a ```side × side``` grid,
+    /// with every other diagonal entry removed to force off-diagonal pivots.
+    fn convection(side: usize) -> ComplexMatrix<f64> {
+        let n = side * side;
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            let (x, y) = (i % side, i / side);
+            if i % 2 == 0 {
+                m.add_element(i, i, Complex::new(4., 0.5));
+            }
+            if x + 1 < side {
+                m.add_element(i, i + 1, Complex::new(-1.5, 0.));
+                m.add_element(i + 1, i, Complex::new(-0.5, 0.1));
+            }
+            if y + 1 < side {
+                m.add_element(i, i + side, Complex::new(-1., 0.2));
+                m.add_element(i + side, i, Complex::new(-1., 0.));
+            }
+        }
+        m
+    }
+
+    fn residual(m: &ComplexMatrix<f64>, x: &[Complex<f64>], b: &[Complex<f64>]) -> f64 {
+        let r = m.residual_norm(x, b).unwrap();
+        let b_norm = b.iter().map(|bi| bi.norm_sqr()).sum::<f64>().sqrt();
+        r.norm2 / b_norm
+    }
+
+    #[test]
+    fn test_sparse_lu_solves() {
+        let m = convection(20);
+        let lu = m.factorize_sparse().unwrap();
+        assert_eq!(lu.size(), 400);
+        assert!(lu.nnz() >= m.triplets().count());
+        assert!(lu.pivot_growth() >= 1.);
+
+        for k in 0..3 {
+            let b = (0..400)
+                .map(|i| Complex::new((i * (k + 1)) as f64 % 7., k as f64))
+                .collect::<Vec<_>>();
+            let mut x = b.clone();
+            lu.solve(&mut x).unwrap();
+            assert!(residual(&m, &x, &b) < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sparse_lu_permutations() {
+        // The diagonal is empty, so every pivot is off the diagonal.
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 2, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(0., 2.));
+        m.add_element(2, 1, Complex::new(3., 0.));
+        m.add_element(2, 0, Complex::new(1., 1.));
+
+        let lu = m.factorize_sparse().unwrap();
+        assert_eq!(lu.row_permutation().len(), 3);
+        assert!(Permutation::from_vec(lu.column_permutation().as_slice().to_vec()).is_ok());
+        let b = vec![
+            Complex::new(1., 0.),
+            Complex::new(0., 2.),
+            Complex::new(4., 1.),
+        ];
+        let mut x = b.clone();
+        lu.solve(&mut x).unwrap();
+        assert!(residual(&m, &x, &b) < 1e-15);
+        assert_eq!(lu.pivot_growth(), 1.);
+    }
+
+    #[test]
+    fn test_sparse_lu_errors() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 0.));
+        assert_eq!(m.factorize_sparse(), Err(SolveError::Singular));
+
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        assert_eq!(m.factorize_sparse(), Err(SolveError::Singular));
+
+        let lu = convection(3).factorize_sparse().unwrap();
+        let mut short = vec![Complex::new(1., 0.); 8];
+        assert_eq!(lu.solve(&mut short), Err(SolveError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_sparse_lu_sorted_entries() {
+        let m = convection(5);
+        let mut entries = m.triplets().collect::<Vec<_>>();
+        entries.sort_by_key(|t| (t.col, t.row));
+        let sorted = ComplexMatrix::from_sorted_entries(entries).unwrap();
+
+        let b = (0..25)
+            .map(|i| Complex::new(1., i as f64))
+            .collect::<Vec<_>>();
+        let mut x = b.clone();
+        sorted.factorize_sparse().unwrap().solve(&mut x).unwrap();
+        assert!(residual(&m, &x, &b) < 1e-14);
+    }
+
+    #[test]
+    fn test_sparse_lu_serialization() {
+        let m = convection(6);
+        let lu = m.factorize_sparse().unwrap();
+        let bytes = lu.to_bytes();
+        let restored = SparseLu::<f64>::from_bytes(&bytes).unwrap();
+        assert_eq!(restored, lu);
+
+        let b = (0..36)
+            .map(|i| Complex::new(i as f64, 1.))
+            .collect::<Vec<_>>();
+        let mut x = b.clone();
+        restored.solve(&mut x).unwrap();
+        assert!(residual(&m, &x, &b) < 1e-14);
+
+        let path = std::env::temp_dir().join("sparse_complex_test_lu.bin");
+        lu.save(&path).unwrap();
+        assert_eq!(SparseLu::<f64>::load(&path).unwrap(), lu);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(SparseLu::<f64>::from_bytes(&bytes[..bytes.len() - 8]).is_err());
+        assert!(SparseLu::<f32>::from_bytes(&bytes).is_err());
+        assert!(SparseLu::<f64>::from_bytes(b"SCLU").is_err());
+        let mut corrupted = bytes.clone();
+        // The first pivot, right after the header.
+        corrupted[44..52].copy_from_slice(&1000u64.to_le_bytes());
+        assert!(SparseLu::<f64>::from_bytes(&corrupted).is_err());
+
+        // In range, but outside the triangles the solves read.
+        let word = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap()) as usize;
+        let (n, nnz_l, nnz_u) = (word(12), word(20), word(28));
+        let l_indices = 44 + 8 * (3 * n + 1);
+        let u_last = l_indices + 8 * (nnz_l + n + nnz_u);
+        let mut corrupted = bytes.clone();
+        // The row pivoted at the first step, in the first entry of L.
+        corrupted[l_indices..l_indices + 8].copy_from_slice(&bytes[44..52]);
+        assert!(SparseLu::<f64>::from_bytes(&corrupted).is_err());
+        let mut corrupted = bytes.clone();
+        // The last step, in the last entry of U.
+        corrupted[u_last..u_last + 8].copy_from_slice(&(n as u64 - 1).to_le_bytes());
+        assert!(SparseLu::<f64>::from_bytes(&corrupted).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn laplacian_1d(cells: usize, boundary: Boundary) -> ComplexMatrix<f64> {
+        let grid = Grid::new([cells]);
+        ComplexMatrix::from_stencil(&grid, boundary, |_| {
+            vec![
+                ([-1], Complex::new(-1., 0.)),
+                ([0], Complex::new(2., 0.)),
+                ([1], Complex::new(-1., 0.)),
+            ]
+        })
+    }
+
+    #[test]
+    fn test_truncated_1d_is_tridiagonal() {
+        let m = laplacian_1d(4, Boundary::Truncate);
+        assert_eq!(m.get(0, 0), Some(&Complex::new(2., 0.)));
+        assert_eq!(m.get(0, 1), Some(&Complex::new(-1., 0.)));
+        assert_eq!(m.get(0, 3), None);
+        assert_eq!(m.get(3, 0), None);
+    }
+
+    #[test]
+    fn test_periodic_1d_wraps() {
+        let m = laplacian_1d(4, Boundary::Periodic);
+        assert_eq!(m.get(0, 3), Some(&Complex::new(-1., 0.)));
+        assert_eq!(m.get(3, 0), Some(&Complex::new(-1., 0.)));
+    }
+
+    #[test]
+    fn test_3d_linearization_roundtrip() {
+        let grid = Grid::new([2, 3, 4]);
+        assert_eq!(grid.len(), 24);
+        for index in 0..grid.len() {
+            assert_eq!(grid.linear_index(grid.cell(index)), index);
+        }
+        assert_eq!(grid.linear_index([1, 0, 0]), 12);
+        assert_eq!(grid.linear_index([0, 1, 0]), 4);
+        assert_eq!(grid.linear_index([0, 0, 1]), 1);
+    }
+
+    #[test]
+    fn test_stencil_coefficients_depend_on_cell() {
+        let grid = Grid::new([2, 2]);
+        let m = ComplexMatrix::<f64>::from_stencil(&grid, Boundary::Truncate, |cell| {
+            vec![([0, 0], Complex::new(cell[0] as f64, cell[1] as f64))]
+        });
+        assert_eq!(m.get(grid.linear_index([1, 0]), 2), Some(&Complex::new(1., 0.)));
+        assert_eq!(m.get(3, 3), Some(&Complex::new(1., 1.)));
+    }
+}
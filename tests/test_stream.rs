@@ -0,0 +1,50 @@
+#![cfg(feature = "eigen")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_stream_matches_solve() {
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..6 {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < 6 {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(-1., 0.));
+            }
+        }
+        let rhs = (0..20)
+            .map(|k| (0..6).map(|i| Complex::new((i * k) as f64, 1.)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut seen = Vec::new();
+        let solved = m
+            .solve_stream(rhs.iter(), |i, x| {
+                let mut expected = rhs[i].clone();
+                m.solve(&mut expected).unwrap();
+                for (x, y) in x.iter().zip(expected.iter()) {
+                    assert!((x - y).norm() < 1e-12);
+                }
+                seen.push(i);
+            })
+            .unwrap();
+        assert_eq!(solved, 20);
+        assert_eq!(seen, (0..20).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_solve_stream_rejects_mismatched_lengths() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        let rhs = vec![vec![Complex::new(1., 0.); 2], vec![Complex::new(1., 0.); 3]];
+
+        let mut calls = 0;
+        assert!(m.solve_stream(rhs, |_, _| calls += 1).is_err());
+        assert_eq!(calls, 1);
+        assert_eq!(m.solve_stream(Vec::<Vec<Complex<f32>>>::new(), |_, _| calls += 1), Ok(0));
+    }
+}
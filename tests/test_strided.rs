@@ -0,0 +1,53 @@
+#![cfg(feature = "eigen")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_strided_matches_solve() {
+        let (n, nrhs, ld) = (5, 3, 7);
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(0., -1.));
+            }
+        }
+        let pad = Complex::new(-7., 7.);
+        let mut b = vec![pad; (nrhs - 1) * ld + n];
+        for j in 0..nrhs {
+            for i in 0..n {
+                b[j * ld + i] = Complex::new(i as f64, j as f64);
+            }
+        }
+        let original = b.clone();
+
+        m.solve_strided(&mut b, n, nrhs, ld).unwrap();
+        for j in 0..nrhs {
+            let mut expected = original[j * ld..j * ld + n].to_vec();
+            m.solve(&mut expected).unwrap();
+            for (x, y) in b[j * ld..j * ld + n].iter().zip(expected.iter()) {
+                assert!((x - y).norm() < 1e-12);
+            }
+            if j + 1 < nrhs {
+                assert!(b[j * ld + n..(j + 1) * ld].iter().all(|v| *v == pad));
+            }
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_solve_strided_rejects_bad_layouts() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+
+        let mut b = vec![Complex::new(1., 0.); 4];
+        assert!(m.solve_strided(&mut b, 2, 2, 1).is_err());
+        assert!(m.solve_strided(&mut b, 2, 2, 3).is_err());
+        assert_eq!(m.solve_strided(&mut b, 2, 0, 3), Ok(()));
+    }
+}
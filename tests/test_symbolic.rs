@@ -0,0 +1,118 @@
+#![cfg(all(feature = "eigen", feature = "f64"))]
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn arrow(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            if i > 0 {
+                m.add_element(i, 0, Complex::new(-1., 0.));
+                m.add_element(0, i, Complex::new(-1., 0.));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_analysis_permutation_is_valid() {
+        let analysis = arrow(6).analyze_pattern().unwrap();
+        let mut perm = analysis.column_permutation().as_slice().to_vec();
+        perm.sort_unstable();
+        assert_eq!(perm, (0..6).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_estimate_bounds() {
+        let m = arrow(6);
+        let estimate = *m.analyze_pattern().unwrap().estimate();
+
+        // L and U hold at least the diagonal and the pattern of A.
+        assert!(estimate.nnz_l >= 6);
+        assert!(estimate.nnz_l + estimate.nnz_u - 6 >= 16);
+        assert!(estimate.flops > 0.);
+    }
+
+    #[test]
+    fn test_analysis_roundtrip_bytes() {
+        let analysis = arrow(5).analyze_pattern().unwrap();
+        let restored = SymbolicAnalysis::from_bytes(&analysis.to_bytes()).unwrap();
+        assert_eq!(restored, analysis);
+    }
+
+    #[test]
+    fn test_analysis_rejects_invalid_bytes() {
+        assert!(SymbolicAnalysis::from_bytes(b"not an analysis").is_err());
+        assert!(SymbolicAnalysis::from_bytes(&[]).is_err());
+
+        // A header claiming 2^61 columns must not overflow the length check.
+        let mut bytes = arrow(2).analyze_pattern().unwrap().to_bytes();
+        bytes[8..16].copy_from_slice(&(1u64 << 61).to_le_bytes());
+        bytes.truncate(48);
+        assert!(SymbolicAnalysis::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_solve_with_reused_analysis() {
+        let analysis = arrow(5).analyze_pattern().unwrap();
+        let analysis = SymbolicAnalysis::from_bytes(&analysis.to_bytes()).unwrap();
+
+        // Same pattern, different values.
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..5 {
+            m.add_element(i, i, Complex::new(2., -1.));
+            if i > 0 {
+                m.add_element(i, 0, Complex::new(0., 0.5));
+                m.add_element(0, i, Complex::new(0.5, 0.));
+            }
+        }
+        let b = vec![Complex::new(1., 0.); 5];
+
+        let mut expected = b.clone();
+        m.solve(&mut expected).unwrap();
+        let mut x = b;
+        m.solve_with_analysis(&analysis, &mut x).unwrap();
+
+        for (e, v) in expected.iter().zip(x.iter()) {
+            assert_abs_diff_eq!(e.re, v.re, epsilon = 1e-10);
+            assert_abs_diff_eq!(e.im, v.im, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_solve_with_analysis_size_mismatch() {
+        let analysis = arrow(5).analyze_pattern().unwrap();
+        let mut b = vec![Complex::new(1., 0.); 4];
+        assert!(arrow(4).solve_with_analysis(&analysis, &mut b).is_err());
+
+        // The analysis and b agree, but the matrix is larger than both.
+        let analysis = arrow(2).analyze_pattern().unwrap();
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert_eq!(
+            arrow(6).solve_with_analysis(&analysis, &mut b),
+            Err(SolveError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_solve_with_given_ordering() {
+        let m = arrow(5);
+        // Eliminating the hub last avoids any fill-in.
+        let ordering = Permutation::from_vec(vec![4, 0, 1, 2, 3]).unwrap();
+        let analysis = m.analyze_with_ordering(ordering).unwrap();
+        assert_eq!(analysis.estimate().fill_in, 0);
+        assert!(m.analyze_with_ordering(Permutation::identity(4)).is_err());
+
+        let mut expected = vec![Complex::new(1., 0.); 5];
+        m.solve(&mut expected).unwrap();
+        let mut x = vec![Complex::new(1., 0.); 5];
+        m.solve_with_analysis(&analysis, &mut x).unwrap();
+        for (e, v) in expected.iter().zip(x.iter()) {
+            assert_abs_diff_eq!(e.re, v.re, epsilon = 1e-10);
+            assert_abs_diff_eq!(e.im, v.im, epsilon = 1e-10);
+        }
+    }
+}
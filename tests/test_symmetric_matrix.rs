@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_hermitian_mirrors_lower_into_upper() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Upper);
+        m.add_element(0, 0, Complex::new(4., 0.));
+        m.add_element(2, 1, Complex::new(1., 2.));
+
+        let full = m.to_complex_matrix();
+        assert_eq!(full.get(2, 1), Some(&Complex::new(1., 2.)));
+        assert_eq!(full.get(1, 2), Some(&Complex::new(1., -2.)));
+        assert_eq!(full.get(0, 0), Some(&Complex::new(4., 0.)));
+    }
+
+    #[test]
+    fn test_complex_symmetric_lower() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::ComplexSymmetric, Triangle::Lower);
+        m.add_element(0, 3, Complex::new(1., 2.));
+
+        assert_eq!(m.get(3, 0), Some(Complex::new(1., 2.)));
+        assert_eq!(m.get(0, 3), Some(Complex::new(1., 2.)));
+
+        let full = m.to_complex_matrix();
+        assert_eq!(full.get(0, 3), Some(&Complex::new(1., 2.)));
+        assert_eq!(full.get(3, 0), Some(&Complex::new(1., 2.)));
+    }
+
+    #[test]
+    fn test_diagonal_is_not_duplicated() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Lower);
+        m.add_element(1, 1, Complex::new(3., 0.));
+        let full = m.to_complex_matrix();
+        let expected = ComplexMatrix::from_entries(vec![(1, 1, Complex::new(3., 0.))]);
+        assert_eq!(full, expected);
+    }
+
+    #[test]
+    fn test_hermitian_diagonal_is_real() {
+        let mut m = SymmetricMatrix::<f64>::new(Symmetry::Hermitian, Triangle::Upper);
+        m.add_element(0, 0, Complex::new(2., 0.5));
+        m.add_element(0, 1, Complex::new(1., 1.));
+        assert_eq!(m.get(0, 0), Some(Complex::new(2., 0.)));
+        let full = m.to_complex_matrix();
+        assert_eq!(full.get(0, 0), Some(&Complex::new(2., 0.)));
+
+        let mut symmetric =
+            SymmetricMatrix::<f64>::new(Symmetry::ComplexSymmetric, Triangle::Upper);
+        symmetric.add_element(0, 0, Complex::new(2., 0.5));
+        assert_eq!(symmetric.get(0, 0), Some(Complex::new(2., 0.5)));
+    }
+}
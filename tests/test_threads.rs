@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use sparse_complex::*;
+
+    #[test]
+    fn test_with_num_threads_overrides_and_restores() {
+        let outside = num_threads();
+        let (inside, nested) = with_num_threads(3, || (num_threads(), with_num_threads(1, num_threads)));
+        assert_eq!(inside, 3);
+        assert_eq!(nested, 1);
+        assert_eq!(num_threads(), outside);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_with_num_threads_limits_rayon_pool() {
+        assert_eq!(with_num_threads(2, rayon::current_num_threads), 2);
+        assert_eq!(with_num_threads(1, rayon::current_num_threads), 1);
+    }
+
+    #[cfg(all(feature = "openmp", feature = "f64"))]
+    #[test]
+    fn test_set_num_threads_with_openmp() {
+        use num::Complex;
+
+        let n = 200;
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., 1.));
+            m.add_element(i, (i + 7) % n, Complex::new(-1., 0.));
+            m.add_element((i + 13) % n, i, Complex::new(0., -1.));
+        }
+        let b = vec![Complex::new(1., 0.); n];
+        set_num_threads(2);
+        let mut x = b.clone();
+        m.solve(&mut x).unwrap();
+        set_num_threads(0);
+        assert_eq!(num_threads(), 0);
+        assert!(m.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_triplet_tuple_conversions() {
+        let tuple = (2, 1, Complex::new(1., -1.));
+        let triplet = Triplet::<f64>::from(tuple);
+        assert_eq!(triplet, Triplet::new(2, 1, Complex::new(1., -1.)));
+        assert_eq!(<(usize, usize, Complex<f64>)>::from(triplet), tuple);
+    }
+
+    #[test]
+    fn test_collect_and_extend_triplets() {
+        let mut m = (0..3)
+            .map(|i| Triplet::new(i, i, Complex::new(i as f64, 0.)))
+            .collect::<ComplexMatrix<f64>>();
+        m.extend(vec![(0, 2, Complex::new(0., 1.))]);
+        m.add_elements(&[Triplet::new(2, 0, Complex::new(0., -1.))]);
+
+        let copy = ComplexMatrix::from_entries(m.triplets().collect());
+        assert_eq!(copy, m);
+        assert_eq!(m.triplets().count(), 5);
+        assert_eq!(m.get(0, 2), Some(&Complex::new(0., 1.)));
+    }
+}
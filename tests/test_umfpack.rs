@@ -0,0 +1,63 @@
+#![cfg(feature = "umfpack")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    /// The nodal admittance matrix of a ladder of ```n``` nodes with a voltage source
+    /// branch current as the last unknown, as in modified nodal analysis.
+    fn mna(n: usize) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::new();
+        let y = Complex::new(1., -2.);
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(0.1, 0.3));
+            if i + 1 < n {
+                m.add_element(i, i, y);
+                m.add_element(i + 1, i + 1, y);
+                m.add_element(i, i + 1, -y);
+                m.add_element(i + 1, i, -y);
+            }
+        }
+        m.add_element(0, n, Complex::new(1., 0.));
+        m.add_element(n, 0, Complex::new(1., 0.));
+        m
+    }
+
+    #[test]
+    fn test_umfpack_solve() {
+        let m = mna(30);
+        let lu = m.factorize_umfpack().unwrap();
+        assert_eq!(lu.size(), 31);
+        let b = (0..31).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+
+        let mut x = b.clone();
+        lu.solve(&mut x).unwrap();
+        assert!(m.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+
+        let mut adjoint = m.clone();
+        adjoint.transpose_in_place();
+        adjoint.conj_in_place();
+        let mut x = b.clone();
+        lu.solve_adjoint(&mut x).unwrap();
+        assert!(adjoint.residual_norm(&x, &b).unwrap().norm_inf < 1e-12);
+
+        let mut y = b.clone();
+        m.solve_with_backend(&UmfpackBackend, &mut y).unwrap();
+        let mut expected = b;
+        m.solve(&mut expected).unwrap();
+        assert!(y.iter().zip(&expected).all(|(a, e)| (a - e).norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_umfpack_errors() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 0.));
+        assert_eq!(m.factorize_umfpack().err(), Some(SolveError::Singular));
+
+        let lu = mna(3).factorize_umfpack().unwrap();
+        let mut short = vec![Complex::new(1., 0.); 3];
+        assert_eq!(lu.solve(&mut short), Err(SolveError::DimensionMismatch));
+    }
+}
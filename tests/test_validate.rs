@@ -0,0 +1,35 @@
+#![cfg(feature = "debug-validate")]
+
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    #[should_panic(expected = "debug-validate: the element (0, 0) is not finite")]
+    fn test_validate_rejects_non_finite_values() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(f64::NAN, 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        let mut b = vec![Complex::new(1., 0.); 2];
+        let _ = m.solve(&mut b);
+    }
+
+    #[test]
+    fn test_validate_reports_out_of_bounds_as_mismatch() {
+        // The length of b is checked before the triplets, so a short right-hand side
+        // is an error rather than a failed assertion.
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(2, 0, Complex::new(1., 0.));
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert_eq!(m.solve(&mut b), Err(SolveError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_validate_accepts_duplicates_before_compress() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 0, Complex::new(1., 0.));
+        assert_eq!(m.to_compressed().nnz(), 1);
+    }
+}
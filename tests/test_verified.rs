@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[test]
+    fn test_enclosure_contains_exact_solution() {
+        // Integer matrix and solution, so b is exact.
+        let entries = vec![
+            (0, 0, Complex::new(4., 1.)),
+            (0, 1, Complex::new(-1., 0.)),
+            (0, 2, Complex::new(0., 2.)),
+            (1, 0, Complex::new(-1., 0.)),
+            (1, 1, Complex::new(5., -2.)),
+            (2, 1, Complex::new(1., 1.)),
+            (2, 2, Complex::new(3., 0.)),
+        ];
+        let x = [Complex::new(1., 0.), Complex::new(0., 1.), Complex::new(2., -1.)];
+        let m = ComplexMatrix::<f64>::from_entries(entries.clone());
+
+        let mut b = vec![Complex::new(0., 0.); 3];
+        for (i, j, v) in entries {
+            b[i] += v * x[j];
+        }
+
+        let enclosures = m.solve_verified(&b).unwrap();
+        for (e, xi) in enclosures.iter().zip(x.iter()) {
+            assert!(e.contains(*xi));
+            assert!(e.radius < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_enclosure_f32() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(2., 0.));
+        m.add_element(1, 1, Complex::new(0., 4.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+
+        let b = [Complex::new(3., 0.), Complex::new(0., 4.)];
+        let enclosures = m.solve_verified(&b).unwrap();
+        assert!(enclosures[0].contains(Complex::new(1., 0.)));
+        assert!(enclosures[1].contains(Complex::new(1., 0.)));
+        assert!(enclosures[0].radius < 1e-5);
+    }
+
+    #[test]
+    fn test_singular_is_rejected() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1., 0.));
+        let b = [Complex::new(1., 0.), Complex::new(2., 0.)];
+        assert!(m.solve_verified(&b).is_err());
+    }
+
+    #[test]
+    fn test_ill_conditioned_is_rejected() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 1, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(1. + 1e-7, 0.));
+        let b = [Complex::new(1., 0.), Complex::new(2., 0.)];
+        assert!(m.solve_verified(&b).is_err());
+    }
+}
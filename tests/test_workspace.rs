@@ -0,0 +1,85 @@
+#![cfg(feature = "eigen")]
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    #[cfg(feature = "f64")]
+    fn tridiagonal(n: usize, shift: f64) -> ComplexMatrix<f64> {
+        let mut m = ComplexMatrix::<f64>::new();
+        for i in 0..n {
+            m.add_element(i, i, Complex::new(4., shift));
+            if i + 1 < n {
+                m.add_element(i, i + 1, Complex::new(-1., 0.));
+                m.add_element(i + 1, i, Complex::new(-1., 0.));
+            }
+        }
+        m
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_in_place_matches_solve() {
+        let mut workspace = SolveWorkspace::<f64>::new().unwrap();
+        for (n, shift) in [(5, 0.5), (5, 1.5), (8, -1.), (5, 0.)] {
+            let m = tridiagonal(n, shift);
+            let rhs = (0..n).map(|i| Complex::new(i as f64, 1.)).collect::<Vec<_>>();
+
+            let mut expected = rhs.clone();
+            m.solve(&mut expected).unwrap();
+            let mut b = rhs;
+            m.solve_in_place(&mut workspace, &mut b).unwrap();
+            for (x, y) in b.iter().zip(expected.iter()) {
+                assert!((x - y).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    #[test]
+    fn test_solve_in_place_sums_duplicates() {
+        let mut m = ComplexMatrix::<f32>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 1, Complex::new(0., 1.));
+
+        let mut workspace = SolveWorkspace::<f32>::new().unwrap();
+        let mut b = vec![Complex::new(2., 0.), Complex::new(0., 1.)];
+        m.solve_in_place(&mut workspace, &mut b).unwrap();
+        assert_eq!(b, vec![Complex::new(1., 0.), Complex::new(1., 0.)]);
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_solve_in_place_singular() {
+        let mut m = ComplexMatrix::<f64>::new();
+        m.add_element(0, 0, Complex::new(1., 0.));
+        m.add_element(1, 0, Complex::new(1., 0.));
+
+        let mut workspace = SolveWorkspace::<f64>::new().unwrap();
+        let mut b = vec![Complex::new(1., 0.); 2];
+        assert!(m.solve_in_place(&mut workspace, &mut b).is_err());
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_inverse_pow_apply_inverts_pow_apply() {
+        let m = tridiagonal(6, 0.5);
+        let x = (0..6).map(|i| Complex::new(1., i as f64)).collect::<Vec<_>>();
+        let y = m.inverse_pow_apply(3, &m.pow_apply(3, &x)).unwrap();
+        for (yi, xi) in y.iter().zip(x.iter()) {
+            assert!((yi - xi).norm() < 1e-10);
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn test_workspace_memory_usage_grows_with_factors() {
+        let mut workspace = SolveWorkspace::<f64>::new().unwrap();
+        let empty = workspace.memory_usage_bytes();
+        let m = tridiagonal(50, 1.);
+        let mut b = vec![Complex::new(1., 0.); 50];
+        m.solve_in_place(&mut workspace, &mut b).unwrap();
+        assert!(workspace.memory_usage_bytes() > empty + 3 * 50 * 16);
+    }
+}
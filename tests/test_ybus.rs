@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use num::Complex;
+    use sparse_complex::*;
+
+    fn close(a: Option<&Complex<f64>>, b: Complex<f64>) -> bool {
+        (a.unwrap() - b).norm() < 1e-12
+    }
+
+    #[test]
+    fn test_line_pi_model() {
+        let z = Complex::new(0.01, 0.1);
+        let ys = z.inv();
+        let branches = [Branch::line(0, 1, z, 0.2)];
+        let y = ComplexMatrix::<f64>::admittance_from_branches(2, &branches, &[]);
+
+        assert!(close(y.get(0, 0), ys + Complex::new(0., 0.1)));
+        assert!(close(y.get(1, 1), ys + Complex::new(0., 0.1)));
+        assert!(close(y.get(0, 1), -ys));
+        assert!(close(y.get(1, 0), -ys));
+    }
+
+    #[test]
+    fn test_phase_shifting_transformer() {
+        let z = Complex::new(0., 0.2);
+        let ys = z.inv();
+        let (tap, shift) = (1.05, 0.1);
+        let t = Complex::from_polar(tap, shift);
+        let branches = [Branch::transformer(2, 0, z, tap, shift)];
+        let shunts = [Shunt::new(1, Complex::new(0., 0.3))];
+        let y = ComplexMatrix::<f64>::admittance_from_branches(3, &branches, &shunts);
+
+        assert!(close(y.get(2, 2), ys / (tap * tap)));
+        assert!(close(y.get(0, 0), ys));
+        assert!(close(y.get(2, 0), -ys / t.conj()));
+        assert!(close(y.get(0, 2), -ys / t));
+        assert!(close(y.get(1, 1), Complex::new(0., 0.3)));
+        // A phase shifter makes the matrix non-symmetric.
+        assert!((y.get(2, 0).unwrap() - y.get(0, 2).unwrap()).norm() > 1e-3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_branch_out_of_bounds() {
+        let branches = [Branch::line(0, 3, Complex::new(0., 1.), 0.)];
+        ComplexMatrix::<f64>::admittance_from_branches(3, &branches, &[]);
+    }
+}